@@ -0,0 +1,94 @@
+//! Hashrate of the miner. Compare runs with `cargo bench --features desktop --no-default-features`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use solana_sdk::{keccak::Hash as KeccakHash, pubkey::Pubkey};
+
+#[allow(dead_code)]
+#[path = "../src/hashing/mod.rs"]
+mod hashing;
+
+use hashing::{hash_nonce, partition, search, BATCH_SIZE};
+
+/// No hash meets it, so every nonce in the range is hashed.
+const IMPOSSIBLE: KeccakHash = KeccakHash::new_from_array([0; 32]);
+
+fn single_hash(c: &mut Criterion) {
+    let challenge = KeccakHash::new_unique();
+    let signer = Pubkey::new_unique();
+    let mut group = c.benchmark_group("hash");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("hash_nonce", |b| {
+        let mut nonce = 0u64;
+        b.iter(|| {
+            nonce = nonce.wrapping_add(1);
+            hash_nonce(black_box(&challenge), black_box(&signer), black_box(nonce))
+        })
+    });
+    group.finish();
+}
+
+/// The reference hash, one nonce at a time, to compare the kernel against.
+fn reference_batch(c: &mut Criterion) {
+    let challenge = KeccakHash::new_unique();
+    let signer = Pubkey::new_unique();
+    let mut group = c.benchmark_group("search");
+    group.throughput(Throughput::Elements(BATCH_SIZE));
+    group.bench_function("reference", |b| {
+        b.iter(|| {
+            (0..BATCH_SIZE).find(|nonce| hash_nonce(&challenge, &signer, *nonce).le(&IMPOSSIBLE))
+        })
+    });
+    group.finish();
+}
+
+fn search_batch(c: &mut Criterion) {
+    let challenge = KeccakHash::new_unique();
+    let signer = Pubkey::new_unique();
+    let mut group = c.benchmark_group("search");
+    group.throughput(Throughput::Elements(BATCH_SIZE));
+    group.bench_function("batch", |b| {
+        b.iter(|| search(&challenge, &signer, &IMPOSSIBLE, 0..BATCH_SIZE, |_| true))
+    });
+    group.finish();
+}
+
+/// Total throughput with one search per thread. Divide by the thread count for the rate per thread.
+fn search_threads(c: &mut Criterion) {
+    let challenge = KeccakHash::new_unique();
+    let signer = Pubkey::new_unique();
+    let mut group = c.benchmark_group("threads");
+    let max_threads = std::thread::available_parallelism()
+        .map(|n| n.get() as u64)
+        .unwrap_or(1);
+    let mut threads = 1;
+    while threads <= max_threads {
+        group.throughput(Throughput::Elements(threads * BATCH_SIZE));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    std::thread::scope(|s| {
+                        for nonces in partition(threads) {
+                            let nonces = nonces.start..nonces.start + BATCH_SIZE;
+                            s.spawn(move || {
+                                search(&challenge, &signer, &IMPOSSIBLE, nonces, |_| true)
+                            });
+                        }
+                    })
+                })
+            },
+        );
+        threads *= 2;
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    single_hash,
+    reference_batch,
+    search_batch,
+    search_threads
+);
+criterion_main!(benches);
@@ -1,5 +1,26 @@
-import init from "/./assets/dioxus/Mars.js";
+// A module that only validates where wasm SIMD is supported
+const SIMD_PROBE = new Uint8Array([
+  0, 97, 115, 109, 1, 0, 0, 0, 1, 5, 1, 96, 0, 1, 123, 3, 2, 1, 0, 10, 10, 1, 8, 0, 65, 0, 253, 15,
+  253, 98, 11,
+]);
 
-init("/./assets/dioxus/Mars_bg.wasm").then(wasm => {
+async function load(dir) {
+  const { default: init } = await import(`/./assets/dioxus/${dir}Mars.js`);
+  return init(`/./assets/dioxus/${dir}Mars_bg.wasm`);
+}
+
+async function start() {
+  if (WebAssembly.validate(SIMD_PROBE)) {
+    try {
+      return await load("simd/");
+    } catch (err) {
+      // Not built, e.g. when serving locally
+      console.warn("SIMD worker unavailable, falling back to the scalar worker", err);
+    }
+  }
+  return load("");
+}
+
+start().then(wasm => {
   wasm.start_worker();
 });
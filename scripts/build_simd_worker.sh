@@ -0,0 +1,15 @@
+#!/usr/bin/env bash
+# Builds the app again with wasm SIMD, for the web worker. Run after `dx build --release`.
+# public/worker.js loads this build where the browser supports SIMD, and the regular one otherwise.
+# Needs wasm-bindgen-cli at the version of the wasm-bindgen crate in Cargo.lock.
+set -euo pipefail
+
+OUT_DIR="${1:-dist/assets/dioxus/simd}"
+TARGET_DIR="target/simd"
+
+# Replaces the flags in .cargo/config.toml, so they are repeated here
+RUSTFLAGS="--cfg=web_sys_unstable_apis -C target-feature=+simd128" \
+    cargo build --release --target wasm32-unknown-unknown --target-dir "$TARGET_DIR"
+
+wasm-bindgen --target web --no-typescript --out-dir "$OUT_DIR" --out-name Mars \
+    "$TARGET_DIR/wasm32-unknown-unknown/release/mars-app.wasm"
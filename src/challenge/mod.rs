@@ -0,0 +1,67 @@
+//! The challenge a miner searches: its proof hash and the treasury difficulty. Either can change
+//! on chain while a search runs, which makes any solution for the old one fail.
+
+#[cfg(all(test, feature = "desktop"))]
+mod tests;
+
+use std::time::Duration;
+
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::{keccak::Hash as KeccakHash, pubkey::Pubkey};
+#[cfg(feature = "desktop")]
+use solana_sdk::{keccak::Hash as KeccakHash, pubkey::Pubkey};
+
+use crate::gateway::{Gateway, GatewayResult};
+
+/// How often the proof and treasury are checked while searching.
+pub const CHALLENGE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Challenge {
+    pub hash: KeccakHash,
+    pub difficulty: KeccakHash,
+}
+
+impl Challenge {
+    /// Fetches the current challenge of an authority.
+    pub async fn fetch(gateway: &Gateway, authority: Pubkey) -> GatewayResult<Self> {
+        let treasury = gateway.get_treasury().await?;
+        let proof = gateway.get_proof(authority).await?;
+        Ok(Self {
+            hash: proof.hash.into(),
+            difficulty: treasury.difficulty.into(),
+        })
+    }
+
+    /// Compares the challenge being mined with the latest one. `solved` is the last challenge
+    /// this miner solved, whose proof update may show up late.
+    pub fn staleness(&self, latest: &Challenge, solved: Option<KeccakHash>) -> Staleness {
+        if self.hash.ne(&latest.hash) {
+            if solved.eq(&Some(latest.hash)) {
+                // A lagging read still shows the challenge this miner already moved on from
+                Staleness::Fresh
+            } else if solved.eq(&Some(self.hash)) {
+                Staleness::OwnSolution
+            } else {
+                Staleness::ExternalSolution
+            }
+        } else if self.difficulty.ne(&latest.difficulty) {
+            Staleness::Difficulty
+        } else {
+            Staleness::Fresh
+        }
+    }
+}
+
+/// Why a challenge no longer holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Staleness {
+    Fresh,
+    /// The treasury difficulty changed.
+    Difficulty,
+    /// The search restarted on a challenge this miner had already solved.
+    OwnSolution,
+    /// The proof was updated by a solution from somewhere else, such as another machine
+    /// mining with the same keypair.
+    ExternalSolution,
+}
@@ -0,0 +1,69 @@
+use solana_sdk::keccak::Hash as KeccakHash;
+
+use super::{Challenge, Staleness};
+
+fn challenge() -> Challenge {
+    Challenge {
+        hash: KeccakHash::new_unique(),
+        difficulty: KeccakHash::new_unique(),
+    }
+}
+
+#[test]
+fn unchanged_challenge_is_fresh() {
+    let mining = challenge();
+    assert_eq!(mining.staleness(&mining, None), Staleness::Fresh);
+    assert_eq!(
+        mining.staleness(&mining, Some(mining.hash)),
+        Staleness::Fresh
+    );
+}
+
+#[test]
+fn detects_new_difficulty() {
+    let mining = challenge();
+    let latest = Challenge {
+        difficulty: KeccakHash::new_unique(),
+        ..mining
+    };
+    assert_eq!(mining.staleness(&latest, None), Staleness::Difficulty);
+}
+
+#[test]
+fn detects_solution_from_elsewhere() {
+    let mining = challenge();
+    let latest = Challenge {
+        hash: KeccakHash::new_unique(),
+        ..mining
+    };
+    assert_eq!(mining.staleness(&latest, None), Staleness::ExternalSolution);
+    let solved = Some(KeccakHash::new_unique());
+    assert_eq!(
+        mining.staleness(&latest, solved),
+        Staleness::ExternalSolution
+    );
+}
+
+#[test]
+fn late_proof_update_is_own_solution() {
+    let mining = challenge();
+    let latest = Challenge {
+        hash: KeccakHash::new_unique(),
+        difficulty: KeccakHash::new_unique(),
+    };
+    assert_eq!(
+        mining.staleness(&latest, Some(mining.hash)),
+        Staleness::OwnSolution
+    );
+}
+
+#[test]
+fn lagging_read_of_solved_challenge_is_fresh() {
+    let solved = KeccakHash::new_unique();
+    let mining = challenge();
+    let latest = Challenge {
+        hash: solved,
+        difficulty: mining.difficulty,
+    };
+    assert_eq!(mining.staleness(&latest, Some(solved)), Staleness::Fresh);
+}
@@ -6,11 +6,15 @@ use web_time::{Duration, SystemTime, UNIX_EPOCH};
 
 use dioxus::prelude::*;
 use ore_types::{Transfer, TransferType};
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::native_token::lamports_to_sol;
+#[cfg(feature = "desktop")]
+use solana_sdk::native_token::lamports_to_sol;
 
 use crate::{
-    components::{GlobeIcon, MarsIcon, UserBubble, UserIcon},
-    gateway::AsyncResult,
-    hooks::{use_pubkey, use_transfers, ACTIVITY_TABLE_PAGE_LIMIT},
+    components::{ActivityLoadMore, ActivitySearch, GlobeIcon, MarsIcon, UserBubble, UserIcon},
+    gateway::{tip_log, AsyncResult, SortOrder, TransferQuery, TransferSort},
+    hooks::{use_pubkey, use_transfer_search, TransferSearch, ACTIVITY_TABLE_PAGE_LIMIT},
     route::Route,
 };
 
@@ -23,36 +27,46 @@ pub enum ActivityFilter {
 #[component]
 pub fn Activity(cx: Scope) -> Element {
     let filter = use_state(cx, || ActivityFilter::Global);
-    let offset = use_state(cx, || 0u64);
-    let (transfers, has_more) = use_transfers(cx, filter, offset);
-    match transfers {
-        AsyncResult::Ok(transfers) => {
-            render! {
-                div {
-                    class: "flex flex-col gap-4 grow w-full h-2/3 pb-20 min-h-16 rounded justify-start",
-                    div {
-                        class: "flex flex-row justify-between",
-                        h2 {
-                            class: "text-lg md:text-2xl font-bold my-auto",
-                            "Activity"
-                        }
-                        FilterButtons {
-                            filter: filter,
-                            offset: offset
+    let query = use_state(cx, TransferQuery::default);
+    let search = use_transfer_search(cx, query);
+    render! {
+        div {
+            class: "flex flex-col gap-4 grow w-full h-2/3 pb-20 min-h-16 rounded justify-start",
+            div {
+                class: "flex flex-row justify-between",
+                h2 {
+                    class: "text-lg md:text-2xl font-bold my-auto",
+                    "Activity"
+                }
+                FilterButtons {
+                    filter: filter,
+                    query: query
+                }
+            }
+            ActivitySearch {
+                query: query
+            }
+            match search.transfers() {
+                AsyncResult::Ok(transfers) => {
+                    render! {
+                        ActivityList {
+                            query: query,
+                            transfers: transfers,
+                            search: &search
                         }
                     }
-                    ActivityTable{
-                        offset: offset,
-                        transfers: transfers,
-                        has_more: has_more
+                }
+                _ => {
+                    render! {
+                        div {
+                            class: "flex flex-row h-64 w-full loading rounded",
+                        }
                     }
                 }
             }
-        }
-        _ => {
-            render! {
-                div {
-                    class: "flex flex-row h-64 w-full loading rounded",
+            if let ActivityFilter::Personal = filter.get() {
+                render! {
+                    TipHistory {}
                 }
             }
         }
@@ -62,12 +76,13 @@ pub fn Activity(cx: Scope) -> Element {
 #[derive(Props, PartialEq)]
 pub struct FilterButtonsProps<'a> {
     pub filter: &'a UseState<ActivityFilter>,
-    pub offset: &'a UseState<u64>,
+    pub query: &'a UseState<TransferQuery>,
 }
 
 #[component]
 pub fn FilterButtons<'a>(cx: Scope<'a, FilterButtonsProps<'a>>) -> Element {
-    let offset = cx.props.offset;
+    let pubkey = use_pubkey(cx);
+    let query = cx.props.query;
     let filter = cx.props.filter.clone();
     let filter_ = cx.props.filter;
     let selected_class = "";
@@ -86,7 +101,7 @@ pub fn FilterButtons<'a>(cx: Scope<'a, FilterButtonsProps<'a>>) -> Element {
                 class: "{button_class} {personal_class}",
                 onclick: move |_e| {
                     filter_.set(ActivityFilter::Personal);
-                    offset.set(0);
+                    query.modify(|q| TransferQuery { user: Some(pubkey), ..q.clone() });
                 },
                 UserIcon {
                     class: "{icon_class}"
@@ -97,7 +112,7 @@ pub fn FilterButtons<'a>(cx: Scope<'a, FilterButtonsProps<'a>>) -> Element {
                 class: "{button_class} {global_class}",
                 onclick: move |_| {
                     filter.set(ActivityFilter::Global);
-                    offset.set(0);
+                    query.modify(|q| TransferQuery { user: None, ..q.clone() });
                 },
                 GlobeIcon {
                     class: "{icon_class}"
@@ -108,6 +123,86 @@ pub fn FilterButtons<'a>(cx: Scope<'a, FilterButtonsProps<'a>>) -> Element {
     }
 }
 
+#[derive(Props)]
+pub struct ActivityListProps<'a> {
+    pub query: &'a UseState<TransferQuery>,
+    pub transfers: Vec<Transfer>,
+    pub search: &'a TransferSearch,
+}
+
+#[component]
+pub fn ActivityList<'a>(cx: Scope<'a, ActivityListProps<'a>>) -> Element {
+    let query = cx.props.query;
+    let transfers = cx.props.transfers.clone();
+    let search = cx.props.search;
+    let sort_class = "flex flex-row gap-1 px-2 py-1 rounded text-xs font-semibold hover-100 active-200 transition-colors";
+    let arrow = match query.get().order {
+        SortOrder::Desc => "↓",
+        SortOrder::Asc => "↑",
+    };
+    let (time_arrow, amount_arrow) = match query.get().sort {
+        TransferSort::Time => (arrow, ""),
+        TransferSort::Amount => ("", arrow),
+    };
+    render! {
+        div {
+            class: "flex flex-col gap-2",
+            div {
+                class: "flex flex-row gap-2 justify-end -mx-2",
+                button {
+                    class: "{sort_class}",
+                    onclick: move |_| sort_by(query, TransferSort::Time),
+                    "Time {time_arrow}"
+                }
+                button {
+                    class: "{sort_class}",
+                    onclick: move |_| sort_by(query, TransferSort::Amount),
+                    "Amount {amount_arrow}"
+                }
+            }
+            if transfers.is_empty() && !search.has_more() {
+                render! {
+                    p {
+                        class: "text-sm text-gray-300 py-2 sm:px-1",
+                        "No transactions found"
+                    }
+                }
+            } else {
+                render! {
+                    div {
+                        class: "h-full w-full max-w-full -mx-2 sm:mx-0",
+                        for transfer in transfers {
+                            render! {
+                                ActivityRow {
+                                    transfer: transfer
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            ActivityLoadMore {
+                search: search
+            }
+        }
+    }
+}
+
+fn sort_by(query: &UseState<TransferQuery>, sort: TransferSort) {
+    query.modify(|q| {
+        let order = if q.sort.eq(&sort) {
+            q.order.toggle()
+        } else {
+            SortOrder::Desc
+        };
+        TransferQuery {
+            sort,
+            order,
+            ..q.clone()
+        }
+    });
+}
+
 #[derive(Props, PartialEq)]
 pub struct ActivityTableProps<'a> {
     pub offset: &'a UseState<u64>,
@@ -202,22 +297,7 @@ pub fn ActivityRow(cx: Scope<ActivityRowProps>) -> Element {
     let amount = (transfer.amount as f64) / 10f64.powf(mars::TOKEN_DECIMALS as f64);
     let pubkey = use_pubkey(cx);
 
-    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
-    let ts = Duration::from_secs(transfer.ts as u64);
-    let time = now.saturating_sub(ts);
-    let t = time.as_secs();
-    const ONE_MIN: u64 = 60;
-    const ONE_HOUR: u64 = ONE_MIN * 60;
-    const ONE_DAY: u64 = ONE_HOUR * 24;
-    let time_str = if t.gt(&ONE_DAY) {
-        format!("{}d ago", t.saturating_div(ONE_DAY))
-    } else if t.gt(&ONE_HOUR) {
-        format!("{}h ago", t.saturating_div(ONE_HOUR))
-    } else if t.gt(&ONE_MIN) {
-        format!("{}m ago", t.saturating_div(ONE_MIN))
-    } else {
-        format!("{}s ago", t)
-    };
+    let time_str = time_ago(transfer.ts as u64);
 
     let action = match transfer.transfer_type {
         TransferType::Claim => "claimed",
@@ -330,3 +410,76 @@ pub fn ActivityRow(cx: Scope<ActivityRowProps>) -> Element {
         }
     }
 }
+
+/// Tips sent to the default rpc, recorded locally since they are not Mars transfers.
+#[component]
+pub fn TipHistory(cx: Scope) -> Element {
+    // Read from storage once, not on every render
+    let log = use_state(cx, tip_log);
+    let tips: Vec<_> = log
+        .recent
+        .iter()
+        .take(ACTIVITY_TABLE_PAGE_LIMIT)
+        .cloned()
+        .collect();
+    if tips.is_empty() {
+        return None;
+    }
+    render! {
+        div {
+            class: "flex flex-col gap-2 mt-8",
+            h2 {
+                class: "text-lg md:text-2xl font-bold",
+                "Tips"
+            }
+            div {
+                class: "h-full w-full max-w-full -mx-2 sm:mx-0",
+                for tip in tips {
+                    render! {
+                        Link {
+                            class: "flex flex-col gap-0.5 py-3 w-full px-2 rounded hover-100 active-200 transition-colors",
+                            to: Route::Tx { sig: tip.signature.clone() },
+                            p {
+                                span {
+                                    class: "font-bold",
+                                    "You"
+                                }
+                                " tipped {lamports_to_sol(tip.amount)} SOL"
+                                if tip.unconfirmed {
+                                    render! {
+                                        span {
+                                            class: "opacity-50",
+                                            " (unconfirmed)"
+                                        }
+                                    }
+                                }
+                            }
+                            p {
+                                class: "opacity-50 text-nowrap text-sm",
+                                "{time_ago(tip.timestamp)}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn time_ago(ts: u64) -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let time = now.saturating_sub(Duration::from_secs(ts));
+    let t = time.as_secs();
+    const ONE_MIN: u64 = 60;
+    const ONE_HOUR: u64 = ONE_MIN * 60;
+    const ONE_DAY: u64 = ONE_HOUR * 24;
+    if t.gt(&ONE_DAY) {
+        format!("{}d ago", t.saturating_div(ONE_DAY))
+    } else if t.gt(&ONE_HOUR) {
+        format!("{}h ago", t.saturating_div(ONE_HOUR))
+    } else if t.gt(&ONE_MIN) {
+        format!("{}m ago", t.saturating_div(ONE_MIN))
+    } else {
+        format!("{}s ago", t)
+    }
+}
@@ -0,0 +1,283 @@
+use chrono::{Local, NaiveDate, NaiveTime};
+use dioxus::prelude::*;
+
+use crate::{
+    components::SearchIcon,
+    gateway::{TransferDirection, TransferQuery},
+    hooks::TransferSearch,
+};
+
+#[derive(Props)]
+pub struct ActivitySearchProps<'a> {
+    pub query: &'a UseState<TransferQuery>,
+}
+
+#[component]
+pub fn ActivitySearch<'a>(cx: Scope<'a, ActivitySearchProps<'a>>) -> Element {
+    let query = cx.props.query;
+    let show_filters = use_state(cx, || false);
+    let input_class = "w-full bg-transparent text-sm py-1 placeholder-gray-300 dark:placeholder-gray-700 outline-none";
+    let field_class = "flex flex-col gap-1 w-full";
+    let label_class = "text-xs font-medium opacity-50";
+    let filters_label = if *show_filters.get() {
+        "Hide filters"
+    } else {
+        "Filters"
+    };
+    render! {
+        div {
+            class: "flex flex-col gap-4",
+            div {
+                class: "flex flex-row gap-2 w-full border-b border-gray-200 dark:border-gray-800",
+                SearchIcon {
+                    class: "w-4 h-4 my-auto opacity-50"
+                }
+                input {
+                    class: "{input_class}",
+                    placeholder: "Search memos",
+                    value: "{query.get().memo.clone().unwrap_or_default()}",
+                    oninput: move |e| {
+                        let memo = non_empty(&e.value);
+                        query.modify(|q| TransferQuery { memo, ..q.clone() });
+                    }
+                }
+                button {
+                    class: "text-xs font-semibold px-2 py-1 rounded hover-100 active-200 transition-colors text-nowrap",
+                    onclick: move |_| show_filters.set(!*show_filters.get()),
+                    "{filters_label}"
+                }
+            }
+            if *show_filters.get() {
+                render! {
+                    div {
+                        class: "grid grid-cols-1 sm:grid-cols-2 gap-4",
+                        div {
+                            class: "{field_class}",
+                            p {
+                                class: "{label_class}",
+                                "Address"
+                            }
+                            input {
+                                class: "{input_class} font-mono",
+                                placeholder: "Any",
+                                value: "{query.get().address.clone().unwrap_or_default()}",
+                                oninput: move |e| {
+                                    let address = non_empty(&e.value);
+                                    query.modify(|q| TransferQuery { address, ..q.clone() });
+                                }
+                            }
+                        }
+                        div {
+                            class: "{field_class}",
+                            p {
+                                class: "{label_class}",
+                                "Direction"
+                            }
+                            select {
+                                class: "{input_class} hover:cursor-pointer dark:text-white",
+                                onchange: move |e| {
+                                    let direction = match e.value.as_str() {
+                                        "in" => TransferDirection::Incoming,
+                                        "out" => TransferDirection::Outgoing,
+                                        _ => TransferDirection::Any,
+                                    };
+                                    query.modify(|q| TransferQuery { direction, ..q.clone() });
+                                },
+                                option { initial_selected: query.get().direction.eq(&TransferDirection::Any), value: "any", "Any" }
+                                option { initial_selected: query.get().direction.eq(&TransferDirection::Incoming), value: "in", "Incoming" }
+                                option { initial_selected: query.get().direction.eq(&TransferDirection::Outgoing), value: "out", "Outgoing" }
+                            }
+                        }
+                        div {
+                            class: "{field_class}",
+                            p {
+                                class: "{label_class}",
+                                "Min amount"
+                            }
+                            input {
+                                class: "{input_class}",
+                                r#type: "number",
+                                min: 0,
+                                placeholder: "0",
+                                oninput: move |e| {
+                                    let min_amount = parse_amount(&e.value);
+                                    query.modify(|q| TransferQuery { min_amount, ..q.clone() });
+                                }
+                            }
+                        }
+                        div {
+                            class: "{field_class}",
+                            p {
+                                class: "{label_class}",
+                                "Max amount"
+                            }
+                            input {
+                                class: "{input_class}",
+                                r#type: "number",
+                                min: 0,
+                                placeholder: "Any",
+                                oninput: move |e| {
+                                    let max_amount = parse_amount(&e.value);
+                                    query.modify(|q| TransferQuery { max_amount, ..q.clone() });
+                                }
+                            }
+                        }
+                        div {
+                            class: "{field_class}",
+                            p {
+                                class: "{label_class}",
+                                "From"
+                            }
+                            input {
+                                class: "{input_class}",
+                                r#type: "date",
+                                oninput: move |e| {
+                                    let start_of_day = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+                                    let start_ts = parse_date(&e.value, start_of_day);
+                                    query.modify(|q| TransferQuery { start_ts, ..q.clone() });
+                                }
+                            }
+                        }
+                        div {
+                            class: "{field_class}",
+                            p {
+                                class: "{label_class}",
+                                "To"
+                            }
+                            input {
+                                class: "{input_class}",
+                                r#type: "date",
+                                oninput: move |e| {
+                                    let end_of_day = NaiveTime::from_hms_opt(23, 59, 59).unwrap();
+                                    let end_ts = parse_date(&e.value, end_of_day);
+                                    query.modify(|q| TransferQuery { end_ts, ..q.clone() });
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Props)]
+pub struct ActivityLoadMoreProps<'a> {
+    pub search: &'a TransferSearch,
+}
+
+/// Loads the next page when scrolled into view on web. Desktop falls back to a button.
+#[component]
+pub fn ActivityLoadMore<'a>(cx: Scope<'a, ActivityLoadMoreProps<'a>>) -> Element {
+    let search = cx.props.search;
+    if search.is_loading() {
+        return render! {
+            div {
+                class: "flex flex-row h-16 w-full loading rounded",
+            }
+        };
+    }
+    if !search.has_more() {
+        return None;
+    }
+    render! {
+        ActivitySentinel {
+            search: search
+        }
+    }
+}
+
+/// Disconnects the observer when the sentinel unmounts, so it stops loading for a dead search.
+#[cfg(feature = "web")]
+struct SentinelObserver {
+    observer: web_sys::IntersectionObserver,
+    _callback: wasm_bindgen::prelude::Closure<dyn FnMut(js_sys::Array)>,
+}
+
+#[cfg(feature = "web")]
+impl Drop for SentinelObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+#[cfg(feature = "web")]
+#[component]
+fn ActivitySentinel<'a>(cx: Scope<'a, ActivityLoadMoreProps<'a>>) -> Element {
+    use wasm_bindgen::{prelude::Closure, JsCast};
+    use web_sys::{IntersectionObserver, IntersectionObserverEntry};
+
+    const ID: &str = "activity-sentinel";
+    let observer = use_ref::<Option<SentinelObserver>>(cx, || None);
+
+    // Mounted fresh after every page load, so the observer's initial callback
+    // continues loading while the sentinel is still on screen
+    use_effect(cx, (), |_| {
+        let search = cx.props.search.clone();
+        let observer = observer.clone();
+        async move {
+            let Some(el) = gloo::utils::document().get_element_by_id(ID) else {
+                return;
+            };
+            let callback =
+                Closure::<dyn FnMut(js_sys::Array)>::new(move |entries: js_sys::Array| {
+                    let is_visible = entries.iter().any(|entry| {
+                        entry
+                            .unchecked_into::<IntersectionObserverEntry>()
+                            .is_intersecting()
+                    });
+                    if is_visible {
+                        search.load_more();
+                    }
+                });
+            if let Ok(io) = IntersectionObserver::new(callback.as_ref().unchecked_ref()) {
+                io.observe(&el);
+                *observer.write_silent() = Some(SentinelObserver {
+                    observer: io,
+                    _callback: callback,
+                });
+            }
+        }
+    });
+
+    render! {
+        div {
+            id: ID,
+            class: "h-1 w-full",
+        }
+    }
+}
+
+#[cfg(feature = "desktop")]
+#[component]
+fn ActivitySentinel<'a>(cx: Scope<'a, ActivityLoadMoreProps<'a>>) -> Element {
+    let search = cx.props.search;
+    render! {
+        button {
+            class: "w-full py-2 rounded text-sm font-semibold hover-100 active-200 transition-colors",
+            onclick: move |_| search.load_more(),
+            "Load more"
+        }
+    }
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.to_string())
+    }
+}
+
+fn parse_amount(s: &str) -> Option<u64> {
+    s.parse::<f64>()
+        .ok()
+        .map(|n| (n * 10f64.powf(mars::TOKEN_DECIMALS.into())) as u64)
+}
+
+fn parse_date(s: &str, time: NaiveTime) -> Option<i64> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_time(time).and_local_timezone(Local).earliest())
+        .map(|datetime| datetime.timestamp())
+}
@@ -0,0 +1,203 @@
+use chrono::{Local, Offset, TimeZone};
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+
+/// Candidate spacings between time ticks, in seconds.
+const TIME_INTERVALS: [f64; 12] = [
+    MINUTE,
+    5.0 * MINUTE,
+    15.0 * MINUTE,
+    HOUR,
+    3.0 * HOUR,
+    6.0 * HOUR,
+    12.0 * HOUR,
+    DAY,
+    2.0 * DAY,
+    7.0 * DAY,
+    30.0 * DAY,
+    365.0 * DAY,
+];
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AxisScale {
+    #[default]
+    Linear,
+    /// Base 10. Non-positive values are not plotted.
+    Log,
+    /// Values are unix timestamps.
+    Time,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tick {
+    /// Position along the axis, in chart coordinates.
+    pub pos: f64,
+    pub label: String,
+}
+
+/// Maps values from a data domain onto an axis of the given length.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Scale {
+    kind: AxisScale,
+    min: f64,
+    max: f64,
+    length: f64,
+    /// Maps larger values to smaller positions, as on a y axis.
+    inverted: bool,
+}
+
+impl Scale {
+    pub fn new(kind: AxisScale, min: f64, max: f64, length: f64, inverted: bool) -> Self {
+        let (mut min, mut max) = match kind {
+            AxisScale::Log => (
+                min.max(f64::MIN_POSITIVE).log10(),
+                max.max(f64::MIN_POSITIVE).log10(),
+            ),
+            _ => (min, max),
+        };
+        // Avoid dividing by zero when every point has the same value
+        if max.eq(&min) {
+            min -= 0.5;
+            max += 0.5;
+        }
+        Self {
+            kind,
+            min,
+            max,
+            length,
+            inverted,
+        }
+    }
+
+    pub fn map(&self, value: f64) -> f64 {
+        let value = match self.kind {
+            AxisScale::Log => value.max(f64::MIN_POSITIVE).log10(),
+            _ => value,
+        };
+        let pos = ((value - self.min) * self.length) / (self.max - self.min);
+        if self.inverted {
+            self.length - pos
+        } else {
+            pos
+        }
+    }
+
+    /// Returns roughly `count` evenly spaced ticks at round values within the domain.
+    pub fn ticks(&self, count: usize) -> Vec<Tick> {
+        let count = count.max(1) as f64;
+        match self.kind {
+            AxisScale::Linear => {
+                let step = nice_step((self.max - self.min) / count);
+                steps(self.min, self.max, step)
+                    .into_iter()
+                    .map(|v| self.tick(v, format_value(v)))
+                    .collect()
+            }
+            AxisScale::Log => {
+                // One tick per power of ten, thinned out over wide ranges
+                let stride = ((self.max - self.min) / count).ceil().max(1.0);
+                steps(self.min.floor(), self.max, stride)
+                    .into_iter()
+                    .filter(|exp| exp.ge(&self.min))
+                    .map(|exp| {
+                        let v = 10f64.powf(exp);
+                        self.tick(v, format_value(v))
+                    })
+                    .collect()
+            }
+            AxisScale::Time => {
+                let span = self.max - self.min;
+                let interval = TIME_INTERVALS
+                    .into_iter()
+                    .find(|i| (span / i).le(&count))
+                    .unwrap_or(TIME_INTERVALS[TIME_INTERVALS.len() - 1]);
+                // Align day-long intervals to local midnight
+                let offset = if interval.ge(&DAY) {
+                    Local
+                        .timestamp_opt(self.min as i64, 0)
+                        .single()
+                        .map(|dt| dt.offset().fix().local_minus_utc() as f64)
+                        .unwrap_or(0.0)
+                } else {
+                    0.0
+                };
+                let format = if interval.lt(&DAY) {
+                    "%H:%M"
+                } else if interval.lt(&(30.0 * DAY)) {
+                    "%b %d"
+                } else if interval.lt(&(365.0 * DAY)) {
+                    "%b %Y"
+                } else {
+                    "%Y"
+                };
+                steps(self.min + offset, self.max + offset, interval)
+                    .into_iter()
+                    .map(|t| self.tick(t - offset, format_date(t - offset, format)))
+                    .collect()
+            }
+        }
+    }
+
+    fn tick(&self, value: f64, label: String) -> Tick {
+        Tick {
+            pos: self.map(value),
+            label,
+        }
+    }
+}
+
+/// Multiples of `step` between `min` and `max`.
+fn steps(min: f64, max: f64, step: f64) -> Vec<f64> {
+    let mut v = (min / step).ceil() * step;
+    let mut steps = vec![];
+    while v.le(&max) {
+        steps.push(v);
+        v += step;
+    }
+    steps
+}
+
+/// Rounds a raw step up to 1, 2 or 5 times a power of ten.
+fn nice_step(raw: f64) -> f64 {
+    if raw.le(&0.0) || !raw.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(raw.log10().floor());
+    let residual = raw / magnitude;
+    let nice = if residual.le(&1.0) {
+        1.0
+    } else if residual.le(&2.0) {
+        2.0
+    } else if residual.le(&5.0) {
+        5.0
+    } else {
+        10.0
+    };
+    nice * magnitude
+}
+
+fn format_date(ts: f64, format: &str) -> String {
+    Local
+        .timestamp_opt(ts as i64, 0)
+        .single()
+        .map(|dt| dt.format(format).to_string())
+        .unwrap_or_default()
+}
+
+/// Formats a value compactly, e.g. 1.2M.
+pub fn format_value(v: f64) -> String {
+    let abs = v.abs();
+    if abs.ge(&1e9) {
+        format!("{:.2}B", v / 1e9)
+    } else if abs.ge(&1e6) {
+        format!("{:.2}M", v / 1e6)
+    } else if abs.ge(&1e3) {
+        format!("{:.2}K", v / 1e3)
+    } else if abs.ge(&1.0) || abs.eq(&0.0) {
+        format!("{:.2}", v)
+    } else {
+        format!("{:.4}", v)
+    }
+}
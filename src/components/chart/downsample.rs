@@ -0,0 +1,40 @@
+use super::DataPoint;
+
+/// Reduces a series sorted by x to at most `threshold` points, keeping its visual shape.
+///
+/// Uses largest-triangle-three-buckets: the first and last points are kept, and from each
+/// bucket in between the point forming the largest triangle with its neighbours is chosen.
+pub fn downsample(data: &[DataPoint], threshold: usize) -> Vec<DataPoint> {
+    if threshold.lt(&3) || data.len().le(&threshold) {
+        return data.to_vec();
+    }
+
+    let bucket_size = (data.len() - 2) as f64 / (threshold - 2) as f64;
+    let mut sampled = Vec::with_capacity(threshold);
+    let mut a = data[0];
+    sampled.push(a);
+    for i in 0..threshold - 2 {
+        // Average of the next bucket, as the third corner of the triangle
+        let next_start = ((i + 1) as f64 * bucket_size) as usize + 1;
+        let next_end = (((i + 2) as f64 * bucket_size) as usize + 1).min(data.len());
+        let next = &data[next_start..next_end.max(next_start + 1).min(data.len())];
+        let avg_x = next.iter().map(|p| p.x).sum::<f64>() / next.len() as f64;
+        let avg_y = next.iter().map(|p| p.y).sum::<f64>() / next.len() as f64;
+
+        let start = (i as f64 * bucket_size) as usize + 1;
+        let end = next_start.min(data.len() - 1);
+        let mut max_area = -1.0;
+        let mut chosen = data[start];
+        for p in &data[start..end.max(start + 1)] {
+            let area = ((a.x - avg_x) * (p.y - a.y) - (a.x - p.x) * (avg_y - a.y)).abs();
+            if area.gt(&max_area) {
+                max_area = area;
+                chosen = *p;
+            }
+        }
+        sampled.push(chosen);
+        a = chosen;
+    }
+    sampled.push(data[data.len() - 1]);
+    sampled
+}
@@ -0,0 +1,404 @@
+mod axis;
+mod downsample;
+mod series;
+#[cfg(all(test, feature = "desktop"))]
+mod tests;
+
+pub use axis::*;
+pub use downsample::*;
+pub use series::*;
+
+use dioxus::prelude::*;
+
+use crate::hooks::{use_datetime, use_window_width};
+
+// Room around the plot for tooltips and axis labels
+const MARGIN_TOP: f64 = 28.0;
+const MARGIN_BOTTOM: f64 = 20.0;
+const MARGIN_X: f64 = 4.0;
+
+/// Horizontal pixels per point before a series is downsampled.
+const PIXELS_PER_POINT: f64 = 2.0;
+
+const X_TICK_SPACING: f64 = 96.0;
+const Y_TICK_COUNT: usize = 4;
+
+#[derive(Props)]
+pub struct ChartProps<'a> {
+    pub class: Option<&'a str>,
+    pub series: Vec<ChartSeries>,
+    pub height: f64,
+    /// Defaults to the width of the page content.
+    pub width: Option<f64>,
+    #[props(default)]
+    pub x_axis: AxisScale,
+    #[props(default)]
+    pub y_axis: AxisScale,
+    /// Appended to values in the tooltip.
+    pub unit: Option<&'a str>,
+}
+
+#[component]
+pub fn Chart<'a>(cx: Scope<'a, ChartProps<'a>>) -> Element {
+    let highlighted_x = use_state::<Option<f64>>(cx, || None);
+    let window_width = use_window_width(cx);
+    let class = cx.props.class.unwrap_or("");
+    let unit = cx.props.unit.unwrap_or("");
+    let x_axis = cx.props.x_axis;
+    let y_axis = cx.props.y_axis;
+    let height = cx.props.height;
+    let width = cx
+        .props
+        .width
+        .unwrap_or_else(|| window_width.content_width())
+        .max(2.0 * MARGIN_X + 1.0);
+    let plot_width = width - 2.0 * MARGIN_X;
+    let plot_height = (height - MARGIN_TOP - MARGIN_BOTTOM).max(1.0);
+    let axis_width = 0.5;
+    let axis_opacity = 0.5;
+
+    let threshold = (plot_width / PIXELS_PER_POINT) as usize;
+    let series: Vec<ChartSeries> = cx
+        .props
+        .series
+        .iter()
+        .map(|s| {
+            // Log axes cannot show non-positive values
+            let data: Vec<DataPoint> = s
+                .data
+                .iter()
+                .filter(|d| x_axis.ne(&AxisScale::Log) || d.x.gt(&0.0))
+                .filter(|d| y_axis.ne(&AxisScale::Log) || d.y.gt(&0.0))
+                .copied()
+                .collect();
+            ChartSeries {
+                data: downsample(&data, threshold),
+                ..s.clone()
+            }
+        })
+        .collect();
+    if series.iter().all(|s| s.data.is_empty()) {
+        return render! {
+            div {
+                class: "{class} flex",
+                style: "height: {height}px",
+                p {
+                    class: "m-auto text-sm opacity-50",
+                    "No data yet"
+                }
+            }
+        };
+    }
+
+    let mut min_x = f64::MAX;
+    let mut max_x = f64::MIN;
+    let mut min_y = f64::MAX;
+    let mut max_y = f64::MIN;
+    series.iter().flat_map(|s| s.data.iter()).for_each(|d| {
+        min_x = min_x.min(d.x);
+        max_x = max_x.max(d.x);
+        min_y = min_y.min(d.y);
+        max_y = max_y.max(d.y);
+    });
+
+    // Bars grow from zero, and need half a bar of room at either end
+    let bar_series = series
+        .iter()
+        .filter(|s| s.kind.eq(&SeriesKind::Bar))
+        .count();
+    let mut bar_width = 0.0;
+    if bar_series.gt(&0) {
+        if y_axis.eq(&AxisScale::Linear) {
+            min_y = min_y.min(0.0);
+            max_y = max_y.max(0.0);
+        }
+        let points = series
+            .iter()
+            .filter(|s| s.kind.eq(&SeriesKind::Bar))
+            .map(|s| s.data.len())
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        bar_width = (plot_width / points as f64) * 0.8 / bar_series as f64;
+        let pad = if points.gt(&1) {
+            (max_x - min_x) / (2.0 * (points - 1) as f64)
+        } else {
+            0.5
+        };
+        min_x -= pad;
+        max_x += pad;
+    }
+
+    let x_scale = Scale::new(x_axis, min_x, max_x, plot_width, false);
+    let y_scale = Scale::new(y_axis, min_y, max_y, plot_height, true);
+    let map = move |d: &DataPoint| DataPoint {
+        x: x_scale.map(d.x),
+        y: y_scale.map(d.y),
+    };
+    let baseline = y_scale.map(if y_axis.eq(&AxisScale::Log) {
+        min_y
+    } else {
+        0f64.clamp(min_y, max_y)
+    });
+
+    // Paths for lines and areas, rects for bars
+    let mut areas: Vec<(String, &'static str)> = vec![];
+    let mut lines: Vec<(String, &'static str)> = vec![];
+    let mut bars: Vec<(f64, f64, f64, &'static str)> = vec![];
+    let mut bar_index = 0;
+    for s in series.iter() {
+        let points: Vec<DataPoint> = s.data.iter().map(map).collect();
+        match s.kind {
+            SeriesKind::Line | SeriesKind::Area => {
+                let path = points
+                    .iter()
+                    .enumerate()
+                    .fold(String::new(), |acc, (i, p)| {
+                        if i == 0 {
+                            format!("M{},{}", p.x, p.y)
+                        } else {
+                            format!("{} L{},{}", acc, p.x, p.y)
+                        }
+                    });
+                if s.kind.eq(&SeriesKind::Area) {
+                    if let (Some(first), Some(last)) = (points.first(), points.last()) {
+                        let area = format!(
+                            "{} L{},{} L{},{} Z",
+                            path, last.x, plot_height, first.x, plot_height
+                        );
+                        areas.push((area, s.color));
+                    }
+                }
+                lines.push((path, s.color));
+            }
+            SeriesKind::Bar => {
+                let offset = (bar_index as f64 - (bar_series as f64 - 1.0) / 2.0) * bar_width;
+                for p in points {
+                    let x = p.x + offset - bar_width / 2.0;
+                    let y = p.y.min(baseline);
+                    let h = (p.y - baseline).abs();
+                    bars.push((x, y, h, s.color));
+                }
+                bar_index += 1;
+            }
+        }
+    }
+
+    // Closest point of each series to the hovered position
+    let highlighted: Vec<(DataPoint, DataPoint, &ChartSeries)> = match *highlighted_x.get() {
+        Some(hx) => series
+            .iter()
+            .filter_map(|s| {
+                s.data
+                    .iter()
+                    .min_by(|a, b| (map(a).x - hx).abs().total_cmp(&(map(b).x - hx).abs()))
+                    .map(|d| (*d, map(d), s))
+            })
+            .collect(),
+        None => vec![],
+    };
+    let tooltip = highlighted.first().map(|(d, p, _)| {
+        let title = match x_axis {
+            AxisScale::Time => use_datetime(d.x as i64),
+            _ => format_value(d.x),
+        };
+        let values = highlighted
+            .iter()
+            .map(|(d, _, s)| {
+                if series.len().gt(&1) {
+                    format!("{}: {}{}", s.label, format_value(d.y), unit)
+                } else {
+                    format!("{}{}", format_value(d.y), unit)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" · ");
+        // Keep the label inside the chart
+        let anchor = if p.x.gt(&(plot_width / 2.0)) {
+            "end"
+        } else {
+            "start"
+        };
+        (p.x, title, values, anchor)
+    });
+
+    let x_ticks: Vec<(Tick, &str)> = x_scale
+        .ticks((plot_width / X_TICK_SPACING) as usize)
+        .into_iter()
+        .filter(|t| t.pos.ge(&0.0) && t.pos.le(&plot_width))
+        .map(|t| {
+            // Keep the edge labels inside the chart
+            let anchor = if t.pos.lt(&(X_TICK_SPACING / 2.0)) {
+                "start"
+            } else if t.pos.gt(&(plot_width - X_TICK_SPACING / 2.0)) {
+                "end"
+            } else {
+                "middle"
+            };
+            (t, anchor)
+        })
+        .collect();
+    let y_ticks = y_scale.ticks(Y_TICK_COUNT);
+
+    let handle_mouse_move = move |event: MouseEvent| {
+        highlighted_x.set(Some(event.data.element_coordinates().x - MARGIN_X));
+    };
+
+    let handle_mouse_leave = move |_e: MouseEvent| {
+        highlighted_x.set(None);
+    };
+
+    render! {
+        div {
+            class: "flex flex-col gap-2",
+            svg {
+                class: "{class} max-w-full",
+                onmousemove: handle_mouse_move,
+                onmouseleave: handle_mouse_leave,
+                width: "{width}",
+                height: "{height}",
+                view_box: "0 0 {width} {height}",
+                preserve_aspect_ratio: "xMidYMid meet",
+                g {
+                    transform: "translate({MARGIN_X}, {MARGIN_TOP})",
+
+                    // Grid lines and y axis labels
+                    for tick in y_ticks {
+                        render! {
+                            line {
+                                x1: "{0}",
+                                y1: "{tick.pos}",
+                                x2: "{plot_width}",
+                                y2: "{tick.pos}",
+                                stroke: "currentColor",
+                                stroke_width: "{axis_width}",
+                                opacity: "{axis_opacity}"
+                            }
+                            text {
+                                x: "{0}",
+                                y: "{tick.pos - 4.0}",
+                                font_size: "10",
+                                fill: "currentColor",
+                                opacity: "{axis_opacity}",
+                                "{tick.label}"
+                            }
+                        }
+                    }
+
+                    // X axis labels
+                    for (tick, anchor) in x_ticks {
+                        render! {
+                            text {
+                                x: "{tick.pos}",
+                                y: "{plot_height + 14.0}",
+                                font_size: "10",
+                                fill: "currentColor",
+                                opacity: "{axis_opacity}",
+                                text_anchor: "{anchor}",
+                                "{tick.label}"
+                            }
+                        }
+                    }
+
+                    // Series data
+                    for (path, color) in areas {
+                        render! {
+                            path {
+                                d: "{path}",
+                                fill: "{color}",
+                                fill_opacity: "0.15",
+                                stroke: "none",
+                            }
+                        }
+                    }
+                    for (x, y, h, color) in bars {
+                        render! {
+                            rect {
+                                x: "{x}",
+                                y: "{y}",
+                                width: "{bar_width}",
+                                height: "{h}",
+                                fill: "{color}",
+                            }
+                        }
+                    }
+                    for (path, color) in lines {
+                        render! {
+                            path {
+                                d: "{path}",
+                                fill: "none",
+                                stroke: "{color}",
+                                stroke_width: "2",
+                            }
+                        }
+                    }
+
+                    // Dot at each highlighted point
+                    for (_, p, s) in highlighted.iter() {
+                        render! {
+                            circle {
+                                cx: "{p.x}",
+                                cy: "{p.y}",
+                                r: "4",
+                                fill: "{s.color}",
+                            }
+                        }
+                    }
+
+                    // Tooltip
+                    if let Some((x, title, values, anchor)) = tooltip {
+                        render! {
+                            line {
+                                x1: "{x}",
+                                y1: "{0}",
+                                x2: "{x}",
+                                y2: "{plot_height}",
+                                stroke: "currentColor",
+                                stroke_width: "{axis_width}",
+                                opacity: "{axis_opacity}"
+                            }
+                            text {
+                                x: "{x}",
+                                y: "{-16}",
+                                font_size: "10",
+                                fill: "currentColor",
+                                text_anchor: "{anchor}",
+                                "{title}"
+                            }
+                            text {
+                                x: "{x}",
+                                y: "{-4}",
+                                font_size: "10",
+                                font_weight: "600",
+                                fill: "currentColor",
+                                text_anchor: "{anchor}",
+                                "{values}"
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Legend
+            if series.len().gt(&1) {
+                render! {
+                    div {
+                        class: "flex flex-row flex-wrap gap-4 text-xs",
+                        for s in series.iter() {
+                            render! {
+                                span {
+                                    class: "flex flex-row gap-1.5",
+                                    span {
+                                        class: "w-2 h-2 my-auto rounded-full",
+                                        style: "background-color: {s.color}",
+                                    }
+                                    "{s.label}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
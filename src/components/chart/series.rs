@@ -0,0 +1,52 @@
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DataPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SeriesKind {
+    #[default]
+    Line,
+    /// A line filled down to the bottom of the chart.
+    Area,
+    Bar,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChartSeries {
+    pub label: String,
+    pub data: Vec<DataPoint>,
+    /// Stroke and fill color, as any css color.
+    pub color: &'static str,
+    pub kind: SeriesKind,
+}
+
+impl ChartSeries {
+    pub fn line(label: impl ToString, color: &'static str, data: Vec<DataPoint>) -> Self {
+        Self::new(label, color, data, SeriesKind::Line)
+    }
+
+    pub fn area(label: impl ToString, color: &'static str, data: Vec<DataPoint>) -> Self {
+        Self::new(label, color, data, SeriesKind::Area)
+    }
+
+    pub fn bar(label: impl ToString, color: &'static str, data: Vec<DataPoint>) -> Self {
+        Self::new(label, color, data, SeriesKind::Bar)
+    }
+
+    fn new(
+        label: impl ToString,
+        color: &'static str,
+        mut data: Vec<DataPoint>,
+        kind: SeriesKind,
+    ) -> Self {
+        data.sort_by(|a, b| a.x.total_cmp(&b.x));
+        Self {
+            label: label.to_string(),
+            data,
+            color,
+            kind,
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use super::{downsample, format_value, AxisScale, DataPoint, Scale};
+
+fn series(ys: &[f64]) -> Vec<DataPoint> {
+    ys.iter()
+        .enumerate()
+        .map(|(x, &y)| DataPoint { x: x as f64, y })
+        .collect()
+}
+
+#[test]
+fn short_series_are_not_downsampled() {
+    let data = series(&[1.0, 2.0, 3.0]);
+    assert_eq!(downsample(&data, 10), data);
+    assert_eq!(downsample(&data, 3), data);
+    assert_eq!(downsample(&series(&[5.0]), 10), series(&[5.0]));
+    assert!(downsample(&[], 10).is_empty());
+}
+
+#[test]
+fn thresholds_below_three_keep_every_point() {
+    let data = series(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+    assert_eq!(downsample(&data, 2), data);
+    assert_eq!(downsample(&data, 0), data);
+}
+
+#[test]
+fn downsampling_keeps_the_ends_and_peaks() {
+    let mut ys = vec![1.0; 1_000];
+    ys[500] = 100.0;
+    let data = series(&ys);
+    let sampled = downsample(&data, 50);
+    assert_eq!(sampled.len(), 50);
+    assert_eq!(sampled.first(), data.first());
+    assert_eq!(sampled.last(), data.last());
+    assert!(sampled.iter().any(|p| p.y.eq(&100.0)));
+    assert!(sampled.windows(2).all(|w| w[0].x.lt(&w[1].x)));
+}
+
+#[test]
+fn linear_scale_maps_domain_onto_axis() {
+    let scale = Scale::new(AxisScale::Linear, 0.0, 10.0, 100.0, false);
+    assert_eq!(scale.map(0.0), 0.0);
+    assert_eq!(scale.map(5.0), 50.0);
+    assert_eq!(scale.map(10.0), 100.0);
+
+    // Y axes grow upwards
+    let scale = Scale::new(AxisScale::Linear, 0.0, 10.0, 100.0, true);
+    assert_eq!(scale.map(0.0), 100.0);
+    assert_eq!(scale.map(10.0), 0.0);
+}
+
+#[test]
+fn zero_range_maps_to_the_middle() {
+    let scale = Scale::new(AxisScale::Linear, 7.0, 7.0, 100.0, false);
+    assert_eq!(scale.map(7.0), 50.0);
+    assert!(scale.map(7.0).is_finite());
+    assert!(!scale.ticks(4).is_empty());
+}
+
+#[test]
+fn log_scale_maps_powers_of_ten_evenly() {
+    let scale = Scale::new(AxisScale::Log, 1.0, 1_000.0, 300.0, false);
+    assert!((scale.map(10.0) - 100.0).abs().lt(&1e-9));
+    assert!((scale.map(100.0) - 200.0).abs().lt(&1e-9));
+    // Non-positive values are clamped instead of producing NaN
+    assert!(scale.map(0.0).is_finite());
+    let labels: Vec<String> = scale.ticks(3).into_iter().map(|t| t.label).collect();
+    assert_eq!(labels, vec!["1.00", "10.00", "100.00", "1.00K"]);
+}
+
+#[test]
+fn linear_ticks_fall_on_round_values() {
+    let scale = Scale::new(AxisScale::Linear, 0.0, 97.0, 100.0, false);
+    let ticks = scale.ticks(4);
+    let labels: Vec<String> = ticks.iter().map(|t| t.label.clone()).collect();
+    assert_eq!(labels, vec!["0.00", "50.00"]);
+    assert!(ticks.iter().all(|t| t.pos.ge(&0.0) && t.pos.le(&100.0)));
+
+    let scale = Scale::new(AxisScale::Linear, 0.0, 100.0, 100.0, false);
+    assert_eq!(scale.ticks(5).len(), 6);
+}
+
+#[test]
+fn time_ticks_stay_within_the_domain() {
+    let start = 1_717_372_800.0;
+    let scale = Scale::new(AxisScale::Time, start, start + 7.0 * 86_400.0, 700.0, false);
+    let ticks = scale.ticks(7);
+    assert!(!ticks.is_empty());
+    assert!(ticks.iter().all(|t| t.pos.ge(&0.0) && t.pos.le(&700.0)));
+}
+
+#[test]
+fn formats_values_compactly() {
+    assert_eq!(format_value(0.0), "0.00");
+    assert_eq!(format_value(0.5), "0.5000");
+    assert_eq!(format_value(1_500.0), "1.50K");
+    assert_eq!(format_value(2_000_000.0), "2.00M");
+    assert_eq!(format_value(-3_000_000_000.0), "-3.00B");
+}
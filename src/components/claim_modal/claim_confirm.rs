@@ -1,9 +1,14 @@
 use dioxus::prelude::*;
 
 use crate::{
-    components::{BackButton, MarsIcon, Spinner},
-    hooks::{use_gateway, use_priority_fee, use_pubkey, BalanceHandle, PriorityFee},
+    components::{BackButton, FeePreview, MarsIcon, Spinner},
+    gateway::{AsyncResult, TransactionPreview},
+    hooks::{
+        use_gateway, use_notification_settings, use_priority_fee, use_pubkey, BalanceHandle,
+        PriorityFee,
+    },
     metrics::{track, AppEvent},
+    notifications::{notify_event, publish, NotificationEvent, Severity},
     ProofHandle,
 };
 
@@ -18,6 +23,23 @@ pub fn ClaimConfirm(cx: Scope, amount: u64, claim_step: UseState<ClaimStep>) ->
     let amountf = (*amount as f64) / 10f64.powf(mars::TOKEN_DECIMALS.into());
     let gateway = use_gateway(cx);
     let priority_fee = use_priority_fee(cx);
+    let notification_settings = use_notification_settings(cx);
+    let preview = use_state::<AsyncResult<TransactionPreview>>(cx, || AsyncResult::Loading);
+
+    // Re-estimate whenever the priority fee changes
+    let fee = priority_fee.read().0;
+    use_future(cx, (&fee,), |(fee,)| {
+        let preview = preview.clone();
+        let gateway = gateway.clone();
+        let amount = *amount;
+        async move {
+            preview.set(AsyncResult::Loading);
+            match gateway.preview_claim_mars(amount, fee).await {
+                Ok(p) => preview.set(AsyncResult::Ok(p)),
+                Err(err) => preview.set(AsyncResult::Error(err)),
+            }
+        }
+    });
 
     render! {
         div {
@@ -92,6 +114,9 @@ pub fn ClaimConfirm(cx: Scope, amount: u64, claim_step: UseState<ClaimStep>) ->
                         }
                     }
                 }
+                FeePreview {
+                    preview: *preview.get(),
+                }
                 div {
                     class: "flex flex-col sm:flex-row gap-2",
                     button {
@@ -106,6 +131,7 @@ pub fn ClaimConfirm(cx: Scope, amount: u64, claim_step: UseState<ClaimStep>) ->
                             let is_busy = is_busy.clone();
                             let gateway = gateway.clone();
                             let priority_fee = priority_fee.clone();
+                            let notification_settings = notification_settings.clone();
                             cx.spawn({
                                 async move {
                                     // Create associated token account, if needed
@@ -123,15 +149,20 @@ pub fn ClaimConfirm(cx: Scope, amount: u64, claim_step: UseState<ClaimStep>) ->
                                     match gateway.claim_mars(amount, priority_fee.read().0).await {
                                         Ok(_sig) => {
                                             track(AppEvent::Claim, None);
+                                            notify_event(
+                                                &notification_settings.read(),
+                                                NotificationEvent::ClaimCompleted,
+                                                &format!("Claimed {} MARS", amountf),
+                                            );
                                             balance_.restart();
                                             proof_.restart();
                                             is_busy.set(false);
                                             claim_step.set(ClaimStep::Done);
                                         }
-                                        Err(_err) => {
-                                            // TODO Handle error
+                                        Err(err) => {
                                             is_busy.set(false);
-                                            log::error!("Failed to claim!");
+                                            log::error!("Failed to claim: {:?}", err);
+                                            publish(Severity::Error, "Claim failed", err);
                                         }
                                     }
                                 }
@@ -1,15 +1,19 @@
 use dioxus::prelude::*;
-use dioxus_router::prelude::Link;
+use dioxus_router::prelude::use_navigator;
 
 use crate::{
     components::{Copyable, EyeSlashIcon},
-    hooks::use_keypair,
+    hooks::{use_keypair, use_wallet, Wallet},
+    notifications::{publish, Severity},
     route::Route,
+    wallet::mark_backed_up,
 };
 
 #[component]
 pub fn ExportKeySecret(cx: Scope) -> Element {
     let keypair = use_keypair(cx);
+    let wallet = use_wallet(cx);
+    let nav = use_navigator(cx);
     render! {
         div {
             class: "flex flex-col gap-16 grow w-full h-full",
@@ -33,8 +37,14 @@ pub fn ExportKeySecret(cx: Scope) -> Element {
                 large_button: true,
                 "{keypair.to_base58_string()}"
             }
-            Link {
-                to: Route::Settings {},
+            button {
+                onclick: move |_| {
+                    match mark_backed_up() {
+                        Ok(()) => *wallet.write() = Wallet::load(),
+                        Err(err) => publish(Severity::Error, "Failed to save backup status", err),
+                    }
+                    nav.push(Route::Settings {});
+                },
                 class: "bg-orange-500 hover:bg-orange-600 active:bg-orange-700 transition-colors text-white rounded text-center font-semibold py-3 mt-auto",
                 "Done"
             }
@@ -0,0 +1,173 @@
+use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::native_token::lamports_to_sol;
+#[cfg(feature = "desktop")]
+use solana_sdk::native_token::lamports_to_sol;
+
+use crate::{
+    components::{MarsIcon, WarningIcon},
+    gateway::{AsyncResult, GatewayError, TransactionPreview},
+};
+
+#[component]
+pub fn FeePreview(cx: Scope, preview: AsyncResult<TransactionPreview>) -> Element {
+    let container_class = "flex flex-row gap-8 justify-between";
+    let title_class = "text-sm opacity-50 my-auto";
+    let value_class = "font-medium text-right";
+
+    match preview {
+        AsyncResult::Ok(preview) => {
+            let base_fee = lamports_to_sol(preview.base_fee);
+            let priority_fee = lamports_to_sol(preview.priority_fee);
+            let ata_rent = lamports_to_sol(preview.ata_rent);
+            let tip = lamports_to_sol(preview.tip);
+            let mars_change = format_change(
+                (preview.mars_change as f64) / 10f64.powf(mars::TOKEN_DECIMALS as f64),
+            );
+            let sol_change = format_change(
+                lamports_to_sol(preview.sol_change.unsigned_abs())
+                    * preview.sol_change.signum() as f64,
+            );
+            render! {
+                div {
+                    class: "flex flex-col gap-2",
+                    p {
+                        class: "font-semibold",
+                        "Fees"
+                    }
+                    div {
+                        class: "{container_class}",
+                        p {
+                            class: "{title_class}",
+                            "Compute units"
+                        }
+                        p {
+                            class: "{value_class}",
+                            "{preview.compute_units}"
+                        }
+                    }
+                    div {
+                        class: "{container_class}",
+                        p {
+                            class: "{title_class}",
+                            "Network fee"
+                        }
+                        p {
+                            class: "{value_class}",
+                            "{base_fee} SOL"
+                        }
+                    }
+                    div {
+                        class: "{container_class}",
+                        p {
+                            class: "{title_class}",
+                            "Priority fee"
+                        }
+                        p {
+                            class: "{value_class}",
+                            "{priority_fee} SOL"
+                        }
+                    }
+                    if preview.ata_rent.gt(&0) {
+                        render! {
+                            div {
+                                class: "{container_class}",
+                                p {
+                                    class: "{title_class}",
+                                    "Token account rent"
+                                }
+                                p {
+                                    class: "{value_class}",
+                                    "{ata_rent} SOL"
+                                }
+                            }
+                        }
+                    }
+                    if preview.tip.gt(&0) {
+                        render! {
+                            div {
+                                class: "{container_class}",
+                                p {
+                                    class: "{title_class}",
+                                    "Default RPC tip"
+                                }
+                                p {
+                                    class: "{value_class}",
+                                    "{tip} SOL"
+                                }
+                            }
+                        }
+                    }
+                    p {
+                        class: "font-semibold mt-4",
+                        "Balance changes"
+                    }
+                    if preview.mars_change.ne(&0) {
+                        render! {
+                            div {
+                                class: "{container_class}",
+                                p {
+                                    class: "{title_class}",
+                                    "Mars"
+                                }
+                                span {
+                                    class: "flex flex-row gap-1.5",
+                                    MarsIcon {
+                                        class: "w-3.5 h-3.5 my-auto",
+                                    }
+                                    p {
+                                        class: "{value_class}",
+                                        "{mars_change}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    div {
+                        class: "{container_class}",
+                        p {
+                            class: "{title_class}",
+                            "SOL"
+                        }
+                        p {
+                            class: "{value_class}",
+                            "{sol_change} SOL"
+                        }
+                    }
+                }
+            }
+        }
+        AsyncResult::Loading => {
+            render! {
+                div {
+                    class: "flex flex-row w-full h-32 loading rounded",
+                }
+            }
+        }
+        AsyncResult::Error(err) => {
+            let text = match err {
+                GatewayError::SimulationFailed => {
+                    "This transaction is expected to fail. Please review it before confirming."
+                }
+                _ => "Could not estimate fees for this transaction.",
+            };
+            render! {
+                p {
+                    class: "flex flex-row gap-1.5 text-red-500 font-semibold text-sm",
+                    WarningIcon {
+                        class: "w-4 h-4 my-auto shrink-0"
+                    }
+                    "{text}"
+                }
+            }
+        }
+    }
+}
+
+fn format_change(amount: f64) -> String {
+    if amount.gt(&0.0) {
+        format!("+{}", amount)
+    } else {
+        amount.to_string()
+    }
+}
@@ -2,16 +2,16 @@ use dioxus::prelude::*;
 
 use crate::{
     components::{Activity, BackupKeypairWarning, Balance},
-    hooks::use_show_backup_warning,
+    hooks::use_wallet,
 };
 
 #[component]
 pub fn Home(cx: Scope) -> Element {
-    let show_backup_warning = use_show_backup_warning(cx);
+    let wallet = use_wallet(cx);
     render! {
         div {
             class: "flex flex-col gap-16 overflow-visible",
-            if cfg!(feature = "web") && show_backup_warning.read().0 {
+            if !wallet.read().is_backed_up() {
                 render! {
                     BackupKeypairWarning {}
                 }
@@ -928,6 +928,26 @@ pub fn FlagIcon<'a>(cx: Scope<'a, IconProps<'a>>) -> Element {
     }
 }
 
+#[component]
+pub fn EyeIcon<'a>(cx: Scope<'a, IconProps<'a>>) -> Element {
+    let class = cx.props.class.unwrap_or("");
+    render! {
+        svg {
+            view_box: "0 0 24 24",
+            fill: "currentColor",
+            class: "{class}",
+            path {
+                d: "M12 15a3 3 0 1 0 0-6 3 3 0 0 0 0 6Z"
+            }
+            path {
+                fill_rule: "evenodd",
+                clip_rule: "evenodd",
+                d: "M1.323 11.447C2.811 6.976 7.028 3.75 12.001 3.75c4.97 0 9.185 3.223 10.675 7.69.12.362.12.752 0 1.113-1.487 4.471-5.705 7.697-10.677 7.697-4.97 0-9.186-3.223-10.675-7.69a1.762 1.762 0 0 1 0-1.113ZM17.25 12a5.25 5.25 0 1 1-10.5 0 5.25 5.25 0 0 1 10.5 0Z"
+            }
+        }
+    }
+}
+
 #[component]
 pub fn EyeSlashIcon<'a>(cx: Scope<'a, IconProps<'a>>) -> Element {
     let class = cx.props.class.unwrap_or("");
@@ -951,6 +971,40 @@ pub fn EyeSlashIcon<'a>(cx: Scope<'a, IconProps<'a>>) -> Element {
     }
 }
 
+#[component]
+pub fn BellIcon<'a>(cx: Scope<'a, IconProps<'a>>) -> Element {
+    let class = cx.props.class.unwrap_or("");
+    render! {
+        svg {
+            view_box: "0 0 24 24",
+            fill: "currentColor",
+            class: "{class}",
+            path {
+                fill_rule: "evenodd",
+                clip_rule: "evenodd",
+                d: "M5.25 9a6.75 6.75 0 0 1 13.5 0v.75c0 2.123.8 4.057 2.118 5.52a.75.75 0 0 1-.297 1.206c-1.544.57-3.16.99-4.831 1.243a3.75 3.75 0 1 1-7.48 0 24.585 24.585 0 0 1-4.831-1.244.75.75 0 0 1-.298-1.205A8.217 8.217 0 0 0 5.25 9.75V9Zm4.502 8.9a2.25 2.25 0 1 0 4.496 0 25.057 25.057 0 0 1-4.496 0Z"
+            }
+        }
+    }
+}
+
+#[component]
+pub fn XMarkIcon<'a>(cx: Scope<'a, IconProps<'a>>) -> Element {
+    let class = cx.props.class.unwrap_or("");
+    render! {
+        svg {
+            view_box: "0 0 24 24",
+            fill: "currentColor",
+            class: "{class}",
+            path {
+                fill_rule: "evenodd",
+                clip_rule: "evenodd",
+                d: "M5.47 5.47a.75.75 0 0 1 1.06 0L12 10.94l5.47-5.47a.75.75 0 1 1 1.06 1.06L13.06 12l5.47 5.47a.75.75 0 1 1-1.06 1.06L12 13.06l-5.47 5.47a.75.75 0 0 1-1.06-1.06L10.94 12 5.47 6.53a.75.75 0 0 1 0-1.06Z"
+            }
+        }
+    }
+}
+
 // <svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 24 24" fill="currentColor" class="w-6 h-6">
 //   <path d="M3.53 2.47a.75.75 0 0 0-1.06 1.06l18 18a.75.75 0 1 0 1.06-1.06l-18-18ZM22.676 12.553a11.249 11.249 0 0 1-2.631 4.31l-3.099-3.099a5.25 5.25 0 0 0-6.71-6.71L7.759 4.577a11.217 11.217 0 0 1 4.242-.827c4.97 0 9.185 3.223 10.675 7.69.12.362.12.752 0 1.113Z" />
 //   <path d="M15.75 12c0 .18-.013.357-.037.53l-4.244-4.243A3.75 3.75 0 0 1 15.75 12ZM12.53 15.713l-4.243-4.244a3.75 3.75 0 0 0 4.244 4.243Z" />
@@ -9,9 +9,11 @@ use solana_sdk::{bs58, native_token::LAMPORTS_PER_SOL, signature::Keypair, signe
 
 use crate::{
     components::EyeSlashIcon,
-    gateway::{AsyncResult, GatewayError},
-    hooks::{use_gateway, use_keypair_persistent, use_sol_balance},
+    gateway::AsyncResult,
+    hooks::{use_gateway, use_sol_balance, use_wallet, Wallet},
+    notifications::{publish, Severity},
     route::Route,
+    wallet::{import_wallet, parse_keypair},
 };
 
 pub enum ImportKeyStep {
@@ -117,7 +119,7 @@ const KEY_LENGTH: usize = 64;
 #[component]
 fn ImportKeyImport(cx: Scope) -> Element {
     let sol_balance = use_state::<Option<AsyncResult<u64>>>(cx, || None);
-    let keypair_persistent = use_keypair_persistent(cx);
+    let wallet = use_wallet(cx);
     let err_msg = use_state::<Option<String>>(cx, || None);
     let enable_import_button = use_state(cx, || false);
     let private_key_input = use_state(cx, || "".to_string());
@@ -141,7 +143,7 @@ fn ImportKeyImport(cx: Scope) -> Element {
                                 sol_balance.set(Some(AsyncResult::Ok(b)));
                             }
                             Err(err) => {
-                                sol_balance.set(Some(AsyncResult::Error(GatewayError::from(err))));
+                                sol_balance.set(Some(AsyncResult::Error(err)));
                             }
                         }
                     }
@@ -212,8 +214,19 @@ fn ImportKeyImport(cx: Scope) -> Element {
             button {
                 disabled: !enable_import_button.get(),
                 onclick: move |_| {
-                    keypair_persistent.set(private_key_input.get().clone());
-                    nav.push(Route::Settings {});
+                    let Some(keypair) = parse_keypair(private_key_input.get()) else {
+                        return;
+                    };
+                    match import_wallet(&keypair) {
+                        Ok(()) => {
+                            *wallet.write() = Wallet::load();
+                            nav.push(Route::Settings {});
+                        }
+                        Err(err) => {
+                            log::error!("Failed to import key: {}", err);
+                            publish(Severity::Error, "Failed to import key", err);
+                        }
+                    }
                 },
                 class: "bg-orange-500 disabled:opacity-50 hover:bg-orange-600 active:bg-orange-700 transition-colors text-white rounded text-center font-semibold py-3 mt-auto",
                 "Import"
@@ -11,19 +11,29 @@ use solana_extra_wasm::account_decoder::parse_token::UiTokenAccount;
 #[cfg(feature = "desktop")]
 use solana_sdk::pubkey::Pubkey;
 
+use mars::state::Proof;
+
 use crate::{
-    components::MarsIcon,
-    gateway::{AsyncResult, Gateway},
-    hooks::{use_gateway, use_mars_supply, use_treasury},
+    components::{AxisScale, Chart, ChartSeries, DataPoint, MarsIcon},
+    gateway::{AsyncResult, Gateway, SupplySample},
+    hooks::{
+        use_gateway, use_mars_supply, use_pubkey, use_supply_history, use_treasury,
+        use_window_width,
+    },
     route::Route,
+    utils::token_amount,
 };
 
+const MINERS_PAGE_SIZE: usize = 20;
+
 #[component]
 pub fn Stats(cx: Scope) -> Element {
     render! {
         div {
             class: "flex flex-col gap-16 pb-16",
             SupplyStats {}
+            SupplyCharts {}
+            TopMiners {}
             TopHolders {}
         }
     }
@@ -99,6 +109,372 @@ fn MarsValue(cx: Scope, title: String, detail: String, amount: String) -> Elemen
     }
 }
 
+const SUPPLY_CHART_HEIGHT: f64 = 192.0;
+
+#[component]
+pub fn SupplyCharts(cx: Scope) -> Element {
+    let history = use_supply_history(cx);
+    let window_width = use_window_width(cx);
+    let samples = history.read().0.clone();
+    let data = |f: fn(&SupplySample) -> f64| -> Vec<DataPoint> {
+        samples
+            .iter()
+            .map(|s| DataPoint {
+                x: s.ts as f64,
+                y: f(s),
+            })
+            .collect()
+    };
+    let supply = vec![
+        ChartSeries::area("Total", "#f97316", data(|s| token_amount(s.total_supply))),
+        ChartSeries::area(
+            "Circulating",
+            "#3b82f6",
+            data(|s| token_amount(s.circulating_supply)),
+        ),
+    ];
+    let treasury = vec![ChartSeries::area(
+        "Treasury",
+        "#3b82f6",
+        data(|s| token_amount(s.treasury_balance)),
+    )];
+    let reward_rate = vec![ChartSeries::line(
+        "Reward rate",
+        "#3b82f6",
+        data(|s| token_amount(s.reward_rate)),
+    )];
+    let difficulty = vec![ChartSeries::line(
+        "Difficulty",
+        "#3b82f6",
+        data(|s| s.difficulty as f64),
+    )];
+
+    // Two columns from the md breakpoint up
+    let width = if window_width.0.ge(&768.0) {
+        (window_width.content_width() - 32.0) / 2.0
+    } else {
+        window_width.content_width()
+    };
+    render! {
+        div {
+            class: "flex flex-col gap-6",
+            h2 {
+                "History"
+            }
+            div {
+                class: "grid grid-cols-1 md:grid-cols-2 gap-8",
+                SupplyChart {
+                    title: "Supply",
+                    detail: "Circulating and total supply over time.",
+                    series: supply,
+                    width: width,
+                    unit: " MARS"
+                }
+                SupplyChart {
+                    title: "Treasury balance",
+                    detail: "Mars held by the treasury and available to be claimed.",
+                    series: treasury,
+                    width: width,
+                    unit: " MARS"
+                }
+                SupplyChart {
+                    title: "Reward rate",
+                    detail: "Mars paid out per valid hash.",
+                    series: reward_rate,
+                    width: width,
+                    unit: " MARS"
+                }
+                SupplyChart {
+                    title: "Difficulty",
+                    detail: "Leading zero bits required of a valid hash.",
+                    series: difficulty,
+                    width: width,
+                    unit: " bits"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SupplyChart<'a>(
+    cx: Scope,
+    title: &'a str,
+    detail: &'a str,
+    series: Vec<ChartSeries>,
+    width: f64,
+    unit: &'a str,
+) -> Element {
+    render! {
+        div {
+            class: "flex flex-col gap-2",
+            p {
+                class: "font-medium text-black dark:text-white",
+                "{title}"
+            }
+            p {
+                class: "text-gray-300 text-sm",
+                "{detail}"
+            }
+            Chart {
+                series: series.clone(),
+                width: *width,
+                height: SUPPLY_CHART_HEIGHT,
+                x_axis: AxisScale::Time,
+                unit: unit
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinerRanking {
+    Rewards,
+    Hashes,
+}
+
+impl MinerRanking {
+    pub fn sort(&self, proofs: &mut [Proof]) {
+        match self {
+            MinerRanking::Rewards => proofs.sort_by(|a, b| b.total_rewards.cmp(&a.total_rewards)),
+            MinerRanking::Hashes => proofs.sort_by(|a, b| b.total_hashes.cmp(&a.total_hashes)),
+        }
+    }
+
+    /// Returns the 1-based rank of the authority's proof, if it has one.
+    pub fn rank(&self, proofs: &[Proof], authority: &Pubkey) -> Option<usize> {
+        let mut proofs = proofs.to_vec();
+        self.sort(&mut proofs);
+        proofs
+            .iter()
+            .position(|p| p.authority.eq(authority))
+            .map(|i| i + 1)
+    }
+}
+
+#[component]
+pub fn TopMiners(cx: Scope) -> Element {
+    let proofs = use_state::<AsyncResult<Vec<Proof>>>(cx, || AsyncResult::Loading);
+    let ranking = use_state(cx, || MinerRanking::Rewards);
+    let page = use_state(cx, || 0usize);
+    let gateway = use_gateway(cx);
+    let pubkey = use_pubkey(cx);
+
+    use_future(cx, (), |_| {
+        let gateway = gateway.clone();
+        let proofs = proofs.clone();
+        async move {
+            match gateway.get_proofs().await {
+                Ok(p) => proofs.set(AsyncResult::Ok(p)),
+                Err(err) => proofs.set(AsyncResult::Error(err)),
+            }
+        }
+    });
+
+    let selected_class = "";
+    let unselected_class = "text-gray-300 dark:text-gray-700";
+    let (rewards_class, hashes_class) = match ranking.get() {
+        MinerRanking::Rewards => (selected_class, unselected_class),
+        MinerRanking::Hashes => (unselected_class, selected_class),
+    };
+    let button_class =
+        "px-2 md:px-3 py-2 rounded-full text-xs md:text-sm font-semibold hover-100 active-200 transition-colors";
+
+    render! {
+        div {
+            class: "flex flex-col gap-4",
+            div {
+                class: "flex flex-row justify-between",
+                h2 {
+                    "Top miners"
+                }
+                div {
+                    class: "flex flex-row gap-1 md:gap-2 -mx-1 md:-mx-2 my-auto",
+                    button {
+                        class: "{button_class} {rewards_class}",
+                        onclick: move |_| {
+                            ranking.set(MinerRanking::Rewards);
+                            page.set(0);
+                        },
+                        "Rewards"
+                    }
+                    button {
+                        class: "{button_class} {hashes_class}",
+                        onclick: move |_| {
+                            ranking.set(MinerRanking::Hashes);
+                            page.set(0);
+                        },
+                        "Hashes"
+                    }
+                }
+            }
+            match proofs.get() {
+                AsyncResult::Ok(proofs) => {
+                    let mut proofs = proofs.clone();
+                    ranking.get().sort(&mut proofs);
+                    let rank = proofs.iter().position(|p| p.authority.eq(&pubkey)).map(|i| i + 1);
+                    let total = proofs.len();
+                    let start = page.get().saturating_mul(MINERS_PAGE_SIZE).min(total);
+                    let end = start.saturating_add(MINERS_PAGE_SIZE).min(total);
+                    let has_more = end.lt(&total);
+                    let rows: Vec<(usize, Proof)> = proofs[start..end]
+                        .iter()
+                        .enumerate()
+                        .map(|(i, p)| (start + i + 1, *p))
+                        .collect();
+                    render! {
+                        if let Some(rank) = rank {
+                            render! {
+                                p {
+                                    class: "text-sm opacity-50 px-1",
+                                    "You are ranked #{rank} of {total} miners."
+                                }
+                            }
+                        }
+                        div {
+                            class: "flex flex-col gap-0 justify-start grow h-full",
+                            MinerTableHeader {
+                                ranking: *ranking.get()
+                            }
+                            for (i, proof) in rows {
+                                render! {
+                                    MinerRow {
+                                        i: i,
+                                        proof: proof,
+                                        ranking: *ranking.get(),
+                                        is_local: proof.authority.eq(&pubkey)
+                                    }
+                                }
+                            }
+                        }
+                        div {
+                            class: "flex flex-row justify-between",
+                            if page.get().gt(&0) {
+                                render! {
+                                    button {
+                                        onclick: move |_| page.set(page.current().saturating_sub(1)),
+                                        class: "rounded-full h-10 w-10 font-semibold hover-100 active-200 transition-colors",
+                                        "←"
+                                    }
+                                }
+                            } else {
+                                render! {
+                                    div{}
+                                }
+                            }
+                            if has_more {
+                                render! {
+                                    button {
+                                        onclick: move |_| page.set(page.current().saturating_add(1)),
+                                        class: "rounded-full h-10 w-10 font-semibold hover-100 active-200 transition-colors",
+                                        "→"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                AsyncResult::Loading => render! {
+                    div {
+                        class: "flex flex-row w-full h-32 loading rounded",
+                    }
+                },
+                AsyncResult::Error(_) => render! {
+                    p {
+                        class: "text-sm text-gray-300 py-2 px-1",
+                        "Failed to load miners"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn MinerRow(
+    cx: Scope,
+    i: usize,
+    proof: Proof,
+    ranking: MinerRanking,
+    is_local: bool,
+) -> Element {
+    let authority = proof.authority.to_string();
+    let name = if *is_local {
+        "You".to_string()
+    } else {
+        authority.clone()
+    };
+    let row_class = if *is_local {
+        "bg-orange-500/10 font-semibold"
+    } else {
+        ""
+    };
+    render! {
+        Link {
+            to: Route::User { id: authority.clone() },
+            class: "flex flex-row shrink w-full justify-between rounded px-2 py-2 hover-100 active-200 transition-colors {row_class}",
+            p {
+                class: "w-32 text-left",
+                "{i}"
+            }
+            p {
+                class: "w-full text-left font-mono font-medium truncate",
+                "{name}"
+            }
+            match ranking {
+                MinerRanking::Rewards => {
+                    let rewards = (proof.total_rewards as f64) / 10f64.powf(mars::TOKEN_DECIMALS as f64);
+                    render! {
+                        div {
+                            class: "flex flex-row gap-1 w-full text-right justify-end ml-8",
+                            MarsIcon {
+                                class: "my-auto w-4 h-4"
+                            }
+                            p {
+                                class: "font-medium",
+                                "{rewards}"
+                            }
+                        }
+                    }
+                }
+                MinerRanking::Hashes => {
+                    render! {
+                        p {
+                            class: "w-full text-right font-medium ml-8",
+                            "{proof.total_hashes}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn MinerTableHeader(cx: Scope, ranking: MinerRanking) -> Element {
+    let value = match ranking {
+        MinerRanking::Rewards => "Rewards",
+        MinerRanking::Hashes => "Hashes",
+    };
+    render! {
+        div {
+            class: "flex flex-row shrink w-full justify-between rounded px-2 py-2 transition-colors text-xs font-medium text-gray-300",
+            p {
+                class: "text-left w-32",
+                "#"
+            }
+            p {
+                class: "text-left w-full",
+                "Miner"
+            }
+            p {
+                class: "text-right w-full ml-8",
+                "{value}"
+            }
+        }
+    }
+}
+
 #[component]
 pub fn TopHolders(cx: Scope) -> Element {
     let token_accounts = use_state(cx, || AsyncResult::Loading);
@@ -1,12 +1,9 @@
 use dioxus::prelude::*;
 
-use crate::components::{Chart, DataPoint};
+use crate::components::{Chart, ChartSeries, DataPoint};
 
 #[component]
 pub fn MinerCharts(cx: Scope) -> Element {
-    // let width = use_window_width(cx);
-    let width = 0;
-
     // TODO Fetch data
     let data = vec![
         DataPoint { x: 10.0, y: 20.0 },
@@ -18,9 +15,7 @@ pub fn MinerCharts(cx: Scope) -> Element {
         div {
             class: "flex h-full",
             Chart {
-                class: "h-48 w-full",
-                data: data,
-                width: (width - 32) as f64,
+                series: vec![ChartSeries::line("Hashes", "currentColor", data)],
                 height: 192f64,
             }
         }
@@ -5,10 +5,11 @@ use solana_client_wasm::solana_sdk::native_token::LAMPORTS_PER_SOL;
 use solana_sdk::native_token::LAMPORTS_PER_SOL;
 
 use crate::{
-    components::{try_start_mining, IsToolbarOpen, MinerStatus, MinerStatusMessage, Spinner},
+    components::{report_failure, try_start_mining, IsToolbarOpen, MinerStatusMessage, Spinner},
     gateway::AsyncResult,
     hooks::{use_gateway, use_sol_balance},
     miner::Miner,
+    miner_state::{MinerError, MinerEvent, MinerStage, MinerState},
 };
 
 const MIN_BALANCE: u64 = LAMPORTS_PER_SOL.saturating_div(100);
@@ -19,7 +20,7 @@ pub fn MinerToolbarActivating(cx: Scope, miner: UseState<Miner>) -> Element {
     let sufficient_balance = use_state(cx, || true);
     let sol_balance = use_sol_balance(cx);
     let is_toolbar_open = use_shared_state::<IsToolbarOpen>(cx).unwrap();
-    let miner_status = use_shared_state::<MinerStatus>(cx).unwrap();
+    let miner_state = use_shared_state::<MinerState>(cx).unwrap();
     let miner_status_message = use_shared_state::<MinerStatusMessage>(cx).unwrap();
 
     use_effect(cx, &sol_balance.clone(), |_| {
@@ -32,7 +33,7 @@ pub fn MinerToolbarActivating(cx: Scope, miner: UseState<Miner>) -> Element {
 
     use_future(cx, &sufficient_balance.clone(), |_| {
         let miner = miner.clone();
-        let miner_status = miner_status.clone();
+        let miner_state = miner_state.clone();
         let miner_status_message = miner_status_message.clone();
         let sufficient_balance = *sufficient_balance.get();
         let gateway = gateway.clone();
@@ -40,11 +41,11 @@ pub fn MinerToolbarActivating(cx: Scope, miner: UseState<Miner>) -> Element {
             if sufficient_balance {
                 match try_start_mining(&gateway, miner.get(), &miner_status_message).await {
                     Ok(()) => {
-                        *miner_status.write() = MinerStatus::Active;
+                        miner_state.write().apply(MinerEvent::Started);
                     }
                     Err(err) => {
-                        log::error!("Failed to start mining: {:?}", err);
-                        *miner_status.write() = MinerStatus::Error;
+                        let error = MinerError::new(MinerStage::Starting, err);
+                        report_failure(&miner_state, error);
                         *miner_status_message.write() = MinerStatusMessage::Error
                     }
                 }
@@ -1,13 +1,48 @@
 use dioxus::prelude::*;
 
-use crate::components::{IsToolbarOpen, MinerToolbarInsufficientFunds, StartButton};
+use crate::{
+    components::{IsToolbarOpen, MinerToolbarInsufficientFunds},
+    gateway::GatewayError,
+    miner_state::{MinerError, MinerEvent, MinerState},
+};
 
 #[component]
-pub fn MinerToolbarError(cx: Scope) -> Element {
+pub fn MinerToolbarError(cx: Scope, error: MinerError) -> Element {
     let is_toolbar_open = use_shared_state::<IsToolbarOpen>(cx).unwrap();
+    let recovery = if error.is_retryable() {
+        "Mining resumes once the network is back, or retry now."
+    } else {
+        "Fix the problem, then retry."
+    };
     if is_toolbar_open.read().0 {
+        if error.error.eq(&GatewayError::InsufficientFunds) {
+            return render! {
+                MinerToolbarInsufficientFunds {}
+            };
+        }
         render! {
-            MinerToolbarInsufficientFunds {}
+            div {
+                class: "flex flex-col grow w-full gap-2 px-4 py-6 sm:px-8",
+                div {
+                    class: "flex flex-row w-full justify-between",
+                    h2 {
+                        class: "text-3xl md:text-4xl lg:text-5xl text-white font-bold",
+                        "{error.title()}"
+                    }
+                    div {
+                        class: "my-auto",
+                        RetryButton {}
+                    }
+                }
+                p {
+                    class: "text-lg text-white",
+                    "{error.error}"
+                }
+                p {
+                    class: "text-sm text-white opacity-80",
+                    "{recovery}"
+                }
+            }
         }
     } else {
         render! {
@@ -21,14 +56,32 @@ pub fn MinerToolbarError(cx: Scope) -> Element {
                     class: "flex-shrink flex-auto truncate my-auto",
                     p {
                         class: "font-mono text-sm truncate flex-shrink flex-auto opacity-80 my-auto ml-2",
-                        "Transaction failed."
+                        "{error}"
                     }
                 }
                 div {
                     class: "flex-shrink-0 flex-none ml-auto my-auto",
-                    StartButton {}
+                    RetryButton {}
                 }
             }
         }
     }
 }
+
+#[component]
+pub fn RetryButton(cx: Scope) -> Element {
+    let state = use_shared_state::<MinerState>(cx).unwrap();
+    render! {
+        button {
+            class: "transition transition-colors flex-shrink-0 flex h-10 px-4 justify-center rounded-full font-semibold hover:bg-red-600 active:bg-red-700",
+            title: "Retry mining",
+            onclick: move |_e| {
+                state.write().apply(MinerEvent::Retry);
+            },
+            span {
+                class: "my-auto",
+                "Retry"
+            }
+        }
+    }
+}
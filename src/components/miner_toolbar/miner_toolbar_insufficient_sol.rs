@@ -5,16 +5,17 @@ use solana_client_wasm::solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pub
 use solana_sdk::{native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
 
 use crate::{
-    components::{Copyable, IsToolbarOpen, MinerStatus},
+    components::{Copyable, IsToolbarOpen},
     gateway::AsyncResult,
     hooks::{use_is_onboarded, use_pubkey, use_sol_balance, IsOnboarded, SolBalanceHandle},
+    miner_state::{MinerEvent, MinerState},
 };
 
 #[component]
 pub fn MinerToolbarInsufficientFunds(cx: Scope) -> Element {
     let sol_balance = use_sol_balance(cx);
     let sol_balance_ = use_context::<SolBalanceHandle>(cx).unwrap();
-    let miner_status = use_shared_state::<MinerStatus>(cx).unwrap();
+    let miner_state = use_shared_state::<MinerState>(cx).unwrap();
     let is_toolbar_open = use_shared_state::<IsToolbarOpen>(cx).unwrap();
     let is_onboarded = use_is_onboarded(cx);
 
@@ -23,7 +24,7 @@ pub fn MinerToolbarInsufficientFunds(cx: Scope) -> Element {
             if sol_balance.0.gt(&0) {
                 sol_balance_.cancel(cx);
                 *is_onboarded.write() = IsOnboarded(true);
-                *miner_status.write() = MinerStatus::Activating;
+                miner_state.write().apply(MinerEvent::Start);
             } else {
                 sol_balance_.restart();
             }
@@ -0,0 +1,65 @@
+use dioxus::prelude::*;
+
+use crate::{
+    components::{IsToolbarOpen, StopButton},
+    miner::Miner,
+    miner_state::{MinerError, MinerState, MAX_RETRIES},
+};
+
+#[component]
+pub fn MinerToolbarRetrying(cx: Scope, error: MinerError, miner: UseState<Miner>) -> Element {
+    let is_toolbar_open = use_shared_state::<IsToolbarOpen>(cx).unwrap();
+    let state = *use_shared_state::<MinerState>(cx).unwrap().read();
+    let delay = state.retry_delay().unwrap_or_default().as_secs();
+    if is_toolbar_open.read().0 {
+        render! {
+            div {
+                class: "flex flex-col grow w-full gap-2 px-4 py-6 sm:px-8",
+                div {
+                    class: "flex flex-row w-full justify-between",
+                    h2 {
+                        class: "text-3xl md:text-4xl lg:text-5xl text-white font-bold",
+                        "Retrying"
+                    }
+                    div {
+                        class: "my-auto",
+                        StopButton {
+                            miner: miner.clone()
+                        }
+                    }
+                }
+                p {
+                    class: "text-lg text-white",
+                    "{error}"
+                }
+                p {
+                    class: "text-sm text-white opacity-80",
+                    "Attempt {state.failures} of {MAX_RETRIES}. Starting again in {delay}s."
+                }
+            }
+        }
+    } else {
+        render! {
+            div {
+                class: "flex flex-row gap-2 max-w-screen w-screen justify-start my-auto px-4 sm:px-8",
+                p {
+                    class: "font-semibold text-white flex-shrink-0 flex-none my-auto",
+                    "Retrying"
+                }
+                div {
+                    class: "flex-shrink flex-auto truncate my-auto",
+                    p {
+                        class: "truncate flex-shrink flex-auto text-sm text-white opacity-80 my-auto ml-2",
+                        "{error}"
+                    }
+                }
+                div {
+                    class: "flex-shrink-0 flex-none ml-auto my-auto",
+                    StopButton {
+                        miner: miner.clone()
+                    }
+                }
+            }
+        }
+    }
+}
@@ -4,6 +4,8 @@ mod miner_toolbar_active;
 mod miner_toolbar_error;
 mod miner_toolbar_insufficient_sol;
 mod miner_toolbar_not_started;
+mod miner_toolbar_paused;
+mod miner_toolbar_retrying;
 mod utils;
 
 use dioxus_std::utils::channel::use_channel;
@@ -13,6 +15,8 @@ pub use miner_toolbar_active::*;
 pub use miner_toolbar_error::*;
 pub use miner_toolbar_insufficient_sol::*;
 pub use miner_toolbar_not_started::*;
+pub use miner_toolbar_paused::*;
+pub use miner_toolbar_retrying::*;
 #[cfg(feature = "web")]
 use solana_client_wasm::solana_sdk::keccak::Hash as KeccakHash;
 #[cfg(feature = "desktop")]
@@ -20,24 +24,25 @@ use solana_sdk::keccak::Hash as KeccakHash;
 
 pub use utils::*;
 
+use chrono::Local;
 use dioxus::prelude::*;
 
+#[cfg(feature = "desktop")]
+use crate::hooks::use_tray;
 use crate::{
-    hooks::{use_gateway, use_miner, use_priority_fee, use_pubkey, use_treasury},
-    miner::{submit_solution, MiningResult},
+    challenge::Challenge,
+    gateway::{AsyncResult, GatewayError},
+    hooks::{
+        stop_on_schedule, use_gateway, use_idle_mining, use_miner, use_miner_recovery,
+        use_mining_schedule, use_mining_usage, use_notification_settings, use_priority_fee,
+        use_pubkey, use_scheduled_mining, use_stale_challenges, use_treasury,
+    },
+    miner::{submit_solution, MiningResult, Submission},
+    miner_state::{MinerError, MinerEvent, MinerStage, MinerState, MinerStatus},
+    notifications::{notify_event, NotificationEvent},
     ProofHandle,
 };
 
-#[derive(Debug)]
-pub enum MinerStatus {
-    NotStarted,
-    Activating,
-    Active,
-
-    // TODO Add error field
-    Error,
-}
-
 #[derive(Copy, Clone, Debug)]
 pub enum MinerStatusMessage {
     GeneratingChallenge,
@@ -54,10 +59,10 @@ pub struct IsToolbarOpen(pub bool);
 
 #[component]
 pub fn MinerToolbar(cx: Scope<MinerToolbarProps>, hidden: bool) -> Element {
-    use_shared_state_provider(cx, || MinerStatus::NotStarted);
+    use_shared_state_provider(cx, MinerState::default);
     use_shared_state_provider(cx, || MinerStatusMessage::Searching);
     use_shared_state_provider(cx, || MinerDisplayHash(KeccakHash::new_unique()));
-    let miner_status = use_shared_state::<MinerStatus>(cx).unwrap();
+    let miner_state = use_shared_state::<MinerState>(cx).unwrap();
     let miner_status_message = use_shared_state::<MinerStatusMessage>(cx).unwrap();
     let miner_display_hash = use_shared_state::<MinerDisplayHash>(cx).unwrap();
     let is_toolbar_open = use_shared_state::<IsToolbarOpen>(cx).unwrap();
@@ -68,6 +73,16 @@ pub fn MinerToolbar(cx: Scope<MinerToolbarProps>, hidden: bool) -> Element {
     let miner = use_miner(cx, ch);
     let pubkey = use_pubkey(cx);
     let (treasury, _) = use_treasury(cx);
+    let notification_settings = use_notification_settings(cx);
+    let schedule = use_mining_schedule(cx);
+    let usage = use_mining_usage(cx);
+
+    #[cfg(feature = "desktop")]
+    use_tray(cx, miner);
+    use_idle_mining(cx, miner);
+    use_scheduled_mining(cx, miner);
+    use_miner_recovery(cx);
+    use_stale_challenges(cx, miner);
 
     let _ = use_future(cx, miner_status_message, |_| {
         let display_hash = miner_display_hash.clone();
@@ -88,7 +103,7 @@ pub fn MinerToolbar(cx: Scope<MinerToolbarProps>, hidden: bool) -> Element {
     // Submit for validation and start mining next hash.
     let _ = use_future(cx, (), |_| {
         let mut rx = ch.clone().receiver();
-        let status = miner_status.clone();
+        let state = miner_state.clone();
         let treasury = treasury.clone();
         let miner = miner.clone();
         let gateway = gateway.clone();
@@ -96,33 +111,76 @@ pub fn MinerToolbar(cx: Scope<MinerToolbarProps>, hidden: bool) -> Element {
         let miner_status_message = miner_status_message.clone();
         let miner_display_hash = miner_display_hash.clone();
         let priority_fee = priority_fee.clone();
+        let notification_settings = notification_settings.clone();
+        let schedule = schedule.clone();
+        let usage = usage.clone();
         async move {
             while let Ok(res) = rx.recv().await {
                 *miner_display_hash.write() = MinerDisplayHash(res.hash);
                 *miner_status_message.write() = MinerStatusMessage::Submitting;
                 let priority_fee = priority_fee.read().0;
-                match submit_solution(&gateway, &res, priority_fee, treasury.clone()).await {
-                    Ok(_sig) => {
+                let cached_treasury = match *treasury.read().unwrap() {
+                    AsyncResult::Ok(treasury) => Some(treasury),
+                    _ => None,
+                };
+                // Still loading or failed to load, so fetch it rather than drop the solution
+                let current_treasury = match cached_treasury {
+                    Some(treasury) => Ok(treasury),
+                    None => gateway.get_treasury().await,
+                };
+                let submission = match current_treasury {
+                    Ok(treasury) => submit_solution(&gateway, &res, priority_fee, treasury).await,
+                    Err(err) => Submission {
+                        result: Err(err),
+                        fees: 0,
+                    },
+                };
+                let now = Local::now().naive_local();
+                match submission.result {
+                    Ok(()) => {
+                        usage.write().record_solution(now, submission.fees);
+                        state.write().apply(MinerEvent::Submitted);
+                        miner.get().mark_solved();
+                        notify_event(
+                            &notification_settings.read(),
+                            NotificationEvent::SolutionAccepted,
+                            &format!("Hash {} was accepted", res.hash),
+                        );
                         proof_.restart();
-                        if let MinerStatus::Active = *status.read() {
+                        let reason = schedule.read().stop_reason(now, &usage.read());
+                        if let Some(reason) = reason {
+                            stop_on_schedule(reason, miner.get(), &state);
+                            continue;
+                        }
+                        let is_active = matches!(state.read().status, MinerStatus::Active);
+                        if is_active {
                             // TODO Read difficulty from passed in treasury rather than refetching
-                            if let Ok(treasury) = gateway.get_treasury().await {
-                                if let Ok(proof) = gateway.get_proof(pubkey).await {
+                            match Challenge::fetch(&gateway, pubkey).await {
+                                Ok(challenge) => {
                                     *miner_status_message.write() = MinerStatusMessage::Searching;
                                     miner
-                                        .start_mining(
-                                            proof.hash.into(),
-                                            treasury.difficulty.into(),
-                                            pubkey,
-                                        )
+                                        .start_mining(challenge.hash, challenge.difficulty, pubkey)
                                         .await;
                                 }
+                                Err(err) => {
+                                    let error = MinerError::new(MinerStage::Starting, err);
+                                    report_failure(&state, error);
+                                }
                             }
                         }
                     }
                     Err(err) => {
+                        usage.write().record_fees(now, submission.fees);
                         *miner_status_message.write() = MinerStatusMessage::Error;
-                        log::error!("Failed to submit hash: {:?}", err);
+                        report_failure(&state, MinerError::new(MinerStage::Submitting, err));
+                        let (event, body) = match err {
+                            GatewayError::InsufficientFunds => (
+                                NotificationEvent::InsufficientSol,
+                                "Top up your miner with SOL to keep mining",
+                            ),
+                            _ => (NotificationEvent::MiningError, "Failed to submit hash"),
+                        };
+                        notify_event(&notification_settings.read(), event, body);
                     }
                 }
             }
@@ -138,9 +196,11 @@ pub fn MinerToolbar(cx: Scope<MinerToolbarProps>, hidden: bool) -> Element {
         "h-16 cursor-pointer"
     };
 
-    let bg = match *miner_status.read() {
+    let status = miner_state.read().status;
+    let bg = match status {
         MinerStatus::Active => "bg-orange-500 text-white",
-        MinerStatus::Error => "bg-red-500 text-white",
+        MinerStatus::Paused | MinerStatus::Retrying(_) => "bg-orange-400 text-white",
+        MinerStatus::Error(_) => "bg-red-500 text-white",
         MinerStatus::NotStarted => {
             if is_open {
                 "bg-white dark:bg-gray-900"
@@ -161,7 +221,7 @@ pub fn MinerToolbar(cx: Scope<MinerToolbarProps>, hidden: bool) -> Element {
             },
             div {
                 class: "flex flex-row justify-between w-full max-w-[96rem] mx-auto h-full",
-                match *miner_status.read() {
+                match status {
                     MinerStatus::NotStarted => {
                         render! {
                             MinerToolbarNotStarted {}
@@ -181,9 +241,26 @@ pub fn MinerToolbar(cx: Scope<MinerToolbarProps>, hidden: bool) -> Element {
                             }
                         }
                     }
-                    MinerStatus::Error => {
+                    MinerStatus::Paused => {
+                        render! {
+                            MinerToolbarPaused {
+                                miner: miner.clone()
+                            }
+                        }
+                    }
+                    MinerStatus::Retrying(error) => {
+                        render! {
+                            MinerToolbarRetrying {
+                                error: error,
+                                miner: miner.clone()
+                            }
+                        }
+                    }
+                    MinerStatus::Error(error) => {
                         render! {
-                            MinerToolbarError {}
+                            MinerToolbarError {
+                                error: error
+                            }
                         }
                     }
                 }
@@ -7,9 +7,12 @@ use solana_client_wasm::solana_sdk::signer::Signer;
 use solana_sdk::signer::Signer;
 
 use crate::{
+    challenge::Challenge,
     gateway::{signer, Gateway, GatewayResult},
     metrics::{track, AppEvent},
     miner::Miner,
+    miner_state::{MinerError, MinerEvent, MinerState, MinerStatus},
+    notifications::{publish, Severity},
 };
 
 use super::MinerStatusMessage;
@@ -23,23 +26,14 @@ pub async fn try_start_mining(
 ) -> GatewayResult<()> {
     // Create proof account, if needed
     *status_message.write() = MinerStatusMessage::GeneratingChallenge;
-    'register: loop {
-        if gateway.register_mars().await.is_ok() {
-            break 'register;
-        }
-    }
+    gateway.register_mars().await?;
 
     // Start mining
-    let signer = signer();
-    let treasury = gateway.get_treasury().await.unwrap();
-    let proof = gateway.get_proof(signer.pubkey()).await.unwrap();
+    let signer = signer()?;
+    let challenge = Challenge::fetch(gateway, signer.pubkey()).await?;
     *status_message.write() = MinerStatusMessage::Searching;
     miner
-        .start_mining(
-            proof.hash.into(),
-            treasury.difficulty.into(),
-            signer.pubkey(),
-        )
+        .start_mining(challenge.hash, challenge.difficulty, signer.pubkey())
         .await;
 
     // Record event for data
@@ -47,3 +41,18 @@ pub async fn try_start_mining(
 
     Ok(())
 }
+
+/// Records a failure, and tells the user whether the miner will try again.
+pub fn report_failure(state: &UseSharedState<MinerState>, error: MinerError) {
+    log::error!("{}", error);
+    state.write().apply(MinerEvent::Fail(error));
+    let state = *state.read();
+    match (state.status, state.retry_delay()) {
+        (MinerStatus::Retrying(_), Some(delay)) => publish(
+            Severity::Warning,
+            error.title(),
+            format!("{}. Retrying in {}s.", error.error, delay.as_secs()),
+        ),
+        _ => publish(Severity::Error, error.title(), error.error),
+    }
+}
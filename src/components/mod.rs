@@ -1,5 +1,6 @@
 mod activity;
 mod activity_indicator;
+mod activity_search;
 mod back_button;
 mod backup_keypair_warning;
 mod balance;
@@ -10,6 +11,7 @@ mod code_block;
 mod copyable;
 mod download;
 mod export_key;
+mod fee_preview;
 mod footer;
 mod home;
 mod icons;
@@ -19,20 +21,25 @@ mod leaderboard;
 mod miner;
 mod miner_toolbar;
 mod navbar;
+mod notification_center;
 mod mars_economics;
 mod page_not_found;
 mod send;
 mod settings;
 mod spinner;
 mod stop_button;
+mod sweep;
 mod tutorial;
 mod tx;
 mod user;
 mod user_bubble;
+mod wallet_setup;
+mod watchlist;
 mod what_is_mining;
 
 pub use activity::*;
 pub use activity_indicator::*;
+pub use activity_search::*;
 pub use back_button::*;
 pub use backup_keypair_warning::*;
 pub use balance::*;
@@ -43,6 +50,7 @@ pub use code_block::*;
 pub use copyable::*;
 pub use download::*;
 pub use export_key::*;
+pub use fee_preview::*;
 pub use footer::*;
 pub use home::*;
 pub use icons::*;
@@ -52,14 +60,18 @@ pub use leaderboard::*;
 pub use miner::*;
 pub use miner_toolbar::*;
 pub use navbar::*;
+pub use notification_center::*;
 pub use mars_economics::*;
 pub use page_not_found::*;
 pub use send::*;
 pub use settings::*;
 pub use spinner::*;
 pub use stop_button::*;
+pub use sweep::*;
 pub use tutorial::*;
 pub use tx::*;
 pub use user::*;
 pub use user_bubble::*;
+pub use wallet_setup::*;
+pub use watchlist::*;
 pub use what_is_mining::*;
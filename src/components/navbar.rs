@@ -2,9 +2,12 @@ use dioxus::prelude::*;
 use dioxus_router::prelude::*;
 
 use crate::{
-    components::{Banner, BannerType, Footer, MarsLogoIcon, MarsWordmarkIcon, PieIcon, UserBubble},
+    components::{
+        Banner, BannerType, BellIcon, EyeIcon, Footer, MarsLogoIcon, MarsWordmarkIcon, PieIcon,
+        Toasts, UserBubble,
+    },
     gateway::AsyncResult,
-    hooks::{use_appearance, use_ping},
+    hooks::{use_appearance, use_notification_center, use_ping},
     route::Route,
 };
 
@@ -14,6 +17,7 @@ use super::Appearance;
 pub fn Navbar(cx: Scope) -> Element {
     let ping = use_ping(cx);
     let appearance = use_appearance(cx);
+    let unread = use_notification_center(cx).read().unread;
     let dark = match *appearance.read() {
         Appearance::Dark => "dark",
         Appearance::Light => "",
@@ -42,6 +46,27 @@ pub fn Navbar(cx: Scope) -> Element {
                     }
                     div {
                         class: "flex flex-row gap-6 md:gap-8 lg:gap-10",
+                        Link {
+                            class: "relative transition-colors flex w-10 h-10 justify-center rounded-full text-gray-300 dark:text-gray-700 hover:text-black dark:hover:text-white",
+                            to: Route::NotificationHistory {},
+                            BellIcon {
+                                class: "w-5 h-5 sm:w-6 sm:h-6 my-auto"
+                            }
+                            if unread.gt(&0) {
+                                render! {
+                                    span {
+                                        class: "absolute top-2 right-2 w-2 h-2 rounded-full bg-orange-500"
+                                    }
+                                }
+                            }
+                        }
+                        Link {
+                            class: "transition-colors flex w-10 h-10 justify-center rounded-full text-gray-300 dark:text-gray-700 hover:text-black dark:hover:text-white",
+                            to: Route::WatchlistDashboard {},
+                            EyeIcon {
+                                class: "w-5 h-5 sm:w-6 sm:h-6 my-auto"
+                            }
+                        }
                         Link {
                             class: "transition-colors flex w-10 h-10 justify-center rounded-full text-gray-300 dark:text-gray-700 hover:text-black dark:hover:text-white",
                             to: Route::Stats {},
@@ -57,6 +82,7 @@ pub fn Navbar(cx: Scope) -> Element {
                 class: "flex flex-col h-full py-4 px-4 sm:px-8 grow w-full max-w-[96rem] mx-auto",
                 Outlet::<Route> {}
             }
+            Toasts {}
         }
     }
 }
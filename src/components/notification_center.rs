@@ -0,0 +1,176 @@
+use dioxus::prelude::*;
+
+use crate::{
+    components::{CheckIcon, InfoIcon, WarningIcon, XMarkIcon},
+    hooks::{use_datetime, use_notification_center},
+    notifications::{AppNotification, Severity},
+};
+
+/// Toasts for new notifications, stacked in the corner of the screen.
+#[component]
+pub fn Toasts(cx: Scope) -> Element {
+    let center = use_notification_center(cx);
+    let toasts = center.read().toasts.clone();
+
+    render! {
+        div {
+            class: "fixed top-4 inset-x-4 sm:left-auto sm:w-96 z-50 flex flex-col gap-2 pointer-events-none",
+            for toast in toasts {
+                render! {
+                    Toast {
+                        key: "{toast.id}",
+                        notification: toast
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn Toast(cx: Scope, notification: AppNotification) -> Element {
+    let center = use_notification_center(cx);
+    let id = notification.id;
+
+    render! {
+        div {
+            class: "flex flex-row gap-3 p-4 rounded drop-shadow-md pointer-events-auto bg-white dark:bg-gray-900",
+            SeverityIcon {
+                severity: notification.severity
+            }
+            NotificationText {
+                notification: notification.clone()
+            }
+            button {
+                class: "shrink-0 mb-auto opacity-50 hover:opacity-100 transition-opacity",
+                title: "Dismiss",
+                onclick: move |_| center.write().dismiss(id),
+                XMarkIcon {
+                    class: "w-4 h-4"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn NotificationHistory(cx: Scope) -> Element {
+    let center = use_notification_center(cx);
+    let history = center.read().history.clone();
+
+    // Everything here has now been seen
+    let unread = center.read().unread;
+    use_effect(cx, &unread, |unread| {
+        if unread.gt(&0) {
+            center.write().mark_read();
+        }
+        async move {}
+    });
+
+    render! {
+        div {
+            class: "flex flex-col gap-4 w-full pb-24",
+            div {
+                class: "flex flex-row justify-between",
+                h2 {
+                    "Notifications"
+                }
+                if !history.is_empty() {
+                    render! {
+                        button {
+                            class: "text-sm font-semibold px-2 py-1 my-auto rounded hover-100 active-200 transition-colors",
+                            onclick: move |_| center.write().clear_history(),
+                            "Clear"
+                        }
+                    }
+                }
+            }
+            if history.is_empty() {
+                render! {
+                    p {
+                        class: "text-sm text-gray-300 py-2 sm:px-1",
+                        "No notifications yet"
+                    }
+                }
+            } else {
+                render! {
+                    div {
+                        class: "flex flex-col",
+                        for notification in history {
+                            render! {
+                                div {
+                                    key: "{notification.id}",
+                                    class: "flex flex-row gap-3 py-3 sm:px-1 border-b border-gray-100 dark:border-gray-900",
+                                    SeverityIcon {
+                                        severity: notification.severity
+                                    }
+                                    NotificationText {
+                                        notification: notification.clone()
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SeverityIcon(cx: Scope, severity: Severity) -> Element {
+    match severity {
+        Severity::Info => render! {
+            InfoIcon {
+                class: "w-5 h-5 shrink-0 opacity-50"
+            }
+        },
+        Severity::Success => render! {
+            CheckIcon {
+                class: "w-5 h-5 shrink-0 text-green-500"
+            }
+        },
+        Severity::Warning => render! {
+            WarningIcon {
+                class: "w-5 h-5 shrink-0 text-orange-500"
+            }
+        },
+        Severity::Error => render! {
+            WarningIcon {
+                class: "w-5 h-5 shrink-0 text-red-500"
+            }
+        },
+    }
+}
+
+#[component]
+fn NotificationText(cx: Scope, notification: AppNotification) -> Element {
+    let time = use_datetime(notification.ts);
+    let count = if notification.count.gt(&1) {
+        format!(" ×{}", notification.count)
+    } else {
+        String::new()
+    };
+
+    render! {
+        div {
+            class: "flex flex-col gap-1 grow min-w-0",
+            p {
+                class: "font-semibold text-sm",
+                "{notification.title}{count}"
+            }
+            if !notification.body.is_empty() {
+                render! {
+                    p {
+                        class: "text-sm opacity-75 break-words",
+                        "{notification.body}"
+                    }
+                }
+            }
+            p {
+                class: "text-xs opacity-50",
+                "{time}"
+            }
+        }
+    }
+}
@@ -13,21 +13,39 @@ use solana_client_wasm::solana_sdk::pubkey::Pubkey;
 #[cfg(feature = "desktop")]
 use solana_sdk::pubkey::Pubkey;
 
+const SOL_DECIMALS: u8 = 9;
+
 pub enum SendStep {
     Edit,
     Confirm,
     Done,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SendAsset {
+    Mars,
+    Sol,
+}
+
+impl SendAsset {
+    pub fn decimals(&self) -> u8 {
+        match self {
+            SendAsset::Mars => mars::TOKEN_DECIMALS,
+            SendAsset::Sol => SOL_DECIMALS,
+        }
+    }
+}
+
 #[component]
 pub fn Send(cx: Scope, to: Option<String>) -> Element {
     let send_step = use_state(cx, || SendStep::Edit);
+    let asset = use_state(cx, || SendAsset::Mars);
     let amount_input = use_state(cx, || "".to_string());
     let recipient_input = use_state(cx, || to.clone().unwrap_or("".to_string()));
     let memo_input = use_state(cx, || "".to_string());
 
     let parsed_amount: u64 = match amount_input.get().parse::<f64>() {
-        Ok(n) => (n * 10f64.powf(mars::TOKEN_DECIMALS.into())) as u64,
+        Ok(n) => (n * 10f64.powf(asset.get().decimals().into())) as u64,
         Err(_) => 0,
     };
 
@@ -43,6 +61,7 @@ pub fn Send(cx: Scope, to: Option<String>) -> Element {
                     render! {
                         SendEdit {
                             send_step: send_step,
+                            asset: asset,
                             parsed_amount: parsed_amount,
                             amount_input: amount_input,
                             recipient_input: recipient_input,
@@ -54,6 +73,7 @@ pub fn Send(cx: Scope, to: Option<String>) -> Element {
                     render! {
                         SendConfirm {
                             send_step: send_step,
+                            asset: *asset.get(),
                             amount: parsed_amount,
                             memo: memo,
                             recipient: recipient.unwrap(),
@@ -62,7 +82,9 @@ pub fn Send(cx: Scope, to: Option<String>) -> Element {
                 }
                 SendStep::Done => {
                     render! {
-                        SendDone {}
+                        SendDone {
+                            asset: *asset.get()
+                        }
                     }
                 }
             }
@@ -5,16 +5,19 @@ use solana_client_wasm::solana_sdk::pubkey::Pubkey;
 use solana_sdk::pubkey::Pubkey;
 
 use crate::{
-    components::{BackButton, MarsIcon, Spinner},
-    hooks::{use_gateway, use_mars_balance_handle},
+    components::{BackButton, FeePreview, MarsIcon, Spinner},
+    gateway::{AsyncResult, TransactionPreview},
+    hooks::{use_gateway, use_mars_balance_handle, SolBalanceHandle},
     metrics::{track, AppEvent},
+    notifications::{publish, Severity},
 };
 
-use super::SendStep;
+use super::{SendAsset, SendStep};
 
 #[derive(Props)]
 pub struct SendConfirmProps<'a> {
     pub send_step: &'a UseState<SendStep>,
+    pub asset: SendAsset,
     pub amount: u64,
     pub recipient: Pubkey,
     pub memo: String,
@@ -25,12 +28,31 @@ pub fn SendConfirm<'a>(cx: Scope<'a, SendConfirmProps<'a>>) -> Element {
     let is_busy = use_state(cx, || false);
     let recipient = cx.props.recipient;
     let amount = cx.props.amount;
+    let asset = cx.props.asset;
     let send_step = cx.props.send_step;
     let memo = cx.props.memo.clone();
     let memo_ = memo.clone();
-    let amountf = (cx.props.amount as f64) / 10f64.powf(mars::TOKEN_DECIMALS.into());
+    let amountf = (cx.props.amount as f64) / 10f64.powf(asset.decimals().into());
     let gateway = use_gateway(cx);
     let balance_ = use_mars_balance_handle(cx);
+    let sol_balance_ = use_context::<SolBalanceHandle>(cx).unwrap();
+    let preview = use_state::<AsyncResult<TransactionPreview>>(cx, || AsyncResult::Loading);
+
+    use_future(cx, (), |_| {
+        let preview = preview.clone();
+        let gateway = gateway.clone();
+        let memo = memo.clone();
+        async move {
+            let res = match asset {
+                SendAsset::Mars => gateway.preview_transfer_mars(amount, recipient, memo).await,
+                SendAsset::Sol => gateway.preview_transfer_sol(amount, recipient, memo).await,
+            };
+            match res {
+                Ok(p) => preview.set(AsyncResult::Ok(p)),
+                Err(err) => preview.set(AsyncResult::Error(err)),
+            }
+        }
+    });
 
     render! {
         div {
@@ -63,12 +85,26 @@ pub fn SendConfirm<'a>(cx: Scope<'a, SendConfirmProps<'a>>) -> Element {
                     }
                     div {
                         class: "flex flex-row gap-2",
-                        MarsIcon {
-                            class: "my-auto w-5 h-5"
-                        }
-                        p {
-                            class: "text-2xl",
-                            "{amountf}"
+                        match asset {
+                            SendAsset::Mars => {
+                                render! {
+                                    MarsIcon {
+                                        class: "my-auto w-5 h-5"
+                                    }
+                                    p {
+                                        class: "text-2xl",
+                                        "{amountf}"
+                                    }
+                                }
+                            }
+                            SendAsset::Sol => {
+                                render! {
+                                    p {
+                                        class: "text-2xl",
+                                        "{amountf} SOL"
+                                    }
+                                }
+                            }
                         }
                     }
                 }
@@ -92,6 +128,9 @@ pub fn SendConfirm<'a>(cx: Scope<'a, SendConfirmProps<'a>>) -> Element {
                         "{memo_}"
                     }
                 }
+                FeePreview {
+                    preview: *preview.get(),
+                }
             }
             div {
                 class: "flex flex-col mt-auto sm:flex-row gap-2",
@@ -101,23 +140,32 @@ pub fn SendConfirm<'a>(cx: Scope<'a, SendConfirmProps<'a>>) -> Element {
                     onclick: move |_| {
                         is_busy.set(true);
                         let balance_ = balance_.clone();
+                        let sol_balance_ = sol_balance_.clone();
                         let memo = memo.clone();
                         let send_step = send_step.clone();
                         let is_busy = is_busy.clone();
                         let gateway = gateway.clone();
                         cx.spawn(async move {
-                            match gateway.transfer_mars(amount, recipient, memo).await {
+                            let res = match asset {
+                                SendAsset::Mars => gateway.transfer_mars(amount, recipient, memo).await,
+                                SendAsset::Sol => gateway.transfer_sol(amount, recipient, memo).await,
+                            };
+                            match res {
                                 Ok(sig) => {
                                     log::info!("Transfer: {:?}", sig);
-                                    track(AppEvent::Transfer, None);
+                                    match asset {
+                                        SendAsset::Mars => track(AppEvent::Transfer, None),
+                                        SendAsset::Sol => track(AppEvent::TransferSol, None),
+                                    }
                                     balance_.restart();
+                                    sol_balance_.refresh();
                                     is_busy.set(false);
                                     send_step.set(SendStep::Done);
                                 }
                                 Err(err) => {
-                                    // TODO Handle error
                                     is_busy.set(false);
                                     log::error!("Failed to send: {:?}", err);
+                                    publish(Severity::Error, "Transfer failed", err);
                                 }
                             }
                         });
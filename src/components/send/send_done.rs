@@ -3,8 +3,14 @@ use dioxus_router::components::Link;
 
 use crate::route::Route;
 
+use super::SendAsset;
+
 #[component]
-pub fn SendDone(cx: Scope) -> Element {
+pub fn SendDone(cx: Scope, asset: SendAsset) -> Element {
+    let asset_name = match asset {
+        SendAsset::Mars => "Mars",
+        SendAsset::Sol => "SOL",
+    };
     render! {
         div {
             class: "flex flex-col grow justify-between",
@@ -15,7 +21,7 @@ pub fn SendDone(cx: Scope) -> Element {
                 }
                 p {
                     class: "text-lg",
-                    "You have sent Mars."
+                    "You have sent {asset_name}."
                 }
                 // p {
                 //     class: "text-sm text-gray-300 dark:text-gray-700",
@@ -3,23 +3,24 @@ use std::str::FromStr;
 use dioxus::prelude::*;
 use dioxus_router::hooks::use_navigator;
 #[cfg(feature = "web")]
-use solana_client_wasm::solana_sdk::pubkey::Pubkey;
+use solana_client_wasm::solana_sdk::{native_token::lamports_to_sol, pubkey::Pubkey};
 #[cfg(feature = "desktop")]
-use solana_sdk::pubkey::Pubkey;
+use solana_sdk::{native_token::lamports_to_sol, pubkey::Pubkey};
 
 use crate::{
     components::WarningIcon,
     gateway::AsyncResult,
-    hooks::{use_mars_balance, UiTokenAmountBalance},
+    hooks::{use_gateway, use_mars_balance, use_sol_balance, UiTokenAmountBalance},
 };
 
-use super::SendStep;
+use super::{SendAsset, SendStep};
 
 // TODO Break recipient, amount, and memo into sequential fullscreen steps
 
 #[derive(Props)]
 pub struct SendEditProps<'a> {
     pub send_step: &'a UseState<SendStep>,
+    pub asset: &'a UseState<SendAsset>,
     pub amount_input: &'a UseState<String>,
     pub recipient_input: &'a UseState<String>,
     pub memo_input: &'a UseState<String>,
@@ -31,15 +32,32 @@ pub struct SendEditProps<'a> {
 #[component]
 pub fn SendEdit<'a>(cx: Scope<'a, SendEditProps<'a>>) -> Element {
     let nav = use_navigator(cx);
+    let gateway = use_gateway(cx);
     let mars_balance = use_mars_balance(cx);
+    let sol_balance = use_sol_balance(cx);
+    let asset = cx.props.asset;
     let amount_input = cx.props.amount_input;
     let recipient_input = cx.props.recipient_input;
     let memo_input = cx.props.memo_input;
     let send_step = cx.props.send_step;
     let recipient = Pubkey::from_str(recipient_input.get());
-    let (max_amount, max_amount_str) = match mars_balance {
-        AsyncResult::Ok(balance) => (balance.balance(), balance.ui_amount_string),
-        _ => (0, "0".to_owned()),
+    let sol_lamports = match sol_balance {
+        AsyncResult::Ok(balance) => Some(balance.0),
+        _ => None,
+    };
+    let max_sol = use_future(cx, &sol_lamports, |_| {
+        let gateway = gateway.clone();
+        async move { gateway.max_sol_transfer().await.unwrap_or(0) }
+    });
+    let (max_amount, max_amount_str) = match asset.get() {
+        SendAsset::Mars => match mars_balance {
+            AsyncResult::Ok(balance) => (balance.balance(), balance.ui_amount_string),
+            _ => (0, "0".to_owned()),
+        },
+        SendAsset::Sol => {
+            let max = max_sol.value().copied().unwrap_or(0);
+            (max, lamports_to_sol(max).to_string())
+        }
     };
 
     let amount_error_text = if cx.props.parsed_amount.gt(&max_amount) {
@@ -61,11 +79,28 @@ pub fn SendEdit<'a>(cx: Scope<'a, SendEditProps<'a>>) -> Element {
 
     let memo = memo_input.get().trim();
 
+    // Memos are optional for SOL transfers
+    let is_memo_required = asset.get().eq(&SendAsset::Mars);
+    let memo_label = if is_memo_required {
+        "Memo"
+    } else {
+        "Memo (optional)"
+    };
+
     let is_disabled = amount_input.get().len().eq(&0)
         || amount_input.get().parse::<f64>().is_err()
         || amount_error_text.is_some()
         || recipient.is_err()
-        || memo.len().eq(&0);
+        || (is_memo_required && memo.len().eq(&0));
+
+    let selected_class = "";
+    let unselected_class = "text-gray-300 dark:text-gray-700";
+    let (mars_class, sol_class) = match asset.get() {
+        SendAsset::Mars => (selected_class, unselected_class),
+        SendAsset::Sol => (unselected_class, selected_class),
+    };
+    let asset_button_class =
+        "flex flex-row gap-2 px-3 py-2 rounded-full text-sm font-semibold hover-100 active-200 transition-colors";
 
     render! {
         div {
@@ -75,9 +110,31 @@ pub fn SendEdit<'a>(cx: Scope<'a, SendEditProps<'a>>) -> Element {
                 h2 {
                     "Transfer"
                 }
-                p {
-                    class: "text-lg",
-                    "Send Mars to another user."
+                div {
+                    class: "flex flex-row justify-between",
+                    p {
+                        class: "text-lg my-auto",
+                        "Send Mars or SOL to another user."
+                    }
+                    div {
+                        class: "flex flex-row gap-1 -mr-2",
+                        button {
+                            class: "{asset_button_class} {mars_class}",
+                            onclick: move |_| {
+                                asset.set(SendAsset::Mars);
+                                amount_input.set("".to_string());
+                            },
+                            "Mars"
+                        }
+                        button {
+                            class: "{asset_button_class} {sol_class}",
+                            onclick: move |_| {
+                                asset.set(SendAsset::Sol);
+                                amount_input.set("".to_string());
+                            },
+                            "SOL"
+                        }
+                    }
                 }
                 // p {
                 //     class: "text-gray-300 text-sm",
@@ -160,11 +217,12 @@ pub fn SendEdit<'a>(cx: Scope<'a, SendEditProps<'a>>) -> Element {
                     class: "flex flex-col gap-3",
                     p {
                         class: "font-semibold text-sm",
-                        "Memo"
+                        "{memo_label}"
                     }
                     input {
                         class: "mx-auto w-full focus:ring-0 outline-none placeholder-gray-200 dark:placeholder-gray-700 bg-transparent text-xl",
                         placeholder: "What's this for?",
+                        value: "{memo_input.get()}",
                         oninput: move |evt| {
                             let s = evt.value.clone();
                             memo_input.set(s);
@@ -1,37 +1,82 @@
-use std::{fmt, io, str::FromStr};
+use std::{fmt, io, rc::Rc, str::FromStr, sync::Arc};
 
-use dioxus::prelude::*;
+use chrono::Local;
+use dioxus::{html::FileEngine, prelude::*};
 use dioxus_router::components::Link;
 use is_url::is_url;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "web")]
-use solana_client_wasm::solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_client_wasm::solana_sdk::{
+    native_token::{lamports_to_sol, sol_to_lamports, LAMPORTS_PER_SOL},
+    pubkey::Pubkey,
+};
 #[cfg(feature = "desktop")]
-use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::{
+    native_token::{lamports_to_sol, sol_to_lamports, LAMPORTS_PER_SOL},
+    pubkey::Pubkey,
+};
 
 use crate::{
-    components::{BackupKeypairWarning, Copyable},
-    gateway::{AsyncResult, RPC_URL},
+    components::{BackupKeypairWarning, Copyable, Spinner},
+    config::AppConfig,
+    export::{collect_history, save_export, save_file, to_csv, to_json, ExportFormat},
+    gateway::{session_tips, tip_log, AsyncResult, Gateway, RPC_URL},
     hooks::{
-        use_appearance, use_explorer, use_pubkey, use_rpc_url, use_show_backup_warning,
-        use_sol_balance, RpcUrl,
+        use_appearance, use_config, use_config_applier, use_explorer, use_gateway,
+        use_mining_schedule, use_mining_settings, use_mining_usage, use_notification_settings,
+        use_pubkey, use_rpc_url, use_sol_balance, use_tip_config, use_wallet, ConfigApplier,
+        PowerLevel, RpcUrl,
     },
+    notifications::{publish, request_notification_permission, NotificationEvent, Severity},
     route::Route,
+    schedule::{format_minute, parse_minute, WEEKDAYS},
 };
 
+/// The hash budget is entered in millions of hashes.
+const HASHES_PER_UNIT: u64 = 1_000_000;
+
+const CONFIG_FILE_NAME: &str = "mars-config.json";
+
 #[component]
 pub fn Settings(cx: Scope) -> Element {
     let pubkey = use_pubkey(cx);
     let sol_balance = use_sol_balance(cx);
     let explorer = use_explorer(cx);
     let appearance = use_appearance(cx);
-    let show_backup_warning = use_show_backup_warning(cx);
+    let wallet = use_wallet(cx);
 
     let rpc_url = use_rpc_url(cx);
     let rpc_url_input = use_state(cx, || rpc_url.read().0.clone());
     let is_rpc_url_edited = rpc_url.read().0.ne(rpc_url_input.get());
     let rpc_url_error = use_state::<Option<String>>(cx, || None);
 
+    let tip_config = use_tip_config(cx);
+    let tip_amount = lamports_to_sol(tip_config.read().amount);
+    let tip_session_cap = lamports_to_sol(tip_config.read().session_cap);
+    let tip_session_total = lamports_to_sol(session_tips());
+    let tip_total = lamports_to_sol(*use_state(cx, || tip_log().total).get());
+
+    let mining_settings = use_mining_settings(cx);
+    let idle_only_description = if cfg!(feature = "web") {
+        "Pause mining while this tab is hidden or the browser is busy."
+    } else {
+        "Pause mining while this computer is in use. Detected on Linux only."
+    };
+    let mining_schedule = use_mining_schedule(cx);
+    let schedule_enabled = mining_schedule.read().enabled;
+    let hash_budget = mining_schedule.read().daily_hash_budget / HASHES_PER_UNIT;
+    let fee_budget = lamports_to_sol(mining_schedule.read().daily_fee_budget);
+    let mut usage = use_mining_usage(cx).read().clone();
+    usage.roll_over(Local::now().naive_local());
+    let usage_fees = lamports_to_sol(usage.fees);
+    let notification_settings = use_notification_settings(cx);
+    let config = use_config(cx);
+    let config_applier = use_config_applier(cx);
+
+    let gateway = use_gateway(cx);
+    let is_exporting = use_state(cx, || false);
+    let gateway_ = gateway.clone();
+
     let container_class = "flex flex-row gap-8 justify-between w-full sm:px-1";
     let section_title_class = "text-lg md:text-2xl font-bold";
     let data_title_class = "font-medium text-sm opacity-50 my-auto";
@@ -44,7 +89,7 @@ pub fn Settings(cx: Scope) -> Element {
                 h2 {
                     "Settings"
                 }
-                if cfg!(feature = "web") && show_backup_warning.read().0 {
+                if !wallet.read().is_backed_up() {
                     render! {
                         div {
                             class: "mt-8",
@@ -117,6 +162,90 @@ pub fn Settings(cx: Scope) -> Element {
                         }
                     }
                 }
+                div {
+                    class: "{container_class}",
+                    p {
+                        class: "{data_title_class}",
+                        "History"
+                    }
+                    if *is_exporting.get() {
+                        render! {
+                            Spinner {
+                                class: "my-auto"
+                            }
+                        }
+                    } else {
+                        render! {
+                            div {
+                                class: "flex flex-row gap-2 -mr-2",
+                                button {
+                                    class: "font-semibold hover-100 active-200 transition-colors px-4 py-1 rounded",
+                                    onclick: move |_| {
+                                        is_exporting.set(true);
+                                        cx.spawn(export_history(gateway.clone(), pubkey, ExportFormat::Csv, is_exporting.clone()));
+                                    },
+                                    "Export CSV"
+                                }
+                                button {
+                                    class: "font-semibold hover-100 active-200 transition-colors px-4 py-1 rounded",
+                                    onclick: move |_| {
+                                        is_exporting.set(true);
+                                        cx.spawn(export_history(gateway_.clone(), pubkey, ExportFormat::Json, is_exporting.clone()));
+                                    },
+                                    "Export JSON"
+                                }
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    p {
+                        class: "{data_title_class}",
+                        "Settings"
+                    }
+                    div {
+                        class: "flex flex-row gap-2 -mr-2",
+                        input {
+                            id: "config-import",
+                            class: "hidden",
+                            r#type: "file",
+                            accept: ".json",
+                            onchange: move |e| {
+                                if let Some(files) = e.files.clone() {
+                                    cx.spawn(import_config(files, config.get(), config_applier.clone()));
+                                }
+                            }
+                        }
+                        label {
+                            r#for: "config-import",
+                            class: "font-semibold hover-100 active-200 transition-colors px-4 py-1 rounded hover:cursor-pointer",
+                            "Import"
+                        }
+                        button {
+                            class: "font-semibold hover-100 active-200 transition-colors px-4 py-1 rounded",
+                            onclick: move |_| {
+                                cx.spawn(export_config(config.get()));
+                            },
+                            "Export"
+                        }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    p {
+                        class: "{data_title_class}",
+                        "Retire"
+                    }
+                    div {
+                        class: "flex flex-row gap-2 -mr-2",
+                        Link {
+                            to: Route::Sweep {},
+                            class: "font-semibold hover-100 active-200 transition-colors px-4 py-1 rounded",
+                            "Sweep"
+                        }
+                    }
+                }
             }
             div {
                 class: "flex flex-col gap-4",
@@ -228,6 +357,639 @@ pub fn Settings(cx: Scope) -> Element {
                     }
                 }
             }
+            div {
+                class: "flex flex-col gap-4",
+                h2 {
+                    class: "{section_title_class}",
+                    "Tips"
+                }
+                p {
+                    class: "text-sm opacity-50 sm:px-1",
+                    "Transactions sent through the default RPC include a tip to keep the service running. Tips are not sent when using a custom RPC."
+                }
+                div {
+                    class: "{container_class}",
+                    p {
+                        class: "{data_title_class}",
+                        "Enabled"
+                    }
+                    input {
+                        class: "my-auto hover:cursor-pointer",
+                        r#type: "checkbox",
+                        checked: tip_config.read().enabled,
+                        oninput: move |e| {
+                            tip_config.write().enabled = e.value.eq("true");
+                        }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    p {
+                        class: "{data_title_class}",
+                        "Amount per transaction"
+                    }
+                    div {
+                        class: "flex flex-row gap-1",
+                        input {
+                            disabled: !tip_config.read().enabled,
+                            class: "bg-transparent text-right px-1",
+                            step: 0.0001,
+                            min: 0,
+                            r#type: "number",
+                            value: "{tip_amount}",
+                            oninput: move |e| {
+                                if let Ok(v) = e.value.parse::<f64>() {
+                                    tip_config.write().amount = sol_to_lamports(v);
+                                }
+                            }
+                        }
+                        p {
+                            class: "my-auto",
+                            "SOL"
+                        }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    div {
+                        class: "flex flex-col gap-1",
+                        p {
+                            class: "{data_title_class}",
+                            "Session cap"
+                        }
+                        p {
+                            class: "text-xs opacity-50",
+                            "Stop tipping once this amount has been tipped. Set to 0 for no cap."
+                        }
+                    }
+                    div {
+                        class: "flex flex-row gap-1 mb-auto",
+                        input {
+                            disabled: !tip_config.read().enabled,
+                            class: "bg-transparent text-right px-1",
+                            step: 0.001,
+                            min: 0,
+                            r#type: "number",
+                            value: "{tip_session_cap}",
+                            oninput: move |e| {
+                                if let Ok(v) = e.value.parse::<f64>() {
+                                    tip_config.write().session_cap = sol_to_lamports(v);
+                                }
+                            }
+                        }
+                        p {
+                            class: "my-auto",
+                            "SOL"
+                        }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    p {
+                        class: "{data_title_class}",
+                        "Tipped this session"
+                    }
+                    p {
+                        "{tip_session_total} SOL"
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    p {
+                        class: "{data_title_class}",
+                        "Tipped in total"
+                    }
+                    p {
+                        "{tip_total} SOL"
+                    }
+                }
+            }
+            div {
+                class: "flex flex-col gap-4",
+                h2 {
+                    class: "{section_title_class}",
+                    "Mining"
+                }
+                if cfg!(feature = "desktop") {
+                    render! {
+                        div {
+                            class: "{container_class}",
+                            div {
+                                class: "flex flex-col gap-1",
+                                p {
+                                    class: "{data_title_class}",
+                                    "Low priority"
+                                }
+                                p {
+                                    class: "text-xs opacity-50",
+                                    "Run mining threads below normal priority, so other apps stay responsive. Takes effect on the next challenge."
+                                }
+                            }
+                            input {
+                                class: "my-auto hover:cursor-pointer",
+                                r#type: "checkbox",
+                                checked: mining_settings.read().low_priority,
+                                oninput: move |e| {
+                                    mining_settings.write().low_priority = e.value.eq("true");
+                                }
+                            }
+                        }
+                        div {
+                            class: "{container_class}",
+                            div {
+                                class: "flex flex-col gap-1",
+                                p {
+                                    class: "{data_title_class}",
+                                    "Pin threads to cores"
+                                }
+                                p {
+                                    class: "text-xs opacity-50",
+                                    "Keep each mining thread on its own core. Takes effect on the next challenge."
+                                }
+                            }
+                            input {
+                                class: "my-auto hover:cursor-pointer",
+                                r#type: "checkbox",
+                                checked: mining_settings.read().pin_threads,
+                                oninput: move |e| {
+                                    mining_settings.write().pin_threads = e.value.eq("true");
+                                }
+                            }
+                        }
+                        div {
+                            class: "{container_class}",
+                            p {
+                                class: "{data_title_class}",
+                                "Throttle on battery"
+                            }
+                            input {
+                                class: "my-auto hover:cursor-pointer",
+                                r#type: "checkbox",
+                                checked: mining_settings.read().throttle_on_battery,
+                                oninput: move |e| {
+                                    mining_settings.write().throttle_on_battery = e.value.eq("true");
+                                }
+                            }
+                        }
+                        div {
+                            class: "{container_class}",
+                            p {
+                                class: "{data_title_class}",
+                                "Power level on battery"
+                            }
+                            div {
+                                class: "flex flex-row gap-1",
+                                input {
+                                    disabled: !mining_settings.read().throttle_on_battery,
+                                    class: "bg-transparent text-right px-1",
+                                    step: 10,
+                                    min: 10,
+                                    max: 100,
+                                    r#type: "number",
+                                    value: "{mining_settings.read().battery_power_level.0}",
+                                    oninput: move |e| {
+                                        if let Ok(v) = e.value.parse::<u8>() {
+                                            mining_settings.write().battery_power_level = PowerLevel(v.min(100));
+                                        }
+                                    }
+                                }
+                                p {
+                                    class: "my-auto",
+                                    "%"
+                                }
+                            }
+                        }
+                        div {
+                            class: "{container_class}",
+                            div {
+                                class: "flex flex-col gap-1",
+                                p {
+                                    class: "{data_title_class}",
+                                    "Temperature limit"
+                                }
+                                p {
+                                    class: "text-xs opacity-50",
+                                    "Mine on fewer threads while the cpu is hotter than this. Only read on Linux. Set to 0 for no limit."
+                                }
+                            }
+                            div {
+                                class: "flex flex-row gap-1 mb-auto",
+                                input {
+                                    class: "bg-transparent text-right px-1",
+                                    step: 5,
+                                    min: 0,
+                                    max: 120,
+                                    r#type: "number",
+                                    value: "{mining_settings.read().max_temperature}",
+                                    oninput: move |e| {
+                                        if let Ok(v) = e.value.parse::<u8>() {
+                                            mining_settings.write().max_temperature = v;
+                                        }
+                                    }
+                                }
+                                p {
+                                    class: "my-auto",
+                                    "°C"
+                                }
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    div {
+                        class: "flex flex-col gap-1",
+                        p {
+                            class: "{data_title_class}",
+                            "Mine only when idle"
+                        }
+                        p {
+                            class: "text-xs opacity-50",
+                            "{idle_only_description}"
+                        }
+                    }
+                    input {
+                        class: "my-auto hover:cursor-pointer",
+                        r#type: "checkbox",
+                        checked: mining_settings.read().idle_only,
+                        oninput: move |e| {
+                            mining_settings.write().idle_only = e.value.eq("true");
+                        }
+                    }
+                }
+                if cfg!(feature = "desktop") {
+                    render! {
+                        div {
+                            class: "{container_class}",
+                            p {
+                                class: "{data_title_class}",
+                                "Idle after"
+                            }
+                            div {
+                                class: "flex flex-row gap-1",
+                                input {
+                                    disabled: !mining_settings.read().idle_only,
+                                    class: "bg-transparent text-right px-1",
+                                    step: 1,
+                                    min: 1,
+                                    max: 120,
+                                    r#type: "number",
+                                    value: "{mining_settings.read().idle_minutes}",
+                                    oninput: move |e| {
+                                        if let Ok(v) = e.value.parse::<u32>() {
+                                            mining_settings.write().idle_minutes = v.max(1);
+                                        }
+                                    }
+                                }
+                                p {
+                                    class: "my-auto",
+                                    "min"
+                                }
+                            }
+                        }
+                        div {
+                            class: "{container_class}",
+                            div {
+                                class: "flex flex-col gap-1",
+                                p {
+                                    class: "{data_title_class}",
+                                    "Load limit"
+                                }
+                                p {
+                                    class: "text-xs opacity-50",
+                                    "Pause while other programs use more of the cpu than this. Set to 0 for no limit."
+                                }
+                            }
+                            div {
+                                class: "flex flex-row gap-1 mb-auto",
+                                input {
+                                    disabled: !mining_settings.read().idle_only,
+                                    class: "bg-transparent text-right px-1",
+                                    step: 5,
+                                    min: 0,
+                                    max: 100,
+                                    r#type: "number",
+                                    value: "{mining_settings.read().max_load}",
+                                    oninput: move |e| {
+                                        if let Ok(v) = e.value.parse::<u8>() {
+                                            mining_settings.write().max_load = v.min(100);
+                                        }
+                                    }
+                                }
+                                p {
+                                    class: "my-auto",
+                                    "%"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                class: "flex flex-col gap-4",
+                h2 {
+                    class: "{section_title_class}",
+                    "Schedule"
+                }
+                div {
+                    class: "{container_class}",
+                    div {
+                        class: "flex flex-col gap-1",
+                        p {
+                            class: "{data_title_class}",
+                            "Mine on a schedule"
+                        }
+                        p {
+                            class: "text-xs opacity-50",
+                            "Start mining when a window opens, and stop when it closes or a daily limit is reached. A window that ends before it starts runs past midnight."
+                        }
+                    }
+                    input {
+                        class: "my-auto hover:cursor-pointer",
+                        r#type: "checkbox",
+                        checked: schedule_enabled,
+                        oninput: move |e| {
+                            mining_schedule.write().enabled = e.value.eq("true");
+                        }
+                    }
+                }
+                for (weekday, day) in WEEKDAYS.iter().map(|w| (*w, *mining_schedule.read().day(*w))) {
+                    render! {
+                        div {
+                            class: "{container_class}",
+                            div {
+                                class: "flex flex-row gap-2",
+                                input {
+                                    disabled: !schedule_enabled,
+                                    class: "my-auto hover:cursor-pointer",
+                                    r#type: "checkbox",
+                                    checked: day.enabled,
+                                    oninput: move |e| {
+                                        mining_schedule.write().day_mut(weekday).enabled = e.value.eq("true");
+                                    }
+                                }
+                                p {
+                                    class: "{data_title_class}",
+                                    "{weekday}"
+                                }
+                            }
+                            div {
+                                class: "flex flex-row gap-1",
+                                input {
+                                    disabled: !schedule_enabled || !day.enabled,
+                                    class: "bg-transparent px-1",
+                                    r#type: "time",
+                                    value: "{format_minute(day.start)}",
+                                    oninput: move |e| {
+                                        if let Some(v) = parse_minute(&e.value) {
+                                            mining_schedule.write().day_mut(weekday).start = v;
+                                        }
+                                    }
+                                }
+                                p {
+                                    class: "my-auto",
+                                    "–"
+                                }
+                                input {
+                                    disabled: !schedule_enabled || !day.enabled,
+                                    class: "bg-transparent px-1",
+                                    r#type: "time",
+                                    value: "{format_minute(day.end)}",
+                                    oninput: move |e| {
+                                        if let Some(v) = parse_minute(&e.value) {
+                                            mining_schedule.write().day_mut(weekday).end = v;
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if cfg!(feature = "desktop") {
+                    render! {
+                        div {
+                            class: "{container_class}",
+                            div {
+                                class: "flex flex-col gap-1",
+                                p {
+                                    class: "{data_title_class}",
+                                    "Daily hash budget"
+                                }
+                                p {
+                                    class: "text-xs opacity-50",
+                                    "Millions of hashes per day. Set to 0 for no limit."
+                                }
+                            }
+                            div {
+                                class: "flex flex-row gap-1 mb-auto",
+                                input {
+                                    disabled: !schedule_enabled,
+                                    class: "bg-transparent text-right px-1",
+                                    step: 100,
+                                    min: 0,
+                                    r#type: "number",
+                                    value: "{hash_budget}",
+                                    oninput: move |e| {
+                                        if let Ok(v) = e.value.parse::<u64>() {
+                                            mining_schedule.write().daily_hash_budget = v.saturating_mul(HASHES_PER_UNIT);
+                                        }
+                                    }
+                                }
+                                p {
+                                    class: "my-auto",
+                                    "M"
+                                }
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    div {
+                        class: "flex flex-col gap-1",
+                        p {
+                            class: "{data_title_class}",
+                            "Daily fee budget"
+                        }
+                        p {
+                            class: "text-xs opacity-50",
+                            "Fees and tips paid per day. Set to 0 for no limit."
+                        }
+                    }
+                    div {
+                        class: "flex flex-row gap-1 mb-auto",
+                        input {
+                            disabled: !schedule_enabled,
+                            class: "bg-transparent text-right px-1",
+                            step: 0.001,
+                            min: 0,
+                            r#type: "number",
+                            value: "{fee_budget}",
+                            oninput: move |e| {
+                                if let Ok(v) = e.value.parse::<f64>() {
+                                    mining_schedule.write().daily_fee_budget = sol_to_lamports(v);
+                                }
+                            }
+                        }
+                        p {
+                            class: "my-auto",
+                            "SOL"
+                        }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    div {
+                        class: "flex flex-col gap-1",
+                        p {
+                            class: "{data_title_class}",
+                            "Daily solution limit"
+                        }
+                        p {
+                            class: "text-xs opacity-50",
+                            "Stop after this many accepted solutions per day. Set to 0 for no limit."
+                        }
+                    }
+                    input {
+                        disabled: !schedule_enabled,
+                        class: "bg-transparent text-right px-1 mb-auto",
+                        step: 1,
+                        min: 0,
+                        r#type: "number",
+                        value: "{mining_schedule.read().daily_solution_limit}",
+                        oninput: move |e| {
+                            if let Ok(v) = e.value.parse::<u32>() {
+                                mining_schedule.write().daily_solution_limit = v;
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    p {
+                        class: "{data_title_class}",
+                        "Used today"
+                    }
+                    p {
+                        "{usage.solutions} solutions, {usage_fees} SOL"
+                    }
+                }
+            }
+            div {
+                class: "flex flex-col gap-4",
+                h2 {
+                    class: "{section_title_class}",
+                    "Notifications"
+                }
+                for event in NotificationEvent::ALL {
+                    render! {
+                        div {
+                            class: "{container_class}",
+                            p {
+                                class: "{data_title_class}",
+                                "{event.title()}"
+                            }
+                            input {
+                                class: "my-auto hover:cursor-pointer",
+                                r#type: "checkbox",
+                                checked: notification_settings.read().is_enabled(event),
+                                oninput: move |e| {
+                                    let enabled = e.value.eq("true");
+                                    if enabled {
+                                        request_notification_permission();
+                                    }
+                                    notification_settings.write().set_enabled(event, enabled);
+                                }
+                            }
+                        }
+                    }
+                }
+                if cfg!(feature = "desktop") {
+                    render! {
+                        div {
+                            class: "{container_class}",
+                            div {
+                                class: "flex flex-col gap-1",
+                                p {
+                                    class: "{data_title_class}",
+                                    "Minimize to tray"
+                                }
+                                p {
+                                    class: "text-xs opacity-50",
+                                    "Keep mining in the system tray when the window is closed."
+                                }
+                            }
+                            input {
+                                class: "my-auto hover:cursor-pointer",
+                                r#type: "checkbox",
+                                checked: notification_settings.read().minimize_to_tray,
+                                oninput: move |e| {
+                                    notification_settings.write().minimize_to_tray = e.value.eq("true");
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn export_history(
+    gateway: Rc<Gateway>,
+    pubkey: Pubkey,
+    format: ExportFormat,
+    is_exporting: UseState<bool>,
+) {
+    let records = collect_history(&gateway, pubkey).await;
+    let contents = match format {
+        ExportFormat::Csv => to_csv(&records),
+        ExportFormat::Json => to_json(&records),
+    };
+    if let Err(err) = save_export(contents, format).await {
+        log::error!("Failed to export history: {}", err);
+        publish(Severity::Error, "Failed to export history", err);
+    }
+    is_exporting.set(false);
+}
+
+async fn export_config(config: AppConfig) {
+    if let Err(err) = save_file(
+        config.to_json(),
+        CONFIG_FILE_NAME,
+        "json",
+        "application/json",
+    )
+    .await
+    {
+        log::error!("Failed to export settings: {}", err);
+        publish(Severity::Error, "Failed to export settings", err);
+    }
+}
+
+async fn import_config(files: Arc<dyn FileEngine>, current: AppConfig, applier: ConfigApplier) {
+    for name in files.files() {
+        let Some(json) = files.read_file_to_string(&name).await else {
+            publish(
+                Severity::Error,
+                "Failed to import settings",
+                format!("Could not read {}", name),
+            );
+            continue;
+        };
+        match AppConfig::from_json(&json) {
+            Ok(config) => {
+                // Onboarding state belongs to this device, not the shared config
+                applier.apply(AppConfig {
+                    is_onboarded: current.is_onboarded,
+                    ..config
+                });
+                publish(Severity::Success, "Settings imported", name);
+            }
+            Err(err) => {
+                log::error!("Failed to import settings: {}", err);
+                publish(Severity::Error, "Failed to import settings", err);
+            }
         }
     }
 }
@@ -1,14 +1,15 @@
 use dioxus::prelude::*;
 
 use crate::{
-    components::{IsToolbarOpen, MinerStatus, PauseIcon},
+    components::{IsToolbarOpen, PauseIcon},
     metrics::{track, AppEvent},
     miner::Miner,
+    miner_state::{MinerEvent, MinerState},
 };
 
 #[component]
 pub fn StopButton(cx: Scope, miner: UseState<Miner>) -> Element {
-    let status = use_shared_state::<MinerStatus>(cx).unwrap();
+    let state = use_shared_state::<MinerState>(cx).unwrap();
     let is_toolbar_open = use_shared_state::<IsToolbarOpen>(cx).unwrap();
     render! {
         button {
@@ -17,7 +18,7 @@ pub fn StopButton(cx: Scope, miner: UseState<Miner>) -> Element {
             onclick: move |_e| {
                 track(AppEvent::StopMiner, None);
                 miner.get().stop();
-                *status.write() = MinerStatus::NotStarted;
+                state.write().apply(MinerEvent::Stop);
                 *is_toolbar_open.write() = IsToolbarOpen(false);
             },
             PauseIcon {
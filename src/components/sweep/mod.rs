@@ -0,0 +1,56 @@
+mod sweep_confirm;
+mod sweep_done;
+mod sweep_edit;
+
+use std::str::FromStr;
+
+use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "desktop")]
+use solana_sdk::pubkey::Pubkey;
+use sweep_confirm::*;
+use sweep_done::*;
+use sweep_edit::*;
+
+pub enum SweepStep {
+    Edit,
+    Confirm,
+    Done,
+}
+
+#[component]
+pub fn Sweep(cx: Scope) -> Element {
+    let sweep_step = use_state(cx, || SweepStep::Edit);
+    let recipient_input = use_state(cx, || "".to_string());
+    let recipient = Pubkey::from_str(recipient_input.get()).ok();
+
+    render! {
+        div {
+            class: "flex flex-col grow gap-y-8 my-auto",
+            match sweep_step.get() {
+                SweepStep::Edit => {
+                    render! {
+                        SweepEdit {
+                            sweep_step: sweep_step,
+                            recipient_input: recipient_input,
+                        }
+                    }
+                }
+                SweepStep::Confirm => {
+                    render! {
+                        SweepConfirm {
+                            sweep_step: sweep_step,
+                            recipient: recipient.unwrap(),
+                        }
+                    }
+                }
+                SweepStep::Done => {
+                    render! {
+                        SweepDone {}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "desktop")]
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    components::{BackButton, Spinner, WarningIcon},
+    gateway::GatewayError,
+    hooks::{use_gateway, use_mars_balance_handle, SolBalanceHandle},
+};
+
+use super::SweepStep;
+
+#[derive(Props)]
+pub struct SweepConfirmProps<'a> {
+    pub sweep_step: &'a UseState<SweepStep>,
+    pub recipient: Pubkey,
+}
+
+#[component]
+pub fn SweepConfirm<'a>(cx: Scope<'a, SweepConfirmProps<'a>>) -> Element {
+    let is_busy = use_state(cx, || false);
+    let error_text = use_state::<Option<String>>(cx, || None);
+    let recipient = cx.props.recipient;
+    let sweep_step = cx.props.sweep_step;
+    let gateway = use_gateway(cx);
+    let balance_ = use_mars_balance_handle(cx);
+    let sol_balance_ = use_context::<SolBalanceHandle>(cx).unwrap();
+
+    render! {
+        div {
+            class: "flex flex-col h-full grow gap-12",
+            div {
+                class: "flex flex-col gap-3",
+                BackButton {
+                    onclick: move |_| {
+                        sweep_step.set(SweepStep::Edit);
+                    }
+                }
+                h2 {
+                    "Confirm sweep"
+                }
+                p {
+                    class: "text-lg",
+                    "All Mars and SOL on this keypair will be sent to the address below."
+                }
+                p {
+                    class: "text-sm text-gray-300 dark:text-gray-700",
+                    "Once confirmed, this transaction cannot be undone. This keypair will no longer be able to pay for mining."
+                }
+            }
+            div {
+                class: "flex flex-col gap-2",
+                p {
+                    "To"
+                }
+                p {
+                    class: "text-2xl",
+                    "{recipient.to_string()}"
+                }
+            }
+            if let Some(err) = error_text.get() {
+                render! {
+                    p {
+                        class: "flex flex-row flex-nowrap gap-1.5 text-red-500 font-semibold text-sm",
+                        WarningIcon {
+                            class: "w-4 h-4 my-auto"
+                        }
+                        "{err}"
+                    }
+                }
+            }
+            div {
+                class: "flex flex-col mt-auto sm:flex-row gap-2",
+                button {
+                    class: "w-full py-3 rounded font-semibold transition-colors text-white bg-orange-500 hover:bg-orange-600 active:enabled:bg-orange-700",
+                    disabled: *is_busy.get(),
+                    onclick: move |_| {
+                        is_busy.set(true);
+                        error_text.set(None);
+                        let balance_ = balance_.clone();
+                        let sol_balance_ = sol_balance_.clone();
+                        let sweep_step = sweep_step.clone();
+                        let is_busy = is_busy.clone();
+                        let error_text = error_text.clone();
+                        let gateway = gateway.clone();
+                        cx.spawn(async move {
+                            match gateway.sweep(recipient).await {
+                                Ok(sig) => {
+                                    log::info!("Sweep: {:?}", sig);
+                                    balance_.restart();
+                                    sol_balance_.refresh();
+                                    is_busy.set(false);
+                                    sweep_step.set(SweepStep::Done);
+                                }
+                                Err(err) => {
+                                    is_busy.set(false);
+                                    log::error!("Failed to sweep: {:?}", err);
+                                    match err {
+                                        GatewayError::InsufficientFunds => error_text.set(Some("Not enough SOL to pay for the transaction".to_string())),
+                                        _ => error_text.set(Some("Sweep failed. Please try again.".to_string())),
+                                    }
+                                }
+                            }
+                        });
+                    },
+                    if *is_busy.get() {
+                        render! {
+                            Spinner {
+                                class: "mx-auto"
+                            }
+                        }
+                    } else {
+                        render! {
+                            "Confirm"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
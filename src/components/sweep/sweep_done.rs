@@ -0,0 +1,38 @@
+use dioxus::prelude::*;
+use dioxus_router::components::Link;
+
+use crate::route::Route;
+
+#[component]
+pub fn SweepDone(cx: Scope) -> Element {
+    render! {
+        div {
+            class: "flex flex-col grow justify-between",
+            div {
+                class: "flex flex-col gap-3",
+                h2 {
+                    "Success!"
+                }
+                p {
+                    class: "text-lg",
+                    "You have swept this keypair."
+                }
+                p {
+                    class: "text-sm text-gray-300 dark:text-gray-700",
+                    "Stop the miner before retiring this key, or top it up again to keep mining."
+                }
+            }
+            div {
+                class: "flex flex-col gap-3",
+                div {
+                    class: "h-full"
+                }
+                Link {
+                    class: "w-full py-3 rounded font-semibold transition-colors text-center text-white bg-orange-500 hover:bg-orange-600 active:bg-orange-700",
+                    to: Route::Settings {},
+                    "Done"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::str::FromStr;
+
+use dioxus::prelude::*;
+use dioxus_router::hooks::use_navigator;
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::{native_token::lamports_to_sol, pubkey::Pubkey};
+#[cfg(feature = "desktop")]
+use solana_sdk::{native_token::lamports_to_sol, pubkey::Pubkey};
+
+use crate::{
+    components::{MarsIcon, WarningIcon},
+    gateway::AsyncResult,
+    hooks::{use_mars_balance, use_proof, use_pubkey, use_sol_balance},
+};
+
+use super::SweepStep;
+
+#[derive(Props)]
+pub struct SweepEditProps<'a> {
+    pub sweep_step: &'a UseState<SweepStep>,
+    pub recipient_input: &'a UseState<String>,
+}
+
+#[component]
+pub fn SweepEdit<'a>(cx: Scope<'a, SweepEditProps<'a>>) -> Element {
+    let nav = use_navigator(cx);
+    let pubkey = use_pubkey(cx);
+    let mars_balance = use_mars_balance(cx);
+    let sol_balance = use_sol_balance(cx);
+    let proof = *use_proof(cx).read();
+    let sweep_step = cx.props.sweep_step;
+    let recipient_input = cx.props.recipient_input;
+    let recipient = Pubkey::from_str(recipient_input.get());
+
+    let recipient_error_text = match recipient {
+        Ok(recipient) => {
+            if recipient.eq(&pubkey) {
+                Some("Cannot sweep to yourself".to_string())
+            } else {
+                None
+            }
+        }
+        Err(_) => {
+            if recipient_input.get().len().gt(&0) {
+                Some("Invalid address".to_string())
+            } else {
+                None
+            }
+        }
+    };
+
+    let mars_balance_str = match mars_balance {
+        AsyncResult::Ok(balance) => balance.real_number_string_trimmed(),
+        _ => "–".to_string(),
+    };
+
+    let sol_balance_str = match sol_balance {
+        AsyncResult::Ok(balance) => lamports_to_sol(balance.0).to_string(),
+        _ => "–".to_string(),
+    };
+
+    let claimable_rewards = match proof {
+        AsyncResult::Ok(proof) => proof.claimable_rewards,
+        _ => 0,
+    };
+
+    let is_disabled = recipient.is_err() || recipient_error_text.is_some();
+
+    render! {
+        div {
+            class: "flex flex-col h-full grow gap-12",
+            div {
+                class: "flex flex-col gap-3",
+                h2 {
+                    "Sweep"
+                }
+                p {
+                    class: "text-lg",
+                    "Move all Mars and SOL from this keypair to another address."
+                }
+                p {
+                    class: "text-sm text-gray-300 dark:text-gray-700",
+                    "Use this when retiring a mining key. Your Mars token account will be closed and its rent returned."
+                }
+            }
+            div {
+                class: "flex flex-col gap-12",
+                div {
+                    class: "flex flex-col gap-3",
+                    p {
+                        class: "font-semibold text-sm",
+                        "To"
+                    }
+                    input {
+                        class: "mx-auto w-full focus:ring-0 outline-none placeholder-gray-200 dark:placeholder-gray-700 bg-transparent text-xl",
+                        autofocus: true,
+                        placeholder: "Address",
+                        value: "{recipient_input.get()}",
+                        oninput: move |evt| {
+                            let s = evt.value.clone();
+                            recipient_input.set(s);
+                        },
+                    }
+                    if let Some(err) = recipient_error_text {
+                        render! {
+                            p {
+                                class: "flex flex-row flex-nowrap gap-1.5 w-min text-nowrap text-red-500 font-semibold text-sm",
+                                WarningIcon {
+                                    class: "w-4 h-4 my-auto"
+                                }
+                                "{err}"
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: "flex flex-col gap-2",
+                    p {
+                        class: "font-semibold text-sm",
+                        "Balances"
+                    }
+                    div {
+                        class: "flex flex-row gap-2",
+                        MarsIcon {
+                            class: "my-auto w-4 h-4"
+                        }
+                        p {
+                            class: "text-xl",
+                            "{mars_balance_str}"
+                        }
+                    }
+                    p {
+                        class: "text-xl",
+                        "{sol_balance_str} SOL"
+                    }
+                }
+                if claimable_rewards.gt(&0) {
+                    let rewards = (claimable_rewards as f64) / 10f64.powf(mars::TOKEN_DECIMALS as f64);
+                    render! {
+                        p {
+                            class: "flex flex-row gap-1.5 text-red-500 font-semibold text-sm",
+                            WarningIcon {
+                                class: "w-4 h-4 my-auto shrink-0"
+                            }
+                            "You have {rewards} Mars of unclaimed rewards. Claim them before sweeping or they will stay with this keypair."
+                        }
+                    }
+                }
+            }
+            div {
+                class: "flex flex-col sm:flex-row gap-2 mt-auto",
+                button {
+                    class: "w-full py-3 rounded font-semibold transition-colors hover-100 active-200",
+                    onclick: move |_| {
+                        nav.go_back();
+                    },
+                    "Cancel"
+                }
+                button {
+                    class: "w-full py-3 rounded font-semibold transition-colors transition-opacity text-white bg-orange-500 hover:bg-orange-600 active:bg-orange-700 disabled:opacity-20",
+                    disabled: is_disabled,
+                    onclick: move |_| {
+                        sweep_step.set(SweepStep::Confirm);
+                    },
+                    "Review"
+                }
+            }
+        }
+    }
+}
@@ -1,23 +1,31 @@
 use std::str::FromStr;
 
+use chrono::Utc;
 use dioxus::prelude::*;
 use dioxus_router::{components::Link, prelude::use_navigator};
-use mars::BUS_ADDRESSES;
+use mars::{state::Proof, BUS_ADDRESSES};
+use ore_types::{Transfer, TransferType};
+#[cfg(feature = "desktop")]
+use solana_account_decoder::parse_token::UiTokenAmount;
 #[cfg(feature = "web")]
 use solana_client_wasm::solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "web")]
+use solana_extra_wasm::account_decoder::parse_token::UiTokenAmount;
 #[cfg(feature = "desktop")]
 use solana_sdk::pubkey::Pubkey;
 
 use crate::{
     components::{
-        ActivityTable, BackButton, BusBubble, Copyable, MarsIcon, SendButton, TreasuryBubble,
-        UserBubble,
+        ActivityTable, AxisScale, BackButton, BusBubble, Chart, ChartSeries, Copyable, DataPoint,
+        MarsIcon, MinerRanking, SendButton, TreasuryBubble, UserBubble,
     },
     gateway::AsyncResult,
     hooks::{
-        use_explorer_account_url, use_mars_balance_user, use_pubkey, use_user_proof,
-        use_user_transfers,
+        use_datetime, use_explorer_account_url, use_mars_balance, use_mars_balance_user, use_proof,
+        use_proofs, use_pubkey, use_user_proof, use_user_transfer_history, use_user_transfers,
+        use_watchlist, UiTokenAmountBalance,
     },
+    utils::token_amount,
 };
 
 // TODO Not found
@@ -40,11 +48,28 @@ pub fn User(cx: Scope, id: String) -> Element {
     let balance = use_mars_balance_user(cx, user_id);
     let explorer_url = use_explorer_account_url(cx, id);
     let proof = use_user_proof(cx, user_id);
-    let claimable_rewards = match proof {
-        AsyncResult::Ok(proof) => {
-            (proof.claimable_rewards as f64) / 10f64.powf(mars::TOKEN_DECIMALS as f64)
+    let history = use_user_transfer_history(cx, user_id);
+    let proofs = use_proofs(cx);
+    let (rank, own_rank) = match proofs {
+        AsyncResult::Ok(proofs) => (
+            MinerRanking::Rewards.rank(&proofs, &user_id),
+            MinerRanking::Rewards.rank(&proofs, &pubkey),
+        ),
+        _ => (None, None),
+    };
+    let last_mined_at = match &history {
+        AsyncResult::Ok(transfers) => transfers
+            .iter()
+            .find(|t| matches!(t.transfer_type, TransferType::Mine))
+            .map(|t| use_datetime(t.ts)),
+        _ => None,
+    }
+    .unwrap_or_else(|| "-".to_string());
+    let balance_history = match (&history, &balance) {
+        (AsyncResult::Ok(transfers), AsyncResult::Ok(balance)) => {
+            reconstruct_balance(transfers, &user_id.to_string(), balance.balance())
         }
-        _ => 0.0,
+        _ => vec![],
     };
 
     let title = if let Some(index) = BUS_ADDRESSES
@@ -67,7 +92,8 @@ pub fn User(cx: Scope, id: String) -> Element {
         None
     };
 
-    let show_send_button = title.eq("User") && user_id.ne(&pubkey);
+    let is_user = title.eq("User");
+    let show_send_button = is_user && user_id.ne(&pubkey);
 
     let container_class = "flex flex-row gap-8 justify-between py-1 sm:px-1";
     let title_class = "opacity-50 text-sm my-auto";
@@ -113,7 +139,11 @@ pub fn User(cx: Scope, id: String) -> Element {
                         }
                         if show_send_button {
                             render! {
-                                SendButton { to: id.clone() }
+                                div {
+                                    class: "flex flex-row gap-2",
+                                    WatchButton { address: user_id }
+                                    SendButton { to: id.clone() }
+                                }
                             }
                         }
                     }
@@ -149,7 +179,7 @@ pub fn User(cx: Scope, id: String) -> Element {
                             class: "{title_class}",
                             "Balance"
                         }
-                        match balance {
+                        match &balance {
                             AsyncResult::Ok(balance) => {
                                 render! {
                                     span {
@@ -173,24 +203,25 @@ pub fn User(cx: Scope, id: String) -> Element {
                             }
                         }
                     }
-                    if claimable_rewards.gt(&0.0) {
+                }
+            }
+            if is_user {
+                render! {
+                    UserMining {
+                        proof: proof,
+                        rank: format_rank(rank),
+                        last_mined_at: last_mined_at,
+                    }
+                    UserBalanceHistory {
+                        data: balance_history,
+                    }
+                    if user_id.ne(&pubkey) {
                         render! {
-                            div {
-                                class: "{container_class}",
-                                p {
-                                    class: "{title_class}",
-                                    "Unclaimed rewards"
-                                }
-                                span {
-                                    class: "flex flex-row gap-1.5",
-                                    MarsIcon {
-                                        class: "w-3.5 h-3.5 my-auto",
-                                    }
-                                    p {
-                                        class: "{value_class} truncate",
-                                        "{claimable_rewards}"
-                                   }
-                                }
+                            UserComparison {
+                                proof: proof,
+                                rank: format_rank(rank),
+                                own_rank: format_rank(own_rank),
+                                balance: balance.clone(),
                             }
                         }
                     }
@@ -237,3 +268,212 @@ pub fn UserActivity(cx: Scope, user_id: Pubkey) -> Element {
         }
     }
 }
+
+#[component]
+fn WatchButton(cx: Scope, address: Pubkey) -> Element {
+    let watchlist = use_watchlist(cx);
+    let is_watching = watchlist.read().contains(address);
+    let label = if is_watching { "Watching" } else { "Watch" };
+    render! {
+        button {
+            class: "flex transition transition-colors font-semibold px-3 h-10 my-auto rounded-full hover-100 active-200",
+            onclick: move |_| watchlist.write().toggle(address),
+            span {
+                class: "my-auto",
+                "{label}"
+            }
+        }
+    }
+}
+
+#[component]
+fn UserMining(
+    cx: Scope,
+    proof: AsyncResult<Proof>,
+    rank: String,
+    last_mined_at: String,
+) -> Element {
+    let AsyncResult::Ok(proof) = proof else {
+        return None;
+    };
+    let total_hashes = proof.total_hashes;
+    let total_rewards = token_amount(proof.total_rewards);
+    let claimable_rewards = token_amount(proof.claimable_rewards);
+    let container_class = "flex flex-row gap-8 justify-between py-1 sm:px-1";
+    let title_class = "opacity-50 text-sm my-auto";
+    let value_class = "font-medium py-1 rounded truncate";
+    render! {
+        div {
+            class: "flex flex-col gap-4",
+            h2 {
+                "Mining"
+            }
+            div {
+                class: "flex flex-col gap-1",
+                div {
+                    class: "{container_class}",
+                    p { class: "{title_class}", "Rank" }
+                    p { class: "{value_class}", "{rank}" }
+                }
+                div {
+                    class: "{container_class}",
+                    p { class: "{title_class}", "Total hashes" }
+                    p { class: "{value_class}", "{total_hashes}" }
+                }
+                div {
+                    class: "{container_class}",
+                    p { class: "{title_class}", "Total rewards" }
+                    span {
+                        class: "flex flex-row gap-1.5",
+                        MarsIcon { class: "w-3.5 h-3.5 my-auto" }
+                        p { class: "{value_class}", "{total_rewards}" }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    p { class: "{title_class}", "Unclaimed rewards" }
+                    span {
+                        class: "flex flex-row gap-1.5",
+                        MarsIcon { class: "w-3.5 h-3.5 my-auto" }
+                        p { class: "{value_class}", "{claimable_rewards}" }
+                    }
+                }
+                div {
+                    class: "{container_class}",
+                    p { class: "{title_class}", "Last hash" }
+                    p { class: "{value_class}", "{last_mined_at}" }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn UserBalanceHistory(cx: Scope, data: Vec<DataPoint>) -> Element {
+    if data.len().lt(&2) {
+        return None;
+    }
+    render! {
+        div {
+            class: "flex flex-col gap-4",
+            h2 {
+                "Balance history"
+            }
+            Chart {
+                series: vec![ChartSeries::area("Balance", "#3b82f6", data.clone())],
+                height: 128f64,
+                x_axis: AxisScale::Time,
+                unit: " MARS"
+            }
+        }
+    }
+}
+
+#[component]
+fn UserComparison(
+    cx: Scope,
+    proof: AsyncResult<Proof>,
+    rank: String,
+    own_rank: String,
+    balance: AsyncResult<UiTokenAmount>,
+) -> Element {
+    let own_proof = *use_proof(cx).read();
+    let own_balance = use_mars_balance(cx);
+    let AsyncResult::Ok(proof) = proof else {
+        return None;
+    };
+    let AsyncResult::Ok(own_proof) = own_proof else {
+        return None;
+    };
+    let format_balance = |b: &AsyncResult<UiTokenAmount>| match b {
+        AsyncResult::Ok(b) => token_amount(b.balance()).to_string(),
+        _ => "-".to_string(),
+    };
+    let rows = [
+        ("Rank", rank.clone(), own_rank.clone()),
+        (
+            "Balance",
+            format_balance(balance),
+            format_balance(&own_balance),
+        ),
+        (
+            "Total hashes",
+            proof.total_hashes.to_string(),
+            own_proof.total_hashes.to_string(),
+        ),
+        (
+            "Total rewards",
+            token_amount(proof.total_rewards).to_string(),
+            token_amount(own_proof.total_rewards).to_string(),
+        ),
+        (
+            "Unclaimed rewards",
+            token_amount(proof.claimable_rewards).to_string(),
+            token_amount(own_proof.claimable_rewards).to_string(),
+        ),
+    ];
+    let cell_class = "w-1/3 text-right font-medium truncate";
+    render! {
+        div {
+            class: "flex flex-col gap-4",
+            h2 {
+                "Compare"
+            }
+            div {
+                class: "flex flex-col gap-1",
+                div {
+                    class: "flex flex-row gap-4 py-1 sm:px-1 text-sm opacity-50",
+                    p { class: "w-1/3", "" }
+                    p { class: "w-1/3 text-right", "Them" }
+                    p { class: "w-1/3 text-right", "You" }
+                }
+                for (title, theirs, yours) in rows {
+                    render! {
+                        div {
+                            class: "flex flex-row gap-4 py-1 sm:px-1",
+                            p { class: "w-1/3 opacity-50 text-sm my-auto", "{title}" }
+                            p { class: "{cell_class}", "{theirs}" }
+                            p { class: "{cell_class}", "{yours}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Walks back from the current balance through transfers, newest first, to chart the balance over time.
+/// Mining rewards are skipped, since they only reach the wallet once claimed.
+fn reconstruct_balance(transfers: &[Transfer], user: &str, balance: u64) -> Vec<DataPoint> {
+    let mut balance = balance;
+    let mut data = vec![DataPoint {
+        x: Utc::now().timestamp() as f64,
+        y: token_amount(balance),
+    }];
+    for transfer in transfers {
+        if matches!(transfer.transfer_type, TransferType::Mine) {
+            continue;
+        }
+        data.push(DataPoint {
+            x: transfer.ts as f64,
+            y: token_amount(balance),
+        });
+        if transfer.to_address.eq(user) {
+            balance = balance.saturating_sub(transfer.amount);
+        }
+        if transfer.from_address.eq(user) {
+            balance = balance.saturating_add(transfer.amount);
+        }
+        // Step down to the balance before the transfer
+        data.push(DataPoint {
+            x: (transfer.ts - 1) as f64,
+            y: token_amount(balance),
+        });
+    }
+    data
+}
+
+fn format_rank(rank: Option<usize>) -> String {
+    rank.map(|r| format!("#{}", r))
+        .unwrap_or_else(|| "-".to_string())
+}
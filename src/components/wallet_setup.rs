@@ -0,0 +1,246 @@
+use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::signer::Signer;
+#[cfg(feature = "desktop")]
+use solana_sdk::signer::Signer;
+
+use crate::{
+    components::{Appearance, MarsWordmarkIcon, Toasts, WarningIcon},
+    hooks::{use_appearance, use_wallet, Wallet},
+    notifications::{publish, Severity},
+    wallet::{create_wallet, import_wallet, parse_keypair, WalletError},
+};
+
+/// Shown in place of the app until a wallet is created, imported or recovered.
+#[component]
+pub fn WalletSetup(cx: Scope) -> Element {
+    let appearance = use_appearance(cx);
+    let dark = match *appearance.read() {
+        Appearance::Dark => "dark",
+        Appearance::Light => "",
+    };
+    render! {
+        div {
+            class: "relative min-h-screen flex flex-col text-black dark:bg-black dark:text-white {dark}",
+            div {
+                class: "flex flex-col gap-16 w-full max-w-xl mx-auto px-4 sm:px-8 py-16 grow",
+                MarsWordmarkIcon {
+                    class: "h-3 md:h-4 mr-auto"
+                }
+                WalletSetupStep {}
+            }
+            Toasts {}
+        }
+    }
+}
+
+#[component]
+fn WalletSetupStep(cx: Scope) -> Element {
+    let wallet = use_wallet(cx);
+    let Wallet::Unavailable(err) = wallet.read().clone() else {
+        return None;
+    };
+    match err {
+        WalletError::NotCreated => render! {
+            CreateWallet {}
+        },
+        WalletError::Missing(record) => render! {
+            RecoverWallet {
+                pubkey: record.pubkey
+            }
+        },
+        WalletError::Storage(err) => render! {
+            WalletUnreadable {
+                err: err
+            }
+        },
+    }
+}
+
+#[component]
+fn CreateWallet(cx: Scope) -> Element {
+    let wallet = use_wallet(cx);
+    render! {
+        div {
+            class: "flex flex-col gap-3",
+            h2 {
+                "Welcome to Mars"
+            }
+            p {
+                class: "text-lg",
+                "Create a wallet to start mining."
+            }
+        }
+        button {
+            onclick: move |_| {
+                match create_wallet() {
+                    Ok(_) => *wallet.write() = Wallet::load(),
+                    Err(err) => {
+                        log::error!("Failed to create wallet: {}", err);
+                        publish(Severity::Error, "Failed to create wallet", err);
+                    }
+                }
+            },
+            class: "bg-orange-500 hover:bg-orange-600 active:bg-orange-700 transition-colors text-white rounded text-center font-semibold py-3",
+            "Create wallet"
+        }
+        div {
+            class: "flex flex-col gap-4",
+            p {
+                class: "font-semibold",
+                "Mined before?"
+            }
+            p {
+                class: "text-sm opacity-50",
+                "Import the private key you backed up to continue with your existing wallet."
+            }
+            ImportWallet {}
+        }
+    }
+}
+
+#[component]
+fn RecoverWallet(cx: Scope, pubkey: String) -> Element {
+    let wallet = use_wallet(cx);
+    let is_confirming = use_state(cx, || false);
+    render! {
+        div {
+            class: "flex flex-col gap-3",
+            h2 {
+                "Recover your wallet"
+            }
+            div {
+                class: "flex flex-row gap-3 bg-orange-500 w-full rounded px-4 py-5 text-white",
+                WarningIcon {
+                    class: "w-6 h-6 shrink-0"
+                }
+                p {
+                    "The keypair of your wallet is missing or was replaced on this device. Import your backup to keep mining with it."
+                }
+            }
+            p {
+                class: "text-sm opacity-50 break-all",
+                "{pubkey}"
+            }
+        }
+        ImportWallet {
+            expected: pubkey.clone()
+        }
+        div {
+            class: "flex flex-col gap-4",
+            p {
+                class: "font-semibold",
+                "No backup?"
+            }
+            p {
+                class: "text-sm opacity-50",
+                "You can start over with a new wallet. Funds and rewards held by the old one will not be accessible from this device."
+            }
+            if *is_confirming.get() {
+                render! {
+                    button {
+                        onclick: move |_| {
+                            match create_wallet() {
+                                Ok(_) => *wallet.write() = Wallet::load(),
+                                Err(err) => {
+                                    log::error!("Failed to create wallet: {}", err);
+                                    publish(Severity::Error, "Failed to create wallet", err);
+                                }
+                            }
+                        },
+                        class: "text-red-500 hover:bg-red-500 active:bg-red-600 hover:text-white py-3 w-full rounded text-center font-semibold transition-colors",
+                        "I understand I will lose access to my old wallet"
+                    }
+                }
+            } else {
+                render! {
+                    button {
+                        onclick: move |_| is_confirming.set(true),
+                        class: "hover-100 active-200 py-3 w-full rounded text-center font-semibold transition-colors",
+                        "Start over with a new wallet"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn WalletUnreadable(cx: Scope, err: String) -> Element {
+    let wallet = use_wallet(cx);
+    render! {
+        div {
+            class: "flex flex-col gap-3",
+            h2 {
+                "Wallet unavailable"
+            }
+            p {
+                class: "text-lg",
+                "Your wallet could not be loaded. Nothing has been changed."
+            }
+            p {
+                class: "text-sm text-red-500 break-words",
+                "{err}"
+            }
+        }
+        button {
+            onclick: move |_| *wallet.write() = Wallet::load(),
+            class: "bg-orange-500 hover:bg-orange-600 active:bg-orange-700 transition-colors text-white rounded text-center font-semibold py-3",
+            "Try again"
+        }
+    }
+}
+
+#[component]
+fn ImportWallet(cx: Scope, expected: Option<String>) -> Element {
+    let wallet = use_wallet(cx);
+    let input = use_state(cx, || "".to_string());
+    let keypair = parse_keypair(input.get());
+    let is_valid = keypair.is_some();
+    let err_msg = match (&keypair, expected) {
+        (None, _) if !input.get().trim().is_empty() => Some("Invalid private key".to_string()),
+        (Some(keypair), Some(expected)) if keypair.pubkey().to_string().ne(expected) => {
+            Some(format!(
+                "This key belongs to {}, not the wallet being recovered",
+                keypair.pubkey()
+            ))
+        }
+        _ => None,
+    };
+    render! {
+        div {
+            class: "flex flex-col gap-2",
+            input {
+                class: "w-full py-2 text-center placeholder-gray-200 dark:placeholder-gray-700 bg-transparent",
+                placeholder: "Private key",
+                value: "{input.get()}",
+                oninput: move |evt| input.set(evt.value.clone()),
+            }
+            if let Some(err_msg) = err_msg {
+                render! {
+                    p {
+                        class: "text-red-500 text-sm break-words",
+                        "{err_msg}"
+                    }
+                }
+            }
+        }
+        button {
+            disabled: !is_valid,
+            onclick: move |_| {
+                let Some(keypair) = parse_keypair(input.get()) else {
+                    return;
+                };
+                match import_wallet(&keypair) {
+                    Ok(()) => *wallet.write() = Wallet::load(),
+                    Err(err) => {
+                        log::error!("Failed to import key: {}", err);
+                        publish(Severity::Error, "Failed to import key", err);
+                    }
+                }
+            },
+            class: "bg-orange-500 disabled:opacity-50 hover:bg-orange-600 active:bg-orange-700 transition-colors text-white rounded text-center font-semibold py-3",
+            "Import"
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::str::FromStr;
+
+use dioxus::prelude::*;
+use dioxus_router::components::Link;
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::{native_token::lamports_to_sol, pubkey::Pubkey};
+#[cfg(feature = "desktop")]
+use solana_sdk::{native_token::lamports_to_sol, pubkey::Pubkey};
+
+use crate::{
+    components::{ActivityRow, MarsIcon},
+    gateway::AsyncResult,
+    hooks::{
+        use_mars_balance_user, use_sol_balance_user, use_user_proof, use_watch_activity,
+        use_watchlist, UiTokenAmountBalance, WatchEntry,
+    },
+    notifications::request_notification_permission,
+    route::Route,
+    utils::token_amount,
+};
+
+#[component]
+pub fn WatchlistDashboard(cx: Scope) -> Element {
+    let watchlist = use_watchlist(cx);
+    let activity = use_watch_activity(cx);
+    let entries = watchlist.read().0.clone();
+    let transfers = activity.read().0.clone();
+
+    render! {
+        div {
+            class: "flex flex-col gap-16 w-full pb-24",
+            div {
+                class: "flex flex-col gap-4 w-full",
+                h2 {
+                    "Watchlist"
+                }
+                p {
+                    class: "text-sm opacity-50 sm:px-1",
+                    "Track the balances and rewards of addresses you do not hold keys for, such as miners running on other machines."
+                }
+                AddWatchEntry {}
+                if entries.is_empty() {
+                    render! {
+                        p {
+                            class: "text-sm text-gray-300 py-2 sm:px-1",
+                            "No addresses watched yet"
+                        }
+                    }
+                } else {
+                    render! {
+                        div {
+                            class: "flex flex-col gap-2",
+                            for entry in entries {
+                                render! {
+                                    WatchEntryRow {
+                                        key: "{entry.address}",
+                                        entry: entry.clone()
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                class: "flex flex-col gap-4 w-full",
+                h2 {
+                    "Incoming"
+                }
+                if transfers.is_empty() {
+                    render! {
+                        p {
+                            class: "text-sm text-gray-300 py-2 sm:px-1",
+                            "No transfers received since the app was opened"
+                        }
+                    }
+                } else {
+                    render! {
+                        div {
+                            class: "flex flex-col gap-0",
+                            for transfer in transfers {
+                                render! {
+                                    ActivityRow {
+                                        transfer: transfer
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn AddWatchEntry(cx: Scope) -> Element {
+    let watchlist = use_watchlist(cx);
+    let address = use_state(cx, String::new);
+    let label = use_state(cx, String::new);
+    let threshold = use_state(cx, String::new);
+    let error = use_state::<Option<String>>(cx, || None);
+    let input_class = "w-full bg-transparent text-sm py-1 placeholder-gray-300 dark:placeholder-gray-700 outline-none border-b border-gray-200 dark:border-gray-800";
+
+    render! {
+        form {
+            class: "flex flex-col gap-2 sm:px-1",
+            prevent_default: "onsubmit",
+            onsubmit: move |_| {
+                let Ok(pubkey) = Pubkey::from_str(address.get().trim()) else {
+                    error.set(Some("Invalid address".to_string()));
+                    return;
+                };
+                let claimable_threshold = threshold
+                    .get()
+                    .parse::<f64>()
+                    .ok()
+                    .map(|n| (n * 10f64.powf(mars::TOKEN_DECIMALS.into())) as u64);
+                let name = Some(label.get().trim().to_string()).filter(|l| !l.is_empty());
+                watchlist.write().insert(WatchEntry {
+                    address: pubkey.to_string(),
+                    label: name,
+                    claimable_threshold,
+                });
+                request_notification_permission();
+                address.set(String::new());
+                label.set(String::new());
+                threshold.set(String::new());
+                error.set(None);
+            },
+            div {
+                class: "flex flex-col sm:flex-row gap-2",
+                input {
+                    class: "{input_class} font-mono sm:grow",
+                    placeholder: "Address",
+                    value: "{address}",
+                    oninput: move |e| address.set(e.value.clone()),
+                }
+                input {
+                    class: "{input_class} sm:w-40",
+                    placeholder: "Label",
+                    value: "{label}",
+                    oninput: move |e| label.set(e.value.clone()),
+                }
+                input {
+                    class: "{input_class} sm:w-40",
+                    r#type: "number",
+                    min: 0,
+                    step: "any",
+                    placeholder: "Notify at (MARS)",
+                    value: "{threshold}",
+                    oninput: move |e| threshold.set(e.value.clone()),
+                }
+                button {
+                    class: "font-semibold text-sm px-4 py-2 rounded hover-100 active-200 transition-colors",
+                    r#type: "submit",
+                    "Watch"
+                }
+            }
+            if let Some(error) = error.get() {
+                render! {
+                    p {
+                        class: "text-sm text-red-500",
+                        "{error}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn WatchEntryRow(cx: Scope, entry: WatchEntry) -> Element {
+    let pubkey = entry.pubkey().unwrap_or_default();
+    let watchlist = use_watchlist(cx);
+    let mars_balance = use_mars_balance_user(cx, pubkey);
+    let sol_balance = use_sol_balance_user(cx, pubkey);
+    let proof = use_user_proof(cx, pubkey);
+    let name = entry.name();
+
+    let mars_balance = match mars_balance {
+        AsyncResult::Ok(b) => token_amount(b.balance()).to_string(),
+        _ => "-".to_string(),
+    };
+    let sol_balance = match sol_balance {
+        AsyncResult::Ok(b) => lamports_to_sol(b.0).to_string(),
+        _ => "-".to_string(),
+    };
+    let claimable = match proof {
+        AsyncResult::Ok(p) => token_amount(p.claimable_rewards).to_string(),
+        _ => "-".to_string(),
+    };
+    let threshold = entry
+        .claimable_threshold
+        .map(|t| format!(" / {}", token_amount(t)))
+        .unwrap_or_default();
+    let title_class = "opacity-50 text-xs";
+    let value_class = "font-medium text-sm truncate";
+
+    render! {
+        div {
+            class: "flex flex-col sm:flex-row gap-2 sm:gap-8 justify-between py-3 sm:px-1 border-b border-gray-100 dark:border-gray-900",
+            Link {
+                class: "font-semibold my-auto truncate hover:underline",
+                to: Route::User { id: entry.address.clone() },
+                "{name}"
+            }
+            div {
+                class: "grid grid-cols-3 gap-4 sm:gap-8",
+                div {
+                    class: "flex flex-col",
+                    p { class: "{title_class}", "Balance" }
+                    span {
+                        class: "flex flex-row gap-1",
+                        MarsIcon { class: "w-3 h-3 my-auto" }
+                        p { class: "{value_class}", "{mars_balance}" }
+                    }
+                }
+                div {
+                    class: "flex flex-col",
+                    p { class: "{title_class}", "SOL" }
+                    p { class: "{value_class}", "{sol_balance}" }
+                }
+                div {
+                    class: "flex flex-col",
+                    p { class: "{title_class}", "Claimable" }
+                    p { class: "{value_class}", "{claimable}{threshold}" }
+                }
+            }
+            button {
+                class: "text-xs font-semibold px-2 py-1 my-auto rounded hover-100 active-200 transition-colors",
+                onclick: move |_| watchlist.write().remove(&entry.address),
+                "Remove"
+            }
+        }
+    }
+}
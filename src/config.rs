@@ -0,0 +1,289 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(feature = "web")]
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[cfg(feature = "desktop")]
+use crate::file::{get_value, remove_keys, set_key_value};
+use crate::{
+    components::{Appearance, Explorer},
+    gateway::{TipConfig, RPC_URL},
+    hooks::{IsOnboarded, PowerLevel, PriorityFee, RpcUrl, Watchlist},
+    miner::MiningSettings,
+    notifications::{publish, NotificationSettings, Severity},
+    schedule::MiningSchedule,
+};
+
+/// Schema version written by this build. When the schema changes, bump it and append a migration.
+pub const CONFIG_VERSION: u32 = 3;
+
+const CONFIG_KEY: &str = "config";
+
+/// Keys settings were stored under before they were collected into a single config.
+const LEGACY_KEYS: [&str; 7] = [
+    "appearance",
+    "explorer",
+    "power_level",
+    "priority_fee",
+    "rpc",
+    "is_onboarded",
+    "show_backup_warning",
+];
+
+/// Default rpc urls of earlier releases, which no longer serve requests.
+const RETIRED_RPC_URLS: [&str; 2] = [
+    "https://amaleta-5y8tse-fast-mainnet.helius-rpc.com/",
+    "https://rpc-proxy.miraland-labs.workers.dev",
+];
+
+/// Version of a stored config written by a newer release. While set, the stored config is left
+/// untouched, so going back to that release keeps its settings.
+static NEWER_VERSION: AtomicU64 = AtomicU64::new(0);
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// Entry `i` upgrades a config from version `i` to `i + 1`.
+const MIGRATIONS: [Migration; CONFIG_VERSION as usize] = [
+    migrate_legacy_keys,
+    migrate_retired_rpc_urls,
+    migrate_backup_warning,
+];
+
+/// All user settings. The keypair is kept apart, so configs can be shared safely.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(default)]
+pub struct AppConfig {
+    pub version: u32,
+    pub appearance: Appearance,
+    pub explorer: Explorer,
+    pub power_level: PowerLevel,
+    pub mining_settings: MiningSettings,
+    pub mining_schedule: MiningSchedule,
+    pub priority_fee: PriorityFee,
+    pub rpc_url: RpcUrl,
+    pub is_onboarded: IsOnboarded,
+    pub tip_config: TipConfig,
+    pub notification_settings: NotificationSettings,
+    pub watchlist: Watchlist,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            appearance: Appearance::Dark,
+            explorer: Explorer::Solana,
+            power_level: PowerLevel(10),
+            mining_settings: MiningSettings::default(),
+            mining_schedule: MiningSchedule::default(),
+            priority_fee: PriorityFee(0),
+            rpc_url: RpcUrl(RPC_URL.to_string()),
+            is_onboarded: IsOnboarded(false),
+            tip_config: TipConfig::default(),
+            notification_settings: NotificationSettings::default(),
+            watchlist: Watchlist::default(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Parses a config of any supported version, migrating it to the current one.
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        let value = serde_json::from_str(json).map_err(|e| ConfigError::Invalid(e.to_string()))?;
+        Self::from_value(value)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    fn from_value(value: Value) -> Result<Self, ConfigError> {
+        let map = migrate(value)?;
+        serde_json::from_value(Value::Object(map)).map_err(|e| ConfigError::Invalid(e.to_string()))
+    }
+
+    /// Like `from_value`, but a field that does not parse keeps its default instead of failing
+    /// the whole config. Also returns the names of those fields.
+    fn from_value_lenient(value: Value) -> Result<(Self, Vec<String>), ConfigError> {
+        let map = migrate(value)?;
+        let Ok(Value::Object(mut merged)) = serde_json::to_value(AppConfig::default()) else {
+            return Err(ConfigError::Invalid(
+                "Failed to serialize defaults".to_string(),
+            ));
+        };
+        let mut rejected = vec![];
+        for (key, value) in map {
+            let default = merged.insert(key.clone(), value);
+            if serde_json::from_value::<AppConfig>(Value::Object(merged.clone())).is_err() {
+                match default {
+                    Some(default) => merged.insert(key.clone(), default),
+                    None => merged.remove(&key),
+                };
+                rejected.push(key);
+            }
+        }
+        let config = serde_json::from_value(Value::Object(merged))
+            .map_err(|e| ConfigError::Invalid(e.to_string()))?;
+        Ok((config, rejected))
+    }
+}
+
+/// Upgrades a stored config of any supported version to the current schema.
+fn migrate(value: Value) -> Result<Map<String, Value>, ConfigError> {
+    let Value::Object(mut map) = value else {
+        return Err(ConfigError::Invalid("Expected a json object".to_string()));
+    };
+    let version = map.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version.gt(&(CONFIG_VERSION as u64)) {
+        return Err(ConfigError::UnsupportedVersion(version));
+    }
+    for migrate in &MIGRATIONS[version as usize..] {
+        migrate(&mut map);
+    }
+    map.insert("version".to_string(), CONFIG_VERSION.into());
+    Ok(map)
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// Not json, or does not match the schema.
+    Invalid(String),
+    /// Written by a newer release of the app.
+    UnsupportedVersion(u64),
+    Storage(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Invalid(err) => write!(f, "Invalid config: {}", err),
+            ConfigError::UnsupportedVersion(version) => write!(
+                f,
+                "Config version {} is newer than this app supports ({})",
+                version, CONFIG_VERSION
+            ),
+            ConfigError::Storage(err) => write!(f, "Failed to save config: {}", err),
+        }
+    }
+}
+
+/// Version 0 is the flat map of legacy keys. Only the rpc url was stored under another name.
+fn migrate_legacy_keys(map: &mut Map<String, Value>) {
+    if let Some(rpc) = map.remove("rpc") {
+        map.insert("rpc_url".to_string(), rpc);
+    }
+}
+
+fn migrate_retired_rpc_urls(map: &mut Map<String, Value>) {
+    let is_retired = map
+        .get("rpc_url")
+        .and_then(Value::as_str)
+        .map(|url| RETIRED_RPC_URLS.contains(&url))
+        .unwrap_or(false);
+    if is_retired {
+        map.insert("rpc_url".to_string(), Value::String(RPC_URL.to_string()));
+    }
+}
+
+/// The backup warning now follows whether the keypair was actually backed up.
+fn migrate_backup_warning(map: &mut Map<String, Value>) {
+    map.remove("show_backup_warning");
+}
+
+/// Loads the stored config. Falls back to the legacy keys when no config has been saved yet,
+/// and removes them once they are migrated. Settings that fail to parse are reset one by one.
+pub fn load_config() -> AppConfig {
+    let stored = read_value(CONFIG_KEY);
+    let is_legacy = stored.is_none();
+    let stored_version = stored
+        .as_ref()
+        .and_then(|v| v.get("version"))
+        .and_then(Value::as_u64);
+    let value = stored.unwrap_or_else(|| {
+        let legacy = LEGACY_KEYS
+            .iter()
+            .filter_map(|key| read_value(key).map(|v| (key.to_string(), v)))
+            .collect();
+        Value::Object(legacy)
+    });
+    match AppConfig::from_value_lenient(value) {
+        Ok((config, rejected)) => {
+            if !rejected.is_empty() {
+                log::error!("Failed to load settings: {}", rejected.join(", "));
+                publish(
+                    Severity::Warning,
+                    "Some settings were reset",
+                    rejected.join(", "),
+                );
+            }
+            if stored_version.ne(&Some(CONFIG_VERSION as u64)) || !rejected.is_empty() {
+                match save_config(&config) {
+                    Ok(()) if is_legacy => remove_legacy_keys(),
+                    Ok(()) => {}
+                    Err(err) => log::error!("{}", err),
+                }
+            }
+            config
+        }
+        Err(ConfigError::UnsupportedVersion(version)) => {
+            log::error!("Failed to load config: version {}", version);
+            NEWER_VERSION.store(version, Ordering::Relaxed);
+            publish(
+                Severity::Warning,
+                "Settings are read-only",
+                "They were saved by a newer release of the app. Defaults are used for now, and changes are not saved.",
+            );
+            AppConfig::default()
+        }
+        Err(err) => {
+            log::error!("Failed to load config: {}", err);
+            publish(Severity::Warning, "Settings were reset", err);
+            AppConfig::default()
+        }
+    }
+}
+
+/// Writes the whole config under a single key. Refused while a newer config is stored.
+pub fn save_config(config: &AppConfig) -> Result<(), ConfigError> {
+    let newer_version = NEWER_VERSION.load(Ordering::Relaxed);
+    if newer_version.gt(&0) {
+        return Err(ConfigError::UnsupportedVersion(newer_version));
+    }
+
+    #[cfg(feature = "web")]
+    LocalStorage::set(CONFIG_KEY, config).map_err(|e| ConfigError::Storage(e.to_string()))?;
+
+    #[cfg(feature = "desktop")]
+    set_key_value(CONFIG_KEY, config).map_err(|e| ConfigError::Storage(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(feature = "web")]
+fn remove_legacy_keys() {
+    for key in LEGACY_KEYS {
+        LocalStorage::delete(key);
+    }
+}
+
+#[cfg(feature = "desktop")]
+fn remove_legacy_keys() {
+    if let Err(err) = remove_keys(&LEGACY_KEYS) {
+        log::error!("Failed to remove legacy settings: {}", err);
+    }
+}
+
+#[cfg(feature = "web")]
+fn read_value(key: &str) -> Option<Value> {
+    LocalStorage::get(key).ok()
+}
+
+#[cfg(feature = "desktop")]
+fn read_value(key: &str) -> Option<Value> {
+    get_value(key).ok()
+}
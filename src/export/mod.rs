@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use chrono::{TimeZone, Utc};
+use ore_types::TransferType;
+use serde::Serialize;
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "desktop")]
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{
+    gateway::{history, Gateway, HistoryKind, TransferQuery},
+    notifications::{publish, Severity},
+};
+
+#[cfg(all(test, feature = "desktop"))]
+mod tests;
+
+/// Transfers requested per page while exporting.
+const EXPORT_PAGE_SIZE: usize = 100;
+
+/// Upper bound on pages fetched, in case the api keeps reporting more.
+const EXPORT_MAX_PAGES: usize = 1_000;
+
+/// Decimal places of a lamport amount in SOL.
+const SOL_DECIMALS: u32 = 9;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Json => "application/json",
+        }
+    }
+}
+
+/// A single row of exported history.
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ExportRecord {
+    pub timestamp: String,
+    pub signature: String,
+    pub kind: String,
+    pub from: String,
+    pub to: String,
+    /// Mars amount, written out exactly from the token's smallest unit.
+    pub amount: String,
+    pub memo: String,
+    /// Network fees paid in SOL, if known. Only mining and claim transactions sent from this device
+    /// have fees on record.
+    pub fee: Option<String>,
+    #[serde(skip)]
+    ts: i64,
+}
+
+/// Collects the wallet's transfers from the api and merges in the local mining and claim history.
+pub async fn collect_history(gateway: &Gateway, pubkey: Pubkey) -> Vec<ExportRecord> {
+    let mut fees: HashMap<String, u64> = HashMap::new();
+    let local = history();
+    for record in &local {
+        fees.insert(record.signature.clone(), record.fee);
+    }
+
+    // Page through the api
+    let query = TransferQuery::user(Some(pubkey));
+    let mut records = vec![];
+    let mut cursor: Option<String> = None;
+    for page in 0..EXPORT_MAX_PAGES {
+        let offset = (page * EXPORT_PAGE_SIZE) as u64;
+        let Some(res) = gateway
+            .list_transfers(&query, cursor.as_deref(), offset, EXPORT_PAGE_SIZE)
+            .await
+        else {
+            log::error!("Failed to fetch transfers for export");
+            publish(
+                Severity::Warning,
+                "Export incomplete",
+                "Some transfers could not be fetched and are missing from the export",
+            );
+            break;
+        };
+        cursor = res.data.last().map(|t| t.sig.clone());
+        for transfer in res.data {
+            let kind = match transfer.transfer_type {
+                TransferType::Claim => "claim",
+                TransferType::Mine => "mine",
+                TransferType::Spl => "transfer",
+            };
+            records.push(ExportRecord {
+                timestamp: format_timestamp(transfer.ts),
+                fee: fees
+                    .get(&transfer.sig)
+                    .map(|f| format_units(*f, SOL_DECIMALS)),
+                signature: transfer.sig,
+                kind: kind.to_string(),
+                from: transfer.from_address,
+                to: transfer.to_address,
+                amount: format_units(transfer.amount, mars::TOKEN_DECIMALS.into()),
+                memo: transfer.memo.unwrap_or_default(),
+                ts: transfer.ts,
+            });
+        }
+        if !res.has_more {
+            break;
+        }
+    }
+
+    // Add local records the api does not know about
+    for record in local {
+        if records.iter().any(|r| r.signature.eq(&record.signature)) {
+            continue;
+        }
+        let kind = match record.kind {
+            HistoryKind::Mine => "mine",
+            HistoryKind::Claim => "claim",
+        };
+        records.push(ExportRecord {
+            timestamp: format_timestamp(record.timestamp),
+            signature: record.signature,
+            kind: kind.to_string(),
+            from: String::new(),
+            to: pubkey.to_string(),
+            amount: format_units(record.amount, mars::TOKEN_DECIMALS.into()),
+            memo: String::new(),
+            fee: Some(format_units(record.fee, SOL_DECIMALS)),
+            ts: record.timestamp,
+        });
+    }
+
+    records.sort_by(|a, b| b.ts.cmp(&a.ts));
+    records
+}
+
+pub fn to_csv(records: &[ExportRecord]) -> String {
+    let mut csv = String::from("timestamp,signature,type,from,to,amount_mars,memo,fee_sol\n");
+    for r in records {
+        let row = [
+            r.timestamp.clone(),
+            r.signature.clone(),
+            r.kind.clone(),
+            r.from.clone(),
+            r.to.clone(),
+            r.amount.clone(),
+            r.memo.clone(),
+            r.fee.clone().unwrap_or_default(),
+        ];
+        let row: Vec<String> = row.iter().map(|v| csv_field(v)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+pub fn to_json(records: &[ExportRecord]) -> String {
+    serde_json::to_string_pretty(records).unwrap_or_else(|_| "[]".to_string())
+}
+
+pub fn export_file_name(format: ExportFormat) -> String {
+    format!(
+        "mars-history-{}.{}",
+        Utc::now().format("%Y-%m-%d"),
+        format.extension()
+    )
+}
+
+/// Saves the export, asking the user where on desktop.
+pub async fn save_export(contents: String, format: ExportFormat) -> Result<(), String> {
+    save_file(
+        contents,
+        &export_file_name(format),
+        format.extension(),
+        format.mime_type(),
+    )
+    .await
+}
+
+/// Triggers a browser download of the file.
+#[cfg(feature = "web")]
+pub async fn save_file(
+    contents: String,
+    file_name: &str,
+    _extension: &str,
+    mime_type: &str,
+) -> Result<(), String> {
+    use wasm_bindgen::{JsCast, JsValue};
+    use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+    let parts = js_sys::Array::of1(&JsValue::from_str(&contents));
+    let mut options = BlobPropertyBag::new();
+    options.type_(mime_type);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)
+        .map_err(|e| format!("{:?}", e))?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(|e| format!("{:?}", e))?;
+    let anchor = gloo::utils::document()
+        .create_element("a")
+        .map_err(|e| format!("{:?}", e))?
+        .dyn_into::<HtmlAnchorElement>()
+        .map_err(|e| format!("{:?}", e))?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+    Url::revoke_object_url(&url).ok();
+    Ok(())
+}
+
+/// Asks the user where to save the file and writes it there.
+#[cfg(feature = "desktop")]
+pub async fn save_file(
+    contents: String,
+    file_name: &str,
+    extension: &str,
+    _mime_type: &str,
+) -> Result<(), String> {
+    let Some(file) = rfd::AsyncFileDialog::new()
+        .set_file_name(file_name)
+        .add_filter(extension, &[extension])
+        .save_file()
+        .await
+    else {
+        // Cancelled by the user
+        return Ok(());
+    };
+    std::fs::write(file.path(), contents).map_err(|e| e.to_string())
+}
+
+/// Quotes the field if needed. Text that spreadsheets would read as a formula is prefixed with an
+/// apostrophe so it stays text.
+fn csv_field(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Formats an amount in base units as a decimal, without going through floats.
+fn format_units(amount: u64, decimals: u32) -> String {
+    let scale = 10u64.pow(decimals);
+    let whole = amount / scale;
+    let frac = amount % scale;
+    if frac.eq(&0) {
+        return whole.to_string();
+    }
+    let frac = format!("{:0width$}", frac, width = decimals as usize);
+    format!("{}.{}", whole, frac.trim_end_matches('0'))
+}
+
+fn format_timestamp(ts: i64) -> String {
+    Utc.timestamp_opt(ts, 0)
+        .single()
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_default()
+}
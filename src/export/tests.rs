@@ -0,0 +1,57 @@
+use super::{csv_field, format_units, to_csv, ExportRecord, SOL_DECIMALS};
+
+fn record(amount: &str, memo: &str, fee: Option<&str>) -> ExportRecord {
+    ExportRecord {
+        timestamp: "2024-03-01T00:00:00+00:00".to_string(),
+        signature: "sig".to_string(),
+        kind: "transfer".to_string(),
+        from: "from".to_string(),
+        to: "to".to_string(),
+        amount: amount.to_string(),
+        memo: memo.to_string(),
+        fee: fee.map(str::to_string),
+        ts: 0,
+    }
+}
+
+#[test]
+fn format_units_is_exact() {
+    assert_eq!(format_units(0, 9), "0");
+    assert_eq!(format_units(5_000, SOL_DECIMALS), "0.000005");
+    assert_eq!(format_units(1_500_000_000, SOL_DECIMALS), "1.5");
+    assert_eq!(format_units(u64::MAX, 9), "18446744073.709551615");
+    assert_eq!(
+        format_units(123_456_789_012_345_678, 9),
+        "123456789.012345678"
+    );
+}
+
+#[test]
+fn csv_field_quotes_separators() {
+    assert_eq!(csv_field("gm"), "gm");
+    assert_eq!(csv_field("a,b"), "\"a,b\"");
+    assert_eq!(csv_field("say \"gm\""), "\"say \"\"gm\"\"\"");
+    assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+}
+
+#[test]
+fn csv_field_escapes_formulas() {
+    assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+    assert_eq!(csv_field("+1"), "'+1");
+    assert_eq!(csv_field("-1"), "'-1");
+    assert_eq!(csv_field("@SUM(A1)"), "'@SUM(A1)");
+}
+
+#[test]
+fn to_csv_writes_header_and_rows() {
+    let records = [
+        record("1.5", "rent, march", Some("0.000005")),
+        record("0", "=1+1", None),
+    ];
+    assert_eq!(
+        to_csv(&records),
+        "timestamp,signature,type,from,to,amount_mars,memo,fee_sol\n\
+         2024-03-01T00:00:00+00:00,sig,transfer,from,to,1.5,\"rent, march\",0.000005\n\
+         2024-03-01T00:00:00+00:00,sig,transfer,from,to,0,'=1+1,\n"
+    );
+}
@@ -0,0 +1,233 @@
+#[cfg(test)]
+mod tests;
+
+use directories::ProjectDirs;
+use fs2::FileExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::notifications::{publish, Severity};
+
+/// Serializes storage access between threads. The lock file does the same between app instances.
+static STORAGE_LOCK: Mutex<()> = Mutex::new(());
+
+fn filepath() -> PathBuf {
+    // Keep tests away from the user's data
+    if cfg!(test) {
+        return std::env::temp_dir()
+            .join(format!("mars-test-{}", std::process::id()))
+            .join("config.json");
+    }
+    if let Some(proj_dirs) = ProjectDirs::from("com", "mars", "app") {
+        // This gives you a platform-specific config directory
+        // For example, on Windows, this might be C:\Users\Username\AppData\Roaming\YourCompany\YourApp\config
+        // On Linux, /home/username/.config/YourApp, and on macOS, /Users/username/Library/Application Support/com.YourCompany.YourApp
+        proj_dirs.config_dir().join("config.json")
+    } else {
+        panic!("Can't load project directory")
+    }
+}
+
+/// Path next to the storage file, such as `config.json.bak`.
+fn sibling(suffix: &str) -> PathBuf {
+    let mut path = OsString::from(filepath());
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+/// Exclusive access to the storage file, released on drop.
+struct StorageLock {
+    _file: File,
+    _guard: MutexGuard<'static, ()>,
+}
+
+fn lock_storage() -> io::Result<StorageLock> {
+    let guard = STORAGE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    ensure_dir_exists()?;
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(sibling(".lock"))?;
+    file.lock_exclusive()?;
+    Ok(StorageLock {
+        _file: file,
+        _guard: guard,
+    })
+}
+
+/// Reads a storage file. Returns `None` if it is missing or empty.
+fn parse(path: &Path) -> io::Result<Option<Map<String, Value>>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    if contents.trim().is_empty() {
+        return Ok(None);
+    }
+    match serde_json::from_str::<Value>(&contents)? {
+        Value::Object(data) => Ok(Some(data)),
+        _ => Err(io::Error::new(
+            ErrorKind::InvalidData,
+            "Storage is not a json object",
+        )),
+    }
+}
+
+fn read_storage() -> io::Result<Map<String, Value>> {
+    let path = filepath();
+    let err = match parse(&path) {
+        Ok(Some(data)) => return Ok(data),
+        Ok(None) => None,
+        Err(err) => Some(err),
+    };
+
+    // The file is missing, empty or corrupt. Recover the last good version if there is one.
+    match parse(&sibling(".bak")) {
+        Ok(Some(data)) => {
+            let reason = err
+                .as_ref()
+                .map(|err| err.to_string())
+                .unwrap_or_else(|| "Storage file is missing".to_string());
+            log::error!("Restoring storage from backup: {}", reason);
+            publish(Severity::Warning, "Settings restored from backup", reason);
+            if err.is_some() {
+                fs::copy(&path, sibling(".corrupt")).ok();
+            }
+            write_atomic(&path, serde_json::to_string(&data)?.as_bytes())?;
+            Ok(data)
+        }
+        _ => match err {
+            // Never treat unreadable storage as empty, or the next write would erase it
+            Some(err) => {
+                fs::copy(&path, sibling(".corrupt")).ok();
+                Err(err)
+            }
+            None => Ok(Map::new()),
+        },
+    }
+}
+
+fn write_storage(data: &Map<String, Value>) -> io::Result<()> {
+    let path = filepath();
+
+    // Keep the last good version as a backup
+    if let Ok(Some(_)) = parse(&path) {
+        write_atomic(&sibling(".bak"), &fs::read(&path)?)?;
+    }
+
+    let contents = serde_json::to_string(data)?;
+    write_atomic(&path, contents.as_bytes())
+}
+
+/// Writes to a temporary file and renames it into place, so a crash leaves either the old or the new contents.
+fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp = OsString::from(path);
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    let mut file = File::create(&tmp)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)?;
+
+    // Persist the rename itself
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        File::open(dir)?.sync_all()?;
+    }
+
+    Ok(())
+}
+
+pub fn set_key_value<T: Serialize + DeserializeOwned + 'static>(
+    key: &str,
+    value: &T,
+) -> io::Result<()> {
+    let v = serde_json::to_value(value)?;
+    let _lock = lock_storage()?;
+    let mut data = read_storage()?;
+    data.insert(key.to_string(), v);
+    write_storage(&data)
+}
+
+/// Sets several keys in a single write, so either all of them are saved or none.
+pub fn set_key_values(values: &[(&str, Value)]) -> io::Result<()> {
+    let _lock = lock_storage()?;
+    let mut data = read_storage()?;
+    for (key, value) in values {
+        data.insert(key.to_string(), value.clone());
+    }
+    write_storage(&data)
+}
+
+pub fn get_value<T: Serialize + DeserializeOwned + 'static>(key: &str) -> io::Result<T> {
+    let _lock = lock_storage()?;
+    let data = read_storage()?;
+    if let Some(v) = data.get(key) {
+        serde_json::from_value(v.clone()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    } else {
+        Err(io::Error::new(io::ErrorKind::NotFound, "No value for key"))
+    }
+}
+
+/// Removes keys from the storage file. Missing keys are ignored.
+pub fn remove_keys(keys: &[&str]) -> io::Result<()> {
+    let _lock = lock_storage()?;
+    let mut data = read_storage()?;
+    let mut is_changed = false;
+    for key in keys {
+        is_changed |= data.remove(*key).is_some();
+    }
+    if is_changed {
+        write_storage(&data)?;
+    }
+    Ok(())
+}
+
+/// Path of a data file kept next to the config, such as `tip_log.json`.
+fn data_path(name: &str) -> PathBuf {
+    filepath().with_file_name(format!("{}.json", name))
+}
+
+/// Reads data kept in a file of its own. Data written often stays out of the config and keypair.
+/// Until the file is first written, falls back to the key earlier releases stored it under.
+pub fn read_file<T: DeserializeOwned>(name: &str) -> io::Result<T> {
+    let _lock = lock_storage()?;
+    let value: Value = match fs::read_to_string(data_path(name)) {
+        Ok(contents) => serde_json::from_str(&contents)?,
+        Err(err) if err.kind() == ErrorKind::NotFound => read_storage()?
+            .remove(name)
+            .ok_or_else(|| io::Error::new(ErrorKind::NotFound, "No value for key"))?,
+        Err(err) => return Err(err),
+    };
+    serde_json::from_value(value).map_err(|e| io::Error::new(ErrorKind::InvalidData, e))
+}
+
+/// Replaces the contents of a data file, and drops any copy left in the storage file.
+pub fn write_file<T: Serialize>(name: &str, value: &T) -> io::Result<()> {
+    let contents = serde_json::to_string(value)?;
+    let _lock = lock_storage()?;
+    write_atomic(&data_path(name), contents.as_bytes())?;
+    let mut data = read_storage()?;
+    if data.remove(name).is_some() {
+        write_storage(&data)?;
+    }
+    Ok(())
+}
+
+fn ensure_dir_exists() -> io::Result<()> {
+    // Check if the parent directory exists, and if not, create it
+    if let Some(parent_dir) = filepath().parent() {
+        if !parent_dir.exists() {
+            fs::create_dir_all(parent_dir)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use std::{
+    fs,
+    io::ErrorKind,
+    sync::{Mutex, MutexGuard},
+};
+
+use serde_json::{json, Value};
+
+use super::{data_path, get_value, lock_storage, read_file, set_key_value, sibling, write_file};
+
+/// Tests share one storage file, so they take turns.
+static TEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// Clears the storage file and its siblings, then writes the given ones.
+fn setup(files: &[(&str, &str)]) -> MutexGuard<'static, ()> {
+    let guard = TEST_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let _lock = lock_storage().unwrap();
+    for suffix in ["", ".bak", ".corrupt", ".tmp"] {
+        fs::remove_file(sibling(suffix)).ok();
+    }
+    for (suffix, contents) in files {
+        fs::write(sibling(suffix), contents).unwrap();
+    }
+    guard
+}
+
+fn stored(suffix: &str) -> Value {
+    serde_json::from_str(&fs::read_to_string(sibling(suffix)).unwrap()).unwrap()
+}
+
+#[test]
+fn restores_corrupt_storage_from_backup() {
+    let _guard = setup(&[("", "{\"a\": 1,"), (".bak", "{\"a\": 1}")]);
+    assert_eq!(get_value::<u64>("a").unwrap(), 1);
+    assert_eq!(stored(""), json!({ "a": 1 }));
+    assert_eq!(
+        fs::read_to_string(sibling(".corrupt")).unwrap(),
+        "{\"a\": 1,"
+    );
+}
+
+#[test]
+fn corrupt_storage_without_backup_is_an_error() {
+    let _guard = setup(&[("", "{\"a\": 1,")]);
+    let err = get_value::<u64>("a").unwrap_err();
+    assert_ne!(err.kind(), ErrorKind::NotFound);
+
+    // Writing must not replace the unreadable file with an empty map
+    assert!(set_key_value("b", &2).is_err());
+    assert_eq!(fs::read_to_string(sibling("")).unwrap(), "{\"a\": 1,");
+    assert!(sibling(".corrupt").exists());
+}
+
+#[test]
+fn writes_replace_the_file_and_keep_a_backup() {
+    let _guard = setup(&[]);
+    assert_eq!(
+        get_value::<u64>("a").unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    set_key_value("a", &1).unwrap();
+    set_key_value("a", &2).unwrap();
+    assert_eq!(stored(""), json!({ "a": 2 }));
+    assert_eq!(stored(".bak"), json!({ "a": 1 }));
+    assert!(!sibling(".tmp").exists());
+}
+
+#[test]
+fn data_files_take_over_their_storage_key() {
+    let _guard = setup(&[("", "{\"samples\": [1, 2], \"a\": 1}")]);
+    fs::remove_file(data_path("samples")).ok();
+    assert_eq!(read_file::<Vec<u64>>("samples").unwrap(), vec![1, 2]);
+    write_file("samples", &[3]).unwrap();
+    assert_eq!(read_file::<Vec<u64>>("samples").unwrap(), vec![3]);
+    assert_eq!(stored(""), json!({ "a": 1 }));
+}
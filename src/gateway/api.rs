@@ -0,0 +1,40 @@
+use async_trait::async_trait;
+
+use super::{GatewayError, GatewayResult};
+
+/// Requests to the Mars api, so it can be swapped out in tests.
+#[async_trait(?Send)]
+pub trait Api {
+    /// Fetches a path of the api and returns the response body.
+    async fn get(&self, path: &str, query: &[(&str, String)]) -> GatewayResult<String>;
+}
+
+pub struct HttpApi {
+    url: String,
+}
+
+impl HttpApi {
+    pub fn new(url: String) -> Self {
+        HttpApi { url }
+    }
+}
+
+#[async_trait(?Send)]
+impl Api for HttpApi {
+    async fn get(&self, path: &str, query: &[(&str, String)]) -> GatewayResult<String> {
+        let client = reqwest::Client::new();
+        let res = client
+            .get(format!("{}{}", self.url, path))
+            .query(query)
+            .send()
+            .await
+            .map_err(|err| {
+                log::error!("{:?}", err);
+                GatewayError::NetworkUnavailable
+            })?;
+        res.text().await.map_err(|err| {
+            log::error!("{:?}", err);
+            GatewayError::NetworkUnavailable
+        })
+    }
+}
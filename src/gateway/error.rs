@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[cfg(feature = "desktop")]
 use solana_client::client_error::ClientError;
 #[cfg(feature = "web")]
@@ -11,12 +13,36 @@ pub enum GatewayError {
     FailedAta,
     FailedRegister,
     TransactionTimeout,
+    TransactionFailed,
     NetworkUnavailable,
     AccountNotFound,
     SimulationFailed,
+    InsufficientFunds,
+    NoOpenBus,
+    WalletUnavailable,
     Unknown,
 }
 
+impl fmt::Display for GatewayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            GatewayError::FailedDeserialization => "Failed to read an account",
+            GatewayError::FailedAta => "Failed to create a token account",
+            GatewayError::FailedRegister => "Failed to register the miner",
+            GatewayError::TransactionTimeout => "The transaction was not confirmed in time",
+            GatewayError::TransactionFailed => "The transaction failed",
+            GatewayError::NetworkUnavailable => "The network is unavailable",
+            GatewayError::AccountNotFound => "An account was not found",
+            GatewayError::SimulationFailed => "The transaction failed in simulation",
+            GatewayError::InsufficientFunds => "Not enough SOL to pay the fee",
+            GatewayError::NoOpenBus => "Every bus is out of rewards until the epoch resets",
+            GatewayError::WalletUnavailable => "The wallet could not be loaded",
+            GatewayError::Unknown => "Something went wrong",
+        };
+        f.write_str(msg)
+    }
+}
+
 impl From<ClientError> for GatewayError {
     fn from(err: ClientError) -> Self {
         let msg = err.to_string();
@@ -0,0 +1,629 @@
+//! In-memory chain and api, for running the gateway without a network.
+
+use std::{
+    cell::{RefCell, RefMut},
+    collections::HashMap,
+    rc::Rc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use bytemuck::{Pod, Zeroable};
+use mars::{
+    state::{Bus, Proof, Treasury},
+    utils::Discriminator,
+    BUS_ADDRESSES, TREASURY_ADDRESS,
+};
+use serde_json::json;
+use solana_account_decoder::parse_token::{UiTokenAccount, UiTokenAmount};
+use solana_client::{
+    rpc_config::{
+        RpcProgramAccountsConfig, RpcSendTransactionConfig, RpcSimulateTransactionConfig,
+    },
+    rpc_response::RpcTokenAccountBalance,
+};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    commitment_config::CommitmentConfig,
+    compute_budget,
+    hash::Hash,
+    keccak::Hash as KeccakHash,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    system_instruction::SystemInstruction,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use solana_transaction_status::TransactionConfirmationStatus;
+use spl_token::instruction::TokenInstruction;
+
+use super::{
+    mars_token_account_address, proof_pubkey, Api, Gateway, GatewayError, GatewayResult, Rpc,
+    SignatureStatus, Simulation, TipConfig, LAMPORTS_PER_SIGNATURE,
+};
+
+pub const REWARD_RATE: u64 = 1_000;
+pub const BUS_REWARDS: u64 = 1_000_000;
+pub const TOKEN_ACCOUNT_RENT: u64 = 2_039_280;
+/// Rent exempt minimum of an account without data.
+pub const SYSTEM_ACCOUNT_RENT: u64 = 890_880;
+
+/// Compute units charged per instruction.
+const UNITS_PER_INSTRUCTION: u64 = 1_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TokenAccount {
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(Clone)]
+pub struct ChainState {
+    pub slot: u64,
+    pub unix_timestamp: i64,
+    pub blockhash: Hash,
+    pub treasury: Treasury,
+    pub buses: Vec<Bus>,
+    /// Keyed by authority.
+    pub proofs: HashMap<Pubkey, Proof>,
+    pub lamports: HashMap<Pubkey, u64>,
+    pub token_accounts: HashMap<Pubkey, TokenAccount>,
+    pub statuses: HashMap<Signature, SignatureStatus>,
+    /// Every transaction accepted, including dropped ones.
+    pub sent: Vec<Transaction>,
+    /// Simulations to fail before succeeding.
+    pub failing_simulations: usize,
+    /// Sends to reject before accepting.
+    pub failing_sends: usize,
+    /// Sends to accept but never land.
+    pub dropped_sends: usize,
+    /// Sends to land but fail, as if the chain changed after simulating.
+    pub failing_landings: usize,
+}
+
+impl ChainState {
+    fn new() -> Self {
+        let mut treasury = Treasury::zeroed();
+        treasury.reward_rate = REWARD_RATE;
+        let buses = (0..BUS_ADDRESSES.len())
+            .map(|_| {
+                let mut bus = Bus::zeroed();
+                bus.rewards = BUS_REWARDS;
+                bus
+            })
+            .collect();
+        ChainState {
+            slot: 1,
+            // Mid-epoch, so miners do not try to reset it
+            unix_timestamp: 30,
+            blockhash: Hash::new_unique(),
+            treasury,
+            buses,
+            proofs: HashMap::new(),
+            lamports: HashMap::new(),
+            token_accounts: HashMap::new(),
+            statuses: HashMap::new(),
+            sent: vec![],
+            failing_simulations: 0,
+            failing_sends: 0,
+            dropped_sends: 0,
+            failing_landings: 0,
+        }
+    }
+
+    /// Runs the transaction against this state. Fails without side effects on the caller's copy.
+    fn execute(&mut self, tx: &Transaction) -> Result<(), String> {
+        let payer = *tx.message.account_keys.first().ok_or("Missing fee payer")?;
+        debit(&mut self.lamports, &payer, LAMPORTS_PER_SIGNATURE)?;
+        for ix in &tx.message.instructions {
+            let program_id = tx.message.account_keys[ix.program_id_index as usize];
+            let accounts: Vec<Pubkey> = ix
+                .accounts
+                .iter()
+                .map(|i| tx.message.account_keys[*i as usize])
+                .collect();
+            self.execute_ix(program_id, &accounts, &ix.data)?;
+        }
+
+        // Like the runtime, only allow the payer to be drained or left rent exempt
+        let balance = self.lamports.get(&payer).copied().unwrap_or(0);
+        if balance.gt(&0) && balance.lt(&SYSTEM_ACCOUNT_RENT) {
+            return Err("Insufficient funds for rent".to_string());
+        }
+        Ok(())
+    }
+
+    fn execute_ix(
+        &mut self,
+        program_id: Pubkey,
+        accounts: &[Pubkey],
+        data: &[u8],
+    ) -> Result<(), String> {
+        if program_id.eq(&compute_budget::id()) || program_id.eq(&spl_memo::id()) {
+            Ok(())
+        } else if program_id.eq(&system_program::id()) {
+            match bincode::deserialize(data).map_err(|e| e.to_string())? {
+                SystemInstruction::Transfer { lamports } => {
+                    debit(&mut self.lamports, &accounts[0], lamports)?;
+                    *self.lamports.entry(accounts[1]).or_default() += lamports;
+                    Ok(())
+                }
+                ix => Err(format!("Unsupported system instruction {:?}", ix)),
+            }
+        } else if program_id.eq(&spl_associated_token_account::id()) {
+            let (address, owner) = (accounts[1], accounts[2]);
+            if self.token_accounts.contains_key(&address) {
+                return Err("Token account already exists".to_string());
+            }
+            debit(&mut self.lamports, &accounts[0], TOKEN_ACCOUNT_RENT)?;
+            self.token_accounts
+                .insert(address, TokenAccount { owner, amount: 0 });
+            Ok(())
+        } else if program_id.eq(&spl_token::id()) {
+            self.execute_token_ix(accounts, data)
+        } else if program_id.eq(&mars::ID) {
+            self.execute_mars_ix(accounts, data)
+        } else {
+            Err(format!("Unknown program {}", program_id))
+        }
+    }
+
+    fn execute_token_ix(&mut self, accounts: &[Pubkey], data: &[u8]) -> Result<(), String> {
+        match TokenInstruction::unpack(data).map_err(|e| e.to_string())? {
+            TokenInstruction::Transfer { amount } => {
+                let (from, to, authority) = (accounts[0], accounts[1], accounts[2]);
+                let source = self
+                    .token_accounts
+                    .get_mut(&from)
+                    .ok_or("Source token account not found")?;
+                if source.owner.ne(&authority) {
+                    return Err("Not the token account owner".to_string());
+                }
+                source.amount = source
+                    .amount
+                    .checked_sub(amount)
+                    .ok_or("Insufficient token balance")?;
+                self.token_accounts
+                    .get_mut(&to)
+                    .ok_or("Destination token account not found")?
+                    .amount += amount;
+                Ok(())
+            }
+            TokenInstruction::CloseAccount => {
+                let (address, destination) = (accounts[0], accounts[1]);
+                match self.token_accounts.get(&address) {
+                    Some(account) if account.amount.eq(&0) => {
+                        self.token_accounts.remove(&address);
+                        *self.lamports.entry(destination).or_default() += TOKEN_ACCOUNT_RENT;
+                        Ok(())
+                    }
+                    Some(_) => Err("Token account is not empty".to_string()),
+                    None => Err("Token account not found".to_string()),
+                }
+            }
+            ix => Err(format!("Unsupported token instruction {:?}", ix)),
+        }
+    }
+
+    fn execute_mars_ix(&mut self, accounts: &[Pubkey], data: &[u8]) -> Result<(), String> {
+        let signer = accounts[0];
+        let kind = data.first().ok_or("Empty instruction")?;
+        if kind.eq(&mars_ix_kind(MarsIx::Register)) {
+            if self.proofs.contains_key(&signer) {
+                return Err("Proof already exists".to_string());
+            }
+            let mut proof = Proof::zeroed();
+            proof.authority = signer;
+            self.proofs.insert(signer, proof);
+            Ok(())
+        } else if kind.eq(&mars_ix_kind(MarsIx::Mine)) {
+            let reward_rate = self.treasury.reward_rate;
+            let bus_id = BUS_ADDRESSES
+                .iter()
+                .position(|bus| accounts.contains(bus))
+                .ok_or("Missing bus")?;
+            let bus = &mut self.buses[bus_id];
+            bus.rewards = bus
+                .rewards
+                .checked_sub(reward_rate)
+                .ok_or("Bus has insufficient rewards")?;
+            let proof = self.proofs.get_mut(&signer).ok_or("Proof not found")?;
+            proof.claimable_rewards += reward_rate;
+            proof.total_hashes += 1;
+            proof.total_rewards += reward_rate;
+            Ok(())
+        } else if kind.eq(&mars_ix_kind(MarsIx::Claim)) {
+            let amount = read_u64(data)?;
+            let beneficiary = *accounts
+                .iter()
+                .find(|a| self.token_accounts.contains_key(*a))
+                .ok_or("Beneficiary token account not found")?;
+            let proof = self.proofs.get_mut(&signer).ok_or("Proof not found")?;
+            proof.claimable_rewards = proof
+                .claimable_rewards
+                .checked_sub(amount)
+                .ok_or("Claim exceeds rewards")?;
+            self.token_accounts.get_mut(&beneficiary).unwrap().amount += amount;
+            self.treasury.total_claimed_rewards += amount;
+            Ok(())
+        } else if kind.eq(&mars_ix_kind(MarsIx::Reset)) {
+            self.treasury.last_reset_at = self.unix_timestamp;
+            for bus in self.buses.iter_mut() {
+                bus.rewards = BUS_REWARDS;
+            }
+            Ok(())
+        } else {
+            Err(format!("Unsupported mars instruction {}", kind))
+        }
+    }
+
+    fn account(&self, pubkey: &Pubkey) -> Option<Account> {
+        let (owner, data) = if pubkey.eq(&TREASURY_ADDRESS) {
+            (mars::ID, account_data(&self.treasury))
+        } else if let Some(id) = BUS_ADDRESSES.iter().position(|bus| bus.eq(pubkey)) {
+            (mars::ID, account_data(&self.buses[id]))
+        } else if let Some(proof) = self
+            .proofs
+            .values()
+            .find(|p| proof_pubkey(p.authority).eq(pubkey))
+        {
+            (mars::ID, account_data(proof))
+        } else if pubkey.eq(&sysvar::clock::ID) {
+            let clock = Clock {
+                slot: self.slot,
+                unix_timestamp: self.unix_timestamp,
+                ..Default::default()
+            };
+            (sysvar::id(), bincode::serialize(&clock).unwrap())
+        } else if self.token_accounts.contains_key(pubkey) {
+            return Some(Account {
+                lamports: TOKEN_ACCOUNT_RENT,
+                owner: spl_token::id(),
+                ..Default::default()
+            });
+        } else {
+            return self.lamports.get(pubkey).map(|lamports| Account {
+                lamports: *lamports,
+                owner: system_program::id(),
+                ..Default::default()
+            });
+        };
+        Some(Account {
+            lamports: 1,
+            data,
+            owner,
+            ..Default::default()
+        })
+    }
+}
+
+/// Shared handle to an in-memory chain. Clones see the same state.
+#[derive(Clone)]
+pub struct FakeChain(Rc<RefCell<ChainState>>);
+
+impl FakeChain {
+    pub fn new() -> Self {
+        FakeChain(Rc::new(RefCell::new(ChainState::new())))
+    }
+
+    pub fn state(&self) -> RefMut<ChainState> {
+        self.0.borrow_mut()
+    }
+
+    pub fn airdrop(&self, to: Pubkey, lamports: u64) {
+        *self.state().lamports.entry(to).or_default() += lamports;
+    }
+
+    /// Creates the owner's Mars token account, holding the given amount.
+    pub fn mint_to(&self, owner: Pubkey, amount: u64) {
+        self.state()
+            .token_accounts
+            .entry(mars_token_account_address(owner))
+            .or_insert(TokenAccount { owner, amount: 0 })
+            .amount += amount;
+    }
+
+    pub fn token_balance(&self, owner: Pubkey) -> Option<u64> {
+        self.state()
+            .token_accounts
+            .get(&mars_token_account_address(owner))
+            .map(|account| account.amount)
+    }
+
+    pub fn balance(&self, pubkey: Pubkey) -> u64 {
+        self.state().lamports.get(&pubkey).copied().unwrap_or(0)
+    }
+
+    pub fn proof(&self, authority: Pubkey) -> Option<Proof> {
+        self.state().proofs.get(&authority).copied()
+    }
+
+    pub fn sent_count(&self) -> usize {
+        self.state().sent.len()
+    }
+}
+
+#[async_trait(?Send)]
+impl Rpc for FakeChain {
+    async fn get_account(&self, pubkey: &Pubkey) -> GatewayResult<Account> {
+        self.state()
+            .account(pubkey)
+            .ok_or(GatewayError::AccountNotFound)
+    }
+
+    async fn get_account_data(&self, pubkey: &Pubkey) -> GatewayResult<Vec<u8>> {
+        self.get_account(pubkey).await.map(|account| account.data)
+    }
+
+    async fn get_program_accounts_with_config(
+        &self,
+        program_id: &Pubkey,
+        _config: RpcProgramAccountsConfig,
+    ) -> GatewayResult<Vec<(Pubkey, Account)>> {
+        if program_id.ne(&mars::ID) {
+            return Ok(vec![]);
+        }
+        let state = self.state();
+        Ok(state
+            .proofs
+            .values()
+            .map(|proof| proof_pubkey(proof.authority))
+            .filter_map(|address| state.account(&address).map(|account| (address, account)))
+            .collect())
+    }
+
+    async fn get_balance(&self, pubkey: &Pubkey) -> GatewayResult<u64> {
+        Ok(self.balance(*pubkey))
+    }
+
+    async fn get_slot(&self) -> GatewayResult<u64> {
+        Ok(self.state().slot)
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> GatewayResult<u64> {
+        match data_len {
+            0 => Ok(SYSTEM_ACCOUNT_RENT),
+            _ => Ok(TOKEN_ACCOUNT_RENT),
+        }
+    }
+
+    async fn get_token_supply(&self, _mint: &Pubkey) -> GatewayResult<UiTokenAmount> {
+        let supply = self.state().token_accounts.values().map(|a| a.amount).sum();
+        Ok(ui_token_amount(supply))
+    }
+
+    async fn get_token_account(&self, pubkey: &Pubkey) -> GatewayResult<Option<UiTokenAccount>> {
+        Ok(self.state().token_accounts.get(pubkey).map(|account| {
+            serde_json::from_value(json!({
+                "mint": mars::MINT_ADDRESS.to_string(),
+                "owner": account.owner.to_string(),
+                "tokenAmount": ui_token_amount(account.amount),
+                "state": "initialized",
+                "isNative": false,
+                "extensions": [],
+            }))
+            .unwrap()
+        }))
+    }
+
+    async fn get_token_account_balance(&self, pubkey: &Pubkey) -> GatewayResult<UiTokenAmount> {
+        self.state()
+            .token_accounts
+            .get(pubkey)
+            .map(|account| ui_token_amount(account.amount))
+            .ok_or(GatewayError::AccountNotFound)
+    }
+
+    async fn get_token_largest_accounts(
+        &self,
+        _mint: &Pubkey,
+    ) -> GatewayResult<Vec<RpcTokenAccountBalance>> {
+        let mut accounts: Vec<_> = self
+            .state()
+            .token_accounts
+            .iter()
+            .map(|(address, account)| (*address, account.amount))
+            .collect();
+        accounts.sort_by(|a, b| b.1.cmp(&a.1));
+        Ok(accounts
+            .into_iter()
+            .take(20)
+            .map(|(address, amount)| RpcTokenAccountBalance {
+                address: address.to_string(),
+                amount: ui_token_amount(amount),
+            })
+            .collect())
+    }
+
+    async fn get_latest_blockhash_with_commitment(
+        &self,
+        _commitment: CommitmentConfig,
+    ) -> GatewayResult<(Hash, u64)> {
+        let state = self.state();
+        Ok((state.blockhash, state.slot))
+    }
+
+    async fn simulate_transaction_with_config(
+        &self,
+        tx: &Transaction,
+        _config: RpcSimulateTransactionConfig,
+    ) -> GatewayResult<Simulation> {
+        let mut state = self.state();
+        if state.failing_simulations.gt(&0) {
+            state.failing_simulations -= 1;
+            return Ok(Simulation {
+                err: Some("Simulation failed".to_string()),
+                units_consumed: None,
+            });
+        }
+        let mut sim = state.clone();
+        Ok(match sim.execute(tx) {
+            Ok(()) => Simulation {
+                err: None,
+                units_consumed: Some(
+                    UNITS_PER_INSTRUCTION.saturating_mul(tx.message.instructions.len() as u64),
+                ),
+            },
+            Err(err) => Simulation {
+                err: Some(err),
+                units_consumed: None,
+            },
+        })
+    }
+
+    async fn send_transaction_with_config(
+        &self,
+        tx: &Transaction,
+        _config: RpcSendTransactionConfig,
+    ) -> GatewayResult<Signature> {
+        let mut state = self.state();
+        if state.failing_sends.gt(&0) {
+            state.failing_sends -= 1;
+            return Err(GatewayError::NetworkUnavailable);
+        }
+        if tx.verify().is_err() || tx.message.recent_blockhash.ne(&state.blockhash) {
+            return Err(GatewayError::Unknown);
+        }
+        let sig = tx.signatures[0];
+        state.sent.push(tx.clone());
+        if state.dropped_sends.gt(&0) {
+            state.dropped_sends -= 1;
+            return Ok(sig);
+        }
+        let mut next = state.clone();
+        let res = if next.failing_landings.gt(&0) {
+            next.failing_landings -= 1;
+            Err("Instruction failed".to_string())
+        } else {
+            next.execute(tx)
+        };
+        // Sent without preflight, so a failing transaction still lands and pays its fee
+        if let Err(err) = &res {
+            log::error!("Transaction failed: {}", err);
+            let failing_landings = next.failing_landings;
+            next = state.clone();
+            next.failing_landings = failing_landings;
+            let payer = tx.message.account_keys[0];
+            debit(&mut next.lamports, &payer, LAMPORTS_PER_SIGNATURE)
+                .map_err(|_| GatewayError::Unknown)?;
+        }
+        next.slot += 1;
+        next.statuses.insert(
+            sig,
+            SignatureStatus {
+                confirmation_status: Some(TransactionConfirmationStatus::Confirmed),
+                err: res.err(),
+            },
+        );
+        *state = next;
+        Ok(sig)
+    }
+
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> GatewayResult<Vec<Option<SignatureStatus>>> {
+        let state = self.state();
+        Ok(signatures
+            .iter()
+            .map(|sig| state.statuses.get(sig).cloned())
+            .collect())
+    }
+}
+
+/// Serves canned responses by path.
+#[derive(Default)]
+pub struct FakeApi {
+    pub responses: HashMap<String, String>,
+}
+
+impl FakeApi {
+    pub fn with(mut self, path: &str, body: impl ToString) -> Self {
+        self.responses.insert(path.to_string(), body.to_string());
+        self
+    }
+}
+
+#[async_trait(?Send)]
+impl Api for FakeApi {
+    async fn get(&self, path: &str, _query: &[(&str, String)]) -> GatewayResult<String> {
+        self.responses
+            .get(path)
+            .cloned()
+            .ok_or(GatewayError::NetworkUnavailable)
+    }
+}
+
+impl Gateway {
+    /// A gateway on the fake chain, signing with the given keypair and retrying without delay.
+    pub fn offline(chain: &FakeChain, api: FakeApi, keypair: Keypair) -> Self {
+        Gateway {
+            rpc: Box::new(chain.clone()),
+            api: Box::new(api),
+            rpc_url: "offline".to_string(),
+            tip: TipConfig::default(),
+            keypair: Some(keypair),
+            retry_interval: Duration::ZERO,
+            pubsub: None,
+        }
+    }
+}
+
+enum MarsIx {
+    Register,
+    Mine,
+    Claim,
+    Reset,
+}
+
+/// Leading byte of each instruction's data, taken from the program's own builders.
+fn mars_ix_kind(ix: MarsIx) -> u8 {
+    let key = Pubkey::default();
+    let data = match ix {
+        MarsIx::Register => mars::instruction::register(key).data,
+        MarsIx::Mine => {
+            mars::instruction::mine(key, BUS_ADDRESSES[0], KeccakHash::default().into(), 0).data
+        }
+        MarsIx::Claim => mars::instruction::claim(key, key, 0).data,
+        MarsIx::Reset => mars::instruction::reset(key).data,
+    };
+    data[0]
+}
+
+/// Reads the trailing u64 argument of an instruction.
+fn read_u64(data: &[u8]) -> Result<u64, String> {
+    let bytes = data
+        .len()
+        .checked_sub(8)
+        .and_then(|start| data[start..].try_into().ok())
+        .ok_or("Missing argument")?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn debit(lamports: &mut HashMap<Pubkey, u64>, from: &Pubkey, amount: u64) -> Result<(), String> {
+    let balance = lamports.entry(*from).or_default();
+    *balance = balance
+        .checked_sub(amount)
+        .ok_or_else(|| format!("{} has insufficient lamports", from))?;
+    Ok(())
+}
+
+/// Account data as the program lays it out: a discriminator padded to 8 bytes, then the struct.
+fn account_data<T: Pod + Discriminator>(value: &T) -> Vec<u8> {
+    let mut data = vec![0u8; 8];
+    data[0] = T::discriminator() as u8;
+    data.extend_from_slice(bytemuck::bytes_of(value));
+    data
+}
+
+fn ui_token_amount(amount: u64) -> UiTokenAmount {
+    let ui_amount = amount as f64 / 10f64.powi(mars::TOKEN_DECIMALS as i32);
+    serde_json::from_value(json!({
+        "uiAmount": ui_amount,
+        "decimals": mars::TOKEN_DECIMALS,
+        "amount": amount.to_string(),
+        "uiAmountString": ui_amount.to_string(),
+    }))
+    .unwrap()
+}
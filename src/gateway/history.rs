@@ -0,0 +1,73 @@
+#[cfg(feature = "web")]
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::signature::Signature;
+#[cfg(feature = "desktop")]
+use solana_sdk::signature::Signature;
+#[cfg(feature = "desktop")]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(feature = "web")]
+use web_time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "desktop")]
+use crate::file::{read_file, write_file};
+
+/// Stored apart from the config, as it is written after every mining transaction.
+const HISTORY_KEY: &str = "history";
+const HISTORY_LIMIT: usize = 1_000;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum HistoryKind {
+    Mine,
+    Claim,
+}
+
+/// A mining or claim transaction sent from this device, kept for bookkeeping.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct HistoryRecord {
+    pub kind: HistoryKind,
+    pub signature: String,
+    /// Mars amount, in the token's smallest unit.
+    pub amount: u64,
+    /// Lamports paid in base and priority fees. Tips are not fees and are left out.
+    pub fee: u64,
+    pub timestamp: i64,
+}
+
+/// Returns the local mining and claim history, newest first.
+#[cfg(feature = "web")]
+pub fn history() -> Vec<HistoryRecord> {
+    LocalStorage::get(HISTORY_KEY).unwrap_or_default()
+}
+
+/// Returns the local mining and claim history, newest first.
+#[cfg(feature = "desktop")]
+pub fn history() -> Vec<HistoryRecord> {
+    read_file(HISTORY_KEY).unwrap_or_default()
+}
+
+pub fn record_history(kind: HistoryKind, sig: Signature, amount: u64, fee: u64) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let mut records = history();
+    records.insert(
+        0,
+        HistoryRecord {
+            kind,
+            signature: sig.to_string(),
+            amount,
+            fee,
+            timestamp,
+        },
+    );
+    records.truncate(HISTORY_LIMIT);
+
+    #[cfg(feature = "web")]
+    LocalStorage::set(HISTORY_KEY, &records).ok();
+
+    #[cfg(feature = "desktop")]
+    write_file(HISTORY_KEY, &records).ok();
+}
@@ -1,30 +1,44 @@
+mod api;
 mod async_result;
 mod error;
+#[cfg(all(test, feature = "desktop"))]
+mod fake;
+mod history;
+mod preview;
 mod pubkey;
+mod rpc;
+mod supply;
+#[cfg(all(test, feature = "desktop"))]
+mod tests;
+mod tip;
+mod transfers;
 
-use std::str::FromStr;
 #[cfg(feature = "desktop")]
 use std::time::Duration;
 
+pub use api::*;
 pub use async_result::*;
 use cached::proc_macro::cached;
 pub use error::*;
-#[cfg(feature = "web")]
-use gloo_storage::{LocalStorage, Storage};
+pub use history::*;
 use mars::{
     state::{Bus, Proof, Treasury},
-    utils::AccountDeserialize,
+    utils::{AccountDeserialize, Discriminator},
     BUS_ADDRESSES, TREASURY_ADDRESS,
 };
 use ore_types::{response::GetTransfersResponse, Transfer};
+pub use preview::*;
 pub use pubkey::*;
-use rand::Rng;
+pub use rpc::*;
 #[cfg(feature = "desktop")]
-use solana_account_decoder::parse_token::UiTokenAccount;
+use solana_account_decoder::{parse_token::UiTokenAccount, UiAccountEncoding};
 #[cfg(feature = "desktop")]
 use solana_client::{
-    nonblocking::rpc_client::RpcClient,
-    rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig},
+    rpc_config::{
+        RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig,
+        RpcSimulateTransactionConfig,
+    },
+    rpc_filter::{Memcmp, RpcFilterType},
     rpc_response::RpcTokenAccountBalance,
 };
 #[cfg(feature = "web")]
@@ -42,14 +56,17 @@ use solana_client_wasm::{
         transaction::Transaction,
     },
     utils::{
-        rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig},
+        rpc_config::{
+            RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig,
+            RpcSimulateTransactionConfig,
+        },
+        rpc_filter::{Memcmp, RpcFilterType},
         rpc_response::RpcTokenAccountBalance,
     },
-    WasmClient,
 };
 #[cfg(feature = "web")]
 use solana_extra_wasm::{
-    account_decoder::parse_token::UiTokenAccount,
+    account_decoder::{parse_token::UiTokenAccount, UiAccountEncoding},
     program::{
         spl_associated_token_account::{
             get_associated_token_address, instruction::create_associated_token_account,
@@ -79,7 +96,16 @@ use spl_associated_token_account::{
 #[cfg(feature = "web")]
 use web_time::Duration;
 
-use crate::metrics::{track, AppEvent};
+pub use supply::*;
+pub use tip::*;
+pub use transfers::*;
+
+use crate::{
+    metrics::{track, AppEvent},
+    notifications::{publish, Severity},
+    pubsub::{PubsubHandle, Topic, Update},
+    wallet::load_wallet,
+};
 
 pub const API_URL: &str = "https://mars-api-lthm.onrender.com"; // MI: dummy
 pub const RPC_URL: &str = "https://rpc.ironforge.network/mainnet?apiKey=01HX8QVGAHM7J9CYC9GEVHXMHF";
@@ -93,84 +119,162 @@ const GATEWAY_RETRIES: usize = 4;
 const CONFIRM_RETRIES: usize = 8;
 const SIMULATION_RETRIES: usize = 4;
 const DEFAULT_PRIORITY_FEE: u64 = 1_000;
-const TIP_AMOUNT: u64 = 100_000;
+const DYNAMIC_CU_MARGIN: u32 = 1_000;
+
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Milliseconds between attempts to send or confirm a transaction.
+const RETRY_INTERVAL: u64 = 2000;
+
+/// Longest wait between retries.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Waits `base` after the first failed attempt, and twice as long after every one after.
+fn tip_ix(payer: &Pubkey, amount: u64) -> Option<Instruction> {
+    if amount.eq(&0) {
+        return None;
+    }
+    Some(solana_sdk::system_instruction::transfer(
+        payer,
+        &tip_account(),
+        amount,
+    ))
+}
+
+pub fn backoff(base: Duration, attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    base.saturating_mul(factor).min(MAX_BACKOFF)
+}
 
 pub struct Gateway {
-    #[cfg(feature = "web")]
-    pub rpc: WasmClient,
-    #[cfg(feature = "desktop")]
-    pub rpc: RpcClient,
-    api_url: String,
+    pub rpc: Box<dyn Rpc>,
+    api: Box<dyn Api>,
     rpc_url: String,
+    tip: TipConfig,
+    /// Signs in place of the stored keypair.
+    keypair: Option<Keypair>,
+    retry_interval: Duration,
+    /// Confirms transactions by subscription, when connected.
+    pubsub: Option<PubsubHandle>,
 }
 
 impl Gateway {
-    pub fn new(api_url: String, rpc_url: String) -> Self {
+    pub fn new(api_url: String, rpc_url: String, tip: TipConfig) -> Self {
         Gateway {
-            api_url,
-            rpc_url: rpc_url.clone(),
             #[cfg(feature = "web")]
-            rpc: WasmClient::new(&rpc_url),
+            rpc: Box::new(NetworkRpc::new(&rpc_url)),
             #[cfg(feature = "desktop")]
-            rpc: RpcClient::new(rpc_url),
+            rpc: Box::new(NetworkRpc::new(rpc_url.clone())),
+            api: Box::new(HttpApi::new(api_url)),
+            rpc_url,
+            tip,
+            keypair: None,
+            retry_interval: Duration::from_millis(RETRY_INTERVAL),
+            pubsub: None,
+        }
+    }
+
+    pub fn with_pubsub(mut self, pubsub: Option<PubsubHandle>) -> Self {
+        self.pubsub = pubsub;
+        self
+    }
+
+    /// The keypair transactions are signed with.
+    pub fn signer(&self) -> GatewayResult<Keypair> {
+        match &self.keypair {
+            Some(keypair) => Keypair::from_bytes(&keypair.to_bytes())
+                .map_err(|_| GatewayError::WalletUnavailable),
+            None => signer(),
         }
     }
 
+    /// How long to wait before retrying after `attempt` failures.
+    pub fn retry_delay(&self, attempt: u32) -> Duration {
+        backoff(self.retry_interval, attempt)
+    }
+
     pub async fn get_clock(&self) -> GatewayResult<Clock> {
-        let data = self
-            .rpc
-            .get_account_data(&sysvar::clock::ID)
-            .await
-            .map_err(GatewayError::from)?;
+        let data = self.rpc.get_account_data(&sysvar::clock::ID).await?;
         bincode::deserialize::<Clock>(&data).or(Err(GatewayError::FailedDeserialization))
     }
 
     pub async fn get_proof(&self, authority: Pubkey) -> GatewayResult<Proof> {
-        let data = self
+        let data = self.rpc.get_account_data(&proof_pubkey(authority)).await?;
+        Proof::try_from_bytes(&data)
+            .copied()
+            .or(Err(GatewayError::FailedDeserialization))
+    }
+
+    /// Fetches the proof account of every miner.
+    pub async fn get_proofs(&self) -> GatewayResult<Vec<Proof>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![
+                RpcFilterType::DataSize(8 + std::mem::size_of::<Proof>() as u64),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![Proof::discriminator() as u8])),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            with_context: None,
+        };
+        let accounts = self
             .rpc
-            .get_account_data(&proof_pubkey(authority))
-            .await
-            .map_err(GatewayError::from)?;
-        Ok(*Proof::try_from_bytes(&data).expect("Failed to parse proof"))
+            .get_program_accounts_with_config(&mars::ID, config)
+            .await?;
+        Ok(accounts
+            .into_iter()
+            .filter_map(|(_, account)| Proof::try_from_bytes(&account.data).ok().copied())
+            .collect())
     }
 
     pub async fn get_bus(&self, id: usize) -> GatewayResult<Bus> {
-        let bus_address = BUS_ADDRESSES.get(id).unwrap();
-        let data = self
-            .rpc
-            .get_account_data(bus_address)
-            .await
-            .map_err(GatewayError::from)?;
-        Ok(*Bus::try_from_bytes(&data).expect("Failed to parse bus"))
+        let bus_address = BUS_ADDRESSES.get(id).ok_or(GatewayError::AccountNotFound)?;
+        let data = self.rpc.get_account_data(bus_address).await?;
+        Bus::try_from_bytes(&data)
+            .copied()
+            .or(Err(GatewayError::FailedDeserialization))
     }
 
     pub async fn get_treasury(&self) -> GatewayResult<Treasury> {
-        let data = self
-            .rpc
-            .get_account_data(&TREASURY_ADDRESS)
-            .await
-            .map_err(GatewayError::from)?;
-        Ok(*Treasury::try_from_bytes(&data).expect("Failed to parse treasury account"))
+        let data = self.rpc.get_account_data(&TREASURY_ADDRESS).await?;
+        Treasury::try_from_bytes(&data)
+            .copied()
+            .or(Err(GatewayError::FailedDeserialization))
+    }
+
+    /// Samples the current token supply and emission parameters.
+    pub async fn get_supply_sample(&self) -> GatewayResult<SupplySample> {
+        let clock = self.get_clock().await?;
+        let treasury = self.get_treasury().await?;
+        let total_supply = self.rpc.get_token_supply(&mars::MINT_ADDRESS).await?;
+        let treasury_balance = self
+            .get_token_account(&mars_token_account_address(TREASURY_ADDRESS))
+            .await?
+            .map(|t| t.token_amount.amount.parse().unwrap_or(0))
+            .unwrap_or(0);
+        Ok(SupplySample {
+            ts: clock.unix_timestamp,
+            circulating_supply: treasury.total_claimed_rewards,
+            total_supply: total_supply.amount.parse().unwrap_or(0),
+            treasury_balance,
+            reward_rate: treasury.reward_rate,
+            difficulty: difficulty_bits(&treasury.difficulty.to_bytes()),
+        })
     }
 
     pub async fn get_token_account(
         &self,
         pubkey: &Pubkey,
     ) -> GatewayResult<Option<UiTokenAccount>> {
-        self.rpc
-            .get_token_account(pubkey)
-            .await
-            .map_err(GatewayError::from)
+        self.rpc.get_token_account(pubkey).await
     }
 
     pub async fn get_token_largest_accounts(
         &self,
         pubkey: &Pubkey,
     ) -> GatewayResult<Vec<RpcTokenAccountBalance>> {
-        self.rpc
-            .get_token_largest_accounts(pubkey)
-            .await
-            .map_err(GatewayError::from)
+        self.rpc.get_token_largest_accounts(pubkey).await
     }
 
     pub async fn send_and_confirm(
@@ -179,12 +283,37 @@ impl Gateway {
         dynamic_cus: bool,
         skip_confirm: bool,
     ) -> GatewayResult<Signature> {
-        let signer = signer();
+        // Charged against the session cap before sending, so sends in flight together can't
+        // exceed it. Given back if the transaction did not land.
+        let tip = if self.rpc_url.eq(RPC_URL) {
+            take_tip(&self.tip)
+        } else {
+            0
+        };
+        let res = self
+            .send_with_tip(ixs, dynamic_cus, skip_confirm, tip)
+            .await;
+        if tip.gt(&0) {
+            match res {
+                Ok(sig) => record_tip(sig, tip, !skip_confirm),
+                Err(_) => release_tip(tip),
+            }
+        }
+        res
+    }
+
+    async fn send_with_tip(
+        &self,
+        ixs: &[Instruction],
+        dynamic_cus: bool,
+        skip_confirm: bool,
+        tip: u64,
+    ) -> GatewayResult<Signature> {
+        let signer = self.signer()?;
         let (hash, slot) = self
             .rpc
             .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
-            .await
-            .map_err(GatewayError::from)?;
+            .await?;
         let send_cfg = RpcSendTransactionConfig {
             skip_preflight: true,
             preflight_commitment: Some(CommitmentLevel::Confirmed),
@@ -195,18 +324,7 @@ impl Gateway {
 
         // If default rpc, add tip
         let mut ixs = ixs.to_vec();
-        if self.rpc_url.eq(RPC_URL) {
-            let mut rng = rand::thread_rng();
-            let tip_accounts = &[
-                // Miraland donation account only
-                Pubkey::from_str("9h9TXFtSsDAiL5kpCRZuKUxPE4Nv3W56fcSyUC3zmQip").unwrap(),
-            ];
-            let i = rng.gen_range(0..tip_accounts.len());
-            let ix = solana_sdk::system_instruction::transfer(
-                &signer.pubkey(),
-                &tip_accounts[i],
-                TIP_AMOUNT,
-            );
+        if let Some(ix) = tip_ix(&signer.pubkey(), tip) {
             ixs.push(ix);
         }
 
@@ -232,8 +350,6 @@ impl Gateway {
                 .await;
             match sim_res {
                 Ok(sim_res) => {
-                    #[cfg(feature = "desktop")]
-                    let sim_res = sim_res.value;
                     if let Some(err) = sim_res.err {
                         println!("Simulaton error: {:?}", err);
                         sim_attempts += 1;
@@ -241,7 +357,7 @@ impl Gateway {
                         if dynamic_cus {
                             println!("Dynamic CUs: {:?}", units_consumed);
                             let cu_budget_ix = ComputeBudgetInstruction::set_compute_unit_limit(
-                                units_consumed as u32 + 1000,
+                                units_consumed as u32 + DYNAMIC_CU_MARGIN,
                             );
                             let cu_price_ix = ComputeBudgetInstruction::set_compute_unit_price(
                                 DEFAULT_PRIORITY_FEE,
@@ -281,42 +397,27 @@ impl Gateway {
                     if skip_confirm {
                         return Ok(sig);
                     }
-                    for _ in 0..CONFIRM_RETRIES {
-                        match self.rpc.get_signature_statuses(&[sig]).await {
-                            Ok(signature_statuses) => {
-                                #[cfg(feature = "desktop")]
-                                let signature_statuses = signature_statuses.value;
-                                log::info!("Sig status: {:?}", signature_statuses[0]);
-                                for signature_status in signature_statuses {
-                                    if let Some(signature_status) = signature_status.as_ref() {
-                                        if signature_status.confirmation_status.is_some() {
-                                            let current_commitment = signature_status
-                                                .confirmation_status
-                                                .as_ref()
-                                                .unwrap();
-                                            log::info!("Commitment: {:?}", current_commitment);
-                                            match current_commitment {
-                                                TransactionConfirmationStatus::Processed => {}
-                                                TransactionConfirmationStatus::Confirmed
-                                                | TransactionConfirmationStatus::Finalized => {
-                                                    log::info!("Confirmed: true");
-                                                    return Ok(sig);
-                                                }
-                                            }
-                                        } else {
-                                            log::info!("No status");
-                                        }
-                                    }
-                                }
-                            }
-
-                            // Handle confirmation errors
-                            Err(err) => {
-                                log::error!("Error confirming: {:?}", err);
-                                // TODO
-                            }
+                    // One confirmation budget per attempt. A subscription spends it waiting,
+                    // then the status is checked once in case the notification was missed.
+                    let status = match self.pubsub.as_ref().filter(|p| p.is_connected()) {
+                        Some(pubsub) => match self.await_confirmation(pubsub, sig).await {
+                            Some(status) => Some(status),
+                            None => self.poll_confirmation(sig, 1).await,
+                        },
+                        None => self.poll_confirmation(sig, CONFIRM_RETRIES).await,
+                    };
+                    let confirmed = match status {
+                        Some(Ok(())) => true,
+                        // Sending the same transaction again would fail the same way
+                        Some(Err(err)) => {
+                            log::error!("Transaction failed: {}", err);
+                            return Err(GatewayError::TransactionFailed);
                         }
-                        async_std::task::sleep(Duration::from_millis(2000)).await;
+                        None => false,
+                    };
+                    if confirmed {
+                        log::info!("Confirmed: true");
+                        return Ok(sig);
                     }
                     log::info!("Confirmed: false");
                 }
@@ -328,7 +429,7 @@ impl Gateway {
             }
 
             // Retry
-            async_std::task::sleep(Duration::from_millis(2000)).await;
+            async_std::task::sleep(self.retry_interval).await;
             attempts += 1;
             if attempts > GATEWAY_RETRIES {
                 return Err(GatewayError::TransactionTimeout);
@@ -336,10 +437,74 @@ impl Gateway {
         }
     }
 
+    /// Waits for a notification that the transaction was confirmed, with its error if it failed.
+    /// None if none came in time.
+    async fn await_confirmation(
+        &self,
+        pubsub: &PubsubHandle,
+        sig: Signature,
+    ) -> Option<Result<(), String>> {
+        let rx = pubsub.subscribe(Topic::Signature(sig));
+        let timeout = self.retry_interval.saturating_mul(CONFIRM_RETRIES as u32);
+        let confirmation = async move {
+            while let Ok(update) = rx.recv().await {
+                if let Update::Signature(res) = update {
+                    log::info!("Sig notification: {:?}", res);
+                    return Some(res);
+                }
+            }
+            None
+        };
+        async_std::future::timeout(timeout, confirmation)
+            .await
+            .unwrap_or(None)
+    }
+
+    /// Polls the status of a transaction until it is confirmed, or the retries run out. Returns
+    /// the error a confirmed transaction failed with, or `None` if it was not confirmed in time.
+    async fn poll_confirmation(
+        &self,
+        sig: Signature,
+        retries: usize,
+    ) -> Option<Result<(), String>> {
+        for _ in 0..retries {
+            match self.rpc.get_signature_statuses(&[sig]).await {
+                Ok(signature_statuses) => {
+                    log::info!("Sig status: {:?}", signature_statuses.first());
+                    for signature_status in signature_statuses {
+                        if let Some(status) = signature_status {
+                            log::info!("Commitment: {:?}", status.confirmation_status);
+                            match status.confirmation_status {
+                                Some(TransactionConfirmationStatus::Confirmed)
+                                | Some(TransactionConfirmationStatus::Finalized) => {
+                                    return Some(match status.err {
+                                        Some(err) => Err(err),
+                                        None => Ok(()),
+                                    });
+                                }
+                                Some(TransactionConfirmationStatus::Processed) | None => {}
+                            }
+                        } else {
+                            log::info!("No status");
+                        }
+                    }
+                }
+
+                // Handle confirmation errors
+                Err(err) => {
+                    log::error!("Error confirming: {:?}", err);
+                    // TODO
+                }
+            }
+            async_std::task::sleep(self.retry_interval).await;
+        }
+        None
+    }
+
     // Mars
     pub async fn register_mars(&self) -> GatewayResult<()> {
         // Return early, if account is already initialized
-        let signer = signer();
+        let signer = self.signer()?;
         let proof_address = proof_pubkey(signer.pubkey());
         if self.rpc.get_account(&proof_address).await.is_ok() {
             return Ok(());
@@ -357,13 +522,17 @@ impl Gateway {
     }
 
     pub async fn claim_mars(&self, amount: u64, priority_fee: u64) -> GatewayResult<Signature> {
-        let signer = signer();
+        let signer = self.signer()?;
         let beneficiary = mars_token_account_address(signer.pubkey());
         let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(CU_LIMIT_CLAIM);
         let cu_price_ix = ComputeBudgetInstruction::set_compute_unit_price(priority_fee);
         let ix = mars::instruction::claim(signer.pubkey(), beneficiary, amount);
-        self.send_and_confirm(&[cu_limit_ix, cu_price_ix, ix], false, false)
-            .await
+        let sig = self
+            .send_and_confirm(&[cu_limit_ix, cu_price_ix, ix], false, false)
+            .await?;
+        let fee = network_fee(CU_LIMIT_CLAIM, priority_fee);
+        record_history(HistoryKind::Claim, sig, amount, fee);
+        Ok(sig)
     }
 
     pub async fn transfer_mars(
@@ -376,7 +545,7 @@ impl Gateway {
         self.create_token_account_mars(to).await?;
 
         // Submit transfer ix
-        let signer = signer();
+        let signer = self.signer()?;
         let from_token_account = mars_token_account_address(signer.pubkey());
         let to_token_account = mars_token_account_address(to);
         let memo_ix = spl_memo::build_memo(&memo.into_bytes(), &[&signer.pubkey()]);
@@ -393,18 +562,120 @@ impl Gateway {
             .await
     }
 
+    pub async fn transfer_sol(
+        &self,
+        amount: u64,
+        to: Pubkey,
+        memo: String,
+    ) -> GatewayResult<Signature> {
+        let signer = self.signer()?;
+        let mut ixs = vec![];
+        if !memo.is_empty() {
+            ixs.push(spl_memo::build_memo(
+                &memo.into_bytes(),
+                &[&signer.pubkey()],
+            ));
+        }
+        ixs.push(solana_sdk::system_instruction::transfer(
+            &signer.pubkey(),
+            &to,
+            amount,
+        ));
+        self.send_and_confirm(&ixs, true, false).await
+    }
+
+    /// Largest SOL transfer the signer can make. Leaves the account rent exempt, since the
+    /// runtime rejects transfers that leave less, and enough to pay for the transfer itself.
+    pub async fn max_sol_transfer(&self) -> GatewayResult<u64> {
+        let balance = self.rpc.get_balance(&self.signer()?.pubkey()).await?;
+        let rent = self.rpc.get_minimum_balance_for_rent_exemption(0).await?;
+        let fee_reserve = LAMPORTS_PER_SIGNATURE
+            .saturating_mul(2)
+            .saturating_add(self.tip_amount());
+        Ok(balance.saturating_sub(rent).saturating_sub(fee_reserve))
+    }
+
+    /// Moves all Mars and SOL held by the signer to another address.
+    /// The Mars token account is closed in the same transaction so its rent is swept as well.
+    pub async fn sweep(&self, to: Pubkey) -> GatewayResult<Signature> {
+        let signer = self.signer()?;
+        let from_token_account = mars_token_account_address(signer.pubkey());
+        let mut ixs = vec![];
+
+        // Transfer remaining Mars and close the token account, if it exists
+        let mut token_account_lamports = 0;
+        if let Ok(account) = self.rpc.get_account(&from_token_account).await {
+            token_account_lamports = account.lamports;
+            let mars_balance = self
+                .rpc
+                .get_token_account_balance(&from_token_account)
+                .await?
+                .amount
+                .parse::<u64>()
+                .unwrap_or(0);
+            if mars_balance.gt(&0) {
+                let to_token_account = self.create_token_account_mars(to).await?;
+                ixs.push(
+                    spl_token::instruction::transfer(
+                        &spl_token::ID,
+                        &from_token_account,
+                        &to_token_account,
+                        &signer.pubkey(),
+                        &[&signer.pubkey()],
+                        mars_balance,
+                    )
+                    .unwrap(),
+                );
+            }
+            ixs.push(
+                spl_token::instruction::close_account(
+                    &spl_token::ID,
+                    &from_token_account,
+                    &signer.pubkey(),
+                    &signer.pubkey(),
+                    &[&signer.pubkey()],
+                )
+                .unwrap(),
+            );
+        }
+
+        // Transfer all remaining SOL, less the fee of this transaction
+        let sol_balance = self.rpc.get_balance(&signer.pubkey()).await?;
+        let sol_amount = sol_balance
+            .saturating_add(token_account_lamports)
+            .saturating_sub(LAMPORTS_PER_SIGNATURE)
+            .saturating_sub(self.tip_amount());
+        if sol_amount.eq(&0) {
+            return Err(GatewayError::InsufficientFunds);
+        }
+        ixs.push(solana_sdk::system_instruction::transfer(
+            &signer.pubkey(),
+            &to,
+            sol_amount,
+        ));
+
+        // Fixed compute budget so the fee is known ahead of time
+        let sig = self.send_and_confirm(&ixs, false, false).await?;
+        track(AppEvent::Sweep, None);
+        Ok(sig)
+    }
+
+    /// Returns the tip appended to the next transaction sent through the default rpc.
+    pub fn tip_amount(&self) -> u64 {
+        if self.rpc_url.eq(RPC_URL) {
+            self.tip.next_tip(session_tips())
+        } else {
+            0
+        }
+    }
+
     pub async fn create_token_account_mars(&self, owner: Pubkey) -> GatewayResult<Pubkey> {
         // Build instructions.
-        let signer = signer();
+        let signer = self.signer()?;
 
         // Check if account already exists.
         let token_account_address = mars_token_account_address(owner);
-        match self
-            .rpc
-            .get_token_account(&token_account_address)
-            .await
-            .map_err(GatewayError::from)
-        {
+        match self.rpc.get_token_account(&token_account_address).await {
             Ok(token_account) => {
                 if token_account.is_some() {
                     return Ok(token_account_address);
@@ -439,74 +710,56 @@ impl Gateway {
 
     // API
     pub async fn get_transfer(&self, sig: String) -> Option<Transfer> {
-        let client = reqwest::Client::new();
-        match client
-            .get(format!("{}/transfers/{}", self.api_url, sig))
-            .send()
-            .await
-        {
-            Ok(res) => res.json::<Transfer>().await.ok(),
-            Err(e) => {
-                log::error!("{:?}", e);
-                None
-            }
-        }
+        let body = self.api_get(&format!("/transfers/{}", sig), &[]).await?;
+        serde_json::from_str(&body).ok()
     }
 
+    /// Lists transfers matching the query. The cursor is the signature of the last transfer
+    /// already loaded; the offset is sent as well for apis without cursor support.
     pub async fn list_transfers(
         &self,
-        user: Option<Pubkey>,
+        query: &TransferQuery,
+        cursor: Option<&str>,
         offset: u64,
         limit: usize,
     ) -> Option<GetTransfersResponse> {
-        let offset = offset.to_string();
-        let limit = limit.to_string();
-        let mut query = vec![("offset", offset.as_str()), ("limit", limit.as_str())];
-        let user_str = user.map(|u| u.to_string());
-        let user_ref = user_str.as_deref();
-        if let Some(user_str) = user_ref {
-            query.push(("user", user_str));
-        };
-        let client = reqwest::Client::new();
-        match client
-            .get(format!("{}/transfers", &self.api_url))
-            .query(&query)
-            .send()
-            .await
-        {
-            Ok(res) => res.json::<GetTransfersResponse>().await.ok(),
-            Err(e) => {
-                log::error!("{:?}", e);
+        let mut params = query.params();
+        params.push(("offset", offset.to_string()));
+        params.push(("limit", limit.to_string()));
+        if let Some(cursor) = cursor {
+            params.push(("cursor", cursor.to_string()));
+        }
+        let body = self.api_get("/transfers", &params).await?;
+        serde_json::from_str(&body).ok()
+    }
+
+    /// Fetches supply samples recorded by the api, to backfill the local history.
+    pub async fn get_supply_history(&self) -> Option<Vec<SupplySample>> {
+        let body = self.api_get("/supply/history", &[]).await?;
+        serde_json::from_str(&body).ok()
+    }
+
+    async fn api_get(&self, path: &str, query: &[(&str, String)]) -> Option<String> {
+        match self.api.get(path, query).await {
+            Ok(body) => Some(body),
+            Err(err) => {
+                publish(Severity::Warning, "Mars api unavailable", path);
+                log::error!("{} {:?}", path, err);
                 None
             }
         }
     }
 }
 
-#[cfg(feature = "web")]
-pub fn signer() -> Keypair {
-    let key = "keypair";
-    let value = LocalStorage::get(key).ok().unwrap_or_else(|| {
-        let x = Keypair::new().to_base58_string();
-        LocalStorage::set(key, &x).ok();
-        x
-    });
-    Keypair::from_base58_string(&value)
-}
-
-#[cfg(feature = "desktop")]
-pub fn signer() -> Keypair {
-    use crate::file::{get_value, set_key_value};
-
-    let key = "keypair";
-    let value = get_value(key).ok().unwrap_or_else(|| {
-        let value = Keypair::new().to_base58_string();
-        if let Ok(v) = serde_json::to_value(&value) {
-            set_key_value(key, &v).ok();
+/// The user's keypair. Fails if the wallet is missing or unreadable, as it is never generated here.
+pub fn signer() -> GatewayResult<Keypair> {
+    match load_wallet() {
+        Ok((keypair, _)) => Ok(keypair),
+        Err(err) => {
+            log::error!("{}", err);
+            Err(GatewayError::WalletUnavailable)
         }
-        value
-    });
-    Keypair::from_base58_string(&value)
+    }
 }
 
 #[cached]
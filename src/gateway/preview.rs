@@ -0,0 +1,260 @@
+#[cfg(feature = "desktop")]
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+#[cfg(feature = "web")]
+use solana_client_wasm::{
+    solana_sdk::{
+        self, commitment_config::CommitmentConfig, compute_budget::ComputeBudgetInstruction,
+        instruction::Instruction, pubkey::Pubkey, signer::Signer, transaction::Transaction,
+    },
+    utils::rpc_config::RpcSimulateTransactionConfig,
+};
+#[cfg(feature = "web")]
+use solana_extra_wasm::{
+    program::{
+        spl_associated_token_account::instruction::create_associated_token_account, spl_memo,
+        spl_token,
+    },
+    transaction_status::UiTransactionEncoding,
+};
+#[cfg(feature = "desktop")]
+use solana_sdk::{
+    commitment_config::CommitmentConfig, compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction, pubkey::Pubkey, signer::Signer, transaction::Transaction,
+};
+#[cfg(feature = "desktop")]
+use solana_transaction_status::UiTransactionEncoding;
+#[cfg(feature = "desktop")]
+use spl_associated_token_account::instruction::create_associated_token_account;
+
+use super::{
+    mars_token_account_address, tip_ix, Gateway, GatewayError, GatewayResult, CU_LIMIT_CLAIM,
+    DEFAULT_PRIORITY_FEE, DYNAMIC_CU_MARGIN, LAMPORTS_PER_SIGNATURE,
+};
+
+/// Size of an spl token account, used to look up the rent of a new associated token account.
+const TOKEN_ACCOUNT_SIZE: usize = 165;
+
+/// Estimated cost and balance impact of a transaction, computed by simulation before signing.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct TransactionPreview {
+    pub compute_units: u64,
+    pub base_fee: u64,
+    pub priority_fee: u64,
+    pub ata_rent: u64,
+    pub tip: u64,
+    pub mars_change: i64,
+    pub sol_change: i64,
+}
+
+impl TransactionPreview {
+    /// Total lamports paid on top of the amount being moved.
+    pub fn total_cost(&self) -> u64 {
+        self.base_fee
+            .saturating_add(self.priority_fee)
+            .saturating_add(self.ata_rent)
+            .saturating_add(self.tip)
+    }
+}
+
+impl Gateway {
+    pub async fn preview_transfer_mars(
+        &self,
+        amount: u64,
+        to: Pubkey,
+        memo: String,
+    ) -> GatewayResult<TransactionPreview> {
+        let signer = self.signer()?;
+        let mut ixs = vec![];
+        let ata_rent = self.ata_rent(to).await?;
+        if ata_rent.gt(&0) {
+            ixs.push(create_associated_token_account(
+                &signer.pubkey(),
+                &to,
+                &mars::MINT_ADDRESS,
+                &spl_token::id(),
+            ));
+        }
+        ixs.push(spl_memo::build_memo(
+            &memo.into_bytes(),
+            &[&signer.pubkey()],
+        ));
+        ixs.push(
+            spl_token::instruction::transfer(
+                &spl_token::ID,
+                &mars_token_account_address(signer.pubkey()),
+                &mars_token_account_address(to),
+                &signer.pubkey(),
+                &[&signer.pubkey()],
+                amount,
+            )
+            .unwrap(),
+        );
+
+        // Token account creation is sent as its own transaction
+        let tx_count = if ata_rent.gt(&0) { 2 } else { 1 };
+        let compute_units = self.simulate(&ixs).await?;
+        let priority_fee = priority_fee_lamports(
+            compute_units.saturating_add((DYNAMIC_CU_MARGIN as u64).saturating_mul(tx_count)),
+            DEFAULT_PRIORITY_FEE,
+        );
+        let mut preview = self.preview(compute_units, priority_fee, ata_rent, tx_count);
+        preview.mars_change = -(amount as i64);
+        preview.sol_change = -(preview.total_cost() as i64);
+        Ok(preview)
+    }
+
+    pub async fn preview_transfer_sol(
+        &self,
+        amount: u64,
+        to: Pubkey,
+        memo: String,
+    ) -> GatewayResult<TransactionPreview> {
+        let signer = self.signer()?;
+        let mut ixs = vec![];
+        if !memo.is_empty() {
+            ixs.push(spl_memo::build_memo(
+                &memo.into_bytes(),
+                &[&signer.pubkey()],
+            ));
+        }
+        ixs.push(solana_sdk::system_instruction::transfer(
+            &signer.pubkey(),
+            &to,
+            amount,
+        ));
+        let compute_units = self.simulate(&ixs).await?;
+        let priority_fee = priority_fee_lamports(
+            compute_units.saturating_add(DYNAMIC_CU_MARGIN as u64),
+            DEFAULT_PRIORITY_FEE,
+        );
+        let mut preview = self.preview(compute_units, priority_fee, 0, 1);
+        preview.sol_change = -(amount.saturating_add(preview.total_cost()) as i64);
+        Ok(preview)
+    }
+
+    pub async fn preview_claim_mars(
+        &self,
+        amount: u64,
+        priority_fee: u64,
+    ) -> GatewayResult<TransactionPreview> {
+        let signer = self.signer()?;
+        let ata_rent = self.ata_rent(signer.pubkey()).await?;
+        let claim_fee = priority_fee_lamports(CU_LIMIT_CLAIM as u64, priority_fee);
+        let (compute_units, priority_fee, tx_count) = if ata_rent.gt(&0) {
+            // Token account creation is sent as its own transaction, with a dynamic compute budget
+            let ata_units = self
+                .simulate(&[create_associated_token_account(
+                    &signer.pubkey(),
+                    &signer.pubkey(),
+                    &mars::MINT_ADDRESS,
+                    &spl_token::id(),
+                )])
+                .await?;
+            let ata_fee = priority_fee_lamports(
+                ata_units.saturating_add(DYNAMIC_CU_MARGIN as u64),
+                DEFAULT_PRIORITY_FEE,
+            );
+
+            // The claim can't be simulated before the account exists, but its budget is fixed
+            (
+                ata_units.saturating_add(CU_LIMIT_CLAIM as u64),
+                claim_fee.saturating_add(ata_fee),
+                2,
+            )
+        } else {
+            let compute_units = self
+                .simulate(&[
+                    ComputeBudgetInstruction::set_compute_unit_limit(CU_LIMIT_CLAIM),
+                    ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+                    mars::instruction::claim(
+                        signer.pubkey(),
+                        mars_token_account_address(signer.pubkey()),
+                        amount,
+                    ),
+                ])
+                .await?;
+            (compute_units, claim_fee, 1)
+        };
+        let mut preview = self.preview(compute_units, priority_fee, ata_rent, tx_count);
+        preview.mars_change = amount as i64;
+        preview.sol_change = -(preview.total_cost() as i64);
+        Ok(preview)
+    }
+
+    /// Estimates the lamports paid for a transaction with a fixed compute budget, including the tip.
+    pub fn estimate_fee(&self, cu_limit: u32, priority_fee: u64) -> u64 {
+        network_fee(cu_limit, priority_fee).saturating_add(self.tip_amount())
+    }
+
+    fn preview(
+        &self,
+        compute_units: u64,
+        priority_fee: u64,
+        ata_rent: u64,
+        tx_count: u64,
+    ) -> TransactionPreview {
+        TransactionPreview {
+            compute_units,
+            base_fee: LAMPORTS_PER_SIGNATURE.saturating_mul(tx_count),
+            priority_fee,
+            ata_rent,
+            tip: self.tip_amount().saturating_mul(tx_count),
+            mars_change: 0,
+            sol_change: 0,
+        }
+    }
+
+    /// Returns the rent that will be charged to create the owner's Mars token account, if it does not exist yet.
+    async fn ata_rent(&self, owner: Pubkey) -> GatewayResult<u64> {
+        let token_account_address = mars_token_account_address(owner);
+        match self.rpc.get_token_account(&token_account_address).await {
+            Ok(Some(_)) => return Ok(0),
+            Ok(None) | Err(GatewayError::AccountNotFound) => {}
+            Err(err) => return Err(err),
+        }
+        self.rpc
+            .get_minimum_balance_for_rent_exemption(TOKEN_ACCOUNT_SIZE)
+            .await
+    }
+
+    /// Simulates the instructions as sent by the signer and returns the compute units consumed.
+    async fn simulate(&self, ixs: &[Instruction]) -> GatewayResult<u64> {
+        let signer = self.signer()?;
+        let mut ixs = ixs.to_vec();
+        if let Some(ix) = tip_ix(&signer.pubkey(), self.tip_amount()) {
+            ixs.push(ix);
+        }
+        let tx = Transaction::new_with_payer(ixs.as_slice(), Some(&signer.pubkey()));
+        let sim_res = self
+            .rpc
+            .simulate_transaction_with_config(
+                &tx,
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    accounts: None,
+                    min_context_slot: None,
+                },
+            )
+            .await?;
+        if let Some(err) = sim_res.err {
+            log::error!("Simulation error: {:?}", err);
+            return Err(GatewayError::SimulationFailed);
+        }
+        Ok(sim_res.units_consumed.unwrap_or(0))
+    }
+}
+
+/// The base and priority fee of a transaction with a fixed compute budget. Excludes any tip.
+pub fn network_fee(cu_limit: u32, priority_fee: u64) -> u64 {
+    LAMPORTS_PER_SIGNATURE.saturating_add(priority_fee_lamports(cu_limit as u64, priority_fee))
+}
+
+/// Converts a compute unit price in microlamports into the lamports charged for the given units.
+fn priority_fee_lamports(compute_units: u64, microlamports_per_cu: u64) -> u64 {
+    (compute_units as u128)
+        .saturating_mul(microlamports_per_cu as u128)
+        .div_ceil(1_000_000) as u64
+}
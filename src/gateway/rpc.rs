@@ -0,0 +1,236 @@
+use async_trait::async_trait;
+#[cfg(feature = "desktop")]
+use solana_account_decoder::parse_token::{UiTokenAccount, UiTokenAmount};
+#[cfg(feature = "desktop")]
+use solana_client::{
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::{
+        RpcProgramAccountsConfig, RpcSendTransactionConfig, RpcSimulateTransactionConfig,
+    },
+    rpc_response::RpcTokenAccountBalance,
+};
+#[cfg(feature = "web")]
+use solana_client_wasm::{
+    solana_sdk::{
+        account::Account, commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey,
+        signature::Signature, transaction::Transaction,
+    },
+    utils::{
+        rpc_config::{
+            RpcProgramAccountsConfig, RpcSendTransactionConfig, RpcSimulateTransactionConfig,
+        },
+        rpc_response::RpcTokenAccountBalance,
+    },
+    WasmClient,
+};
+#[cfg(feature = "web")]
+use solana_extra_wasm::{
+    account_decoder::parse_token::{UiTokenAccount, UiTokenAmount},
+    transaction_status::TransactionConfirmationStatus,
+};
+#[cfg(feature = "desktop")]
+use solana_sdk::{
+    account::Account, commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey,
+    signature::Signature, transaction::Transaction,
+};
+#[cfg(feature = "desktop")]
+use solana_transaction_status::TransactionConfirmationStatus;
+
+use super::{GatewayError, GatewayResult};
+
+#[cfg(feature = "web")]
+pub type NetworkRpc = WasmClient;
+#[cfg(feature = "desktop")]
+pub type NetworkRpc = RpcClient;
+
+/// Outcome of a transaction simulation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Simulation {
+    pub err: Option<String>,
+    pub units_consumed: Option<u64>,
+}
+
+/// Where a sent transaction stands.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignatureStatus {
+    pub confirmation_status: Option<TransactionConfirmationStatus>,
+    /// Set if the transaction landed but failed. Its fee is charged all the same.
+    pub err: Option<String>,
+}
+
+/// The rpc calls made by the app, so the network can be swapped out in tests.
+#[async_trait(?Send)]
+pub trait Rpc {
+    async fn get_account(&self, pubkey: &Pubkey) -> GatewayResult<Account>;
+
+    async fn get_account_data(&self, pubkey: &Pubkey) -> GatewayResult<Vec<u8>>;
+
+    async fn get_program_accounts_with_config(
+        &self,
+        program_id: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> GatewayResult<Vec<(Pubkey, Account)>>;
+
+    async fn get_balance(&self, pubkey: &Pubkey) -> GatewayResult<u64>;
+
+    async fn get_slot(&self) -> GatewayResult<u64>;
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> GatewayResult<u64>;
+
+    async fn get_token_supply(&self, mint: &Pubkey) -> GatewayResult<UiTokenAmount>;
+
+    async fn get_token_account(&self, pubkey: &Pubkey) -> GatewayResult<Option<UiTokenAccount>>;
+
+    async fn get_token_account_balance(&self, pubkey: &Pubkey) -> GatewayResult<UiTokenAmount>;
+
+    async fn get_token_largest_accounts(
+        &self,
+        mint: &Pubkey,
+    ) -> GatewayResult<Vec<RpcTokenAccountBalance>>;
+
+    async fn get_latest_blockhash_with_commitment(
+        &self,
+        commitment: CommitmentConfig,
+    ) -> GatewayResult<(Hash, u64)>;
+
+    async fn simulate_transaction_with_config(
+        &self,
+        tx: &Transaction,
+        config: RpcSimulateTransactionConfig,
+    ) -> GatewayResult<Simulation>;
+
+    async fn send_transaction_with_config(
+        &self,
+        tx: &Transaction,
+        config: RpcSendTransactionConfig,
+    ) -> GatewayResult<Signature>;
+
+    /// Status of each signature, or `None` if it is not known yet.
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> GatewayResult<Vec<Option<SignatureStatus>>>;
+}
+
+#[async_trait(?Send)]
+impl Rpc for NetworkRpc {
+    async fn get_account(&self, pubkey: &Pubkey) -> GatewayResult<Account> {
+        NetworkRpc::get_account(self, pubkey)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn get_account_data(&self, pubkey: &Pubkey) -> GatewayResult<Vec<u8>> {
+        NetworkRpc::get_account_data(self, pubkey)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn get_program_accounts_with_config(
+        &self,
+        program_id: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> GatewayResult<Vec<(Pubkey, Account)>> {
+        NetworkRpc::get_program_accounts_with_config(self, program_id, config)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn get_balance(&self, pubkey: &Pubkey) -> GatewayResult<u64> {
+        NetworkRpc::get_balance(self, pubkey)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn get_slot(&self) -> GatewayResult<u64> {
+        NetworkRpc::get_slot(self).await.map_err(GatewayError::from)
+    }
+
+    async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> GatewayResult<u64> {
+        NetworkRpc::get_minimum_balance_for_rent_exemption(self, data_len)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn get_token_supply(&self, mint: &Pubkey) -> GatewayResult<UiTokenAmount> {
+        NetworkRpc::get_token_supply(self, mint)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn get_token_account(&self, pubkey: &Pubkey) -> GatewayResult<Option<UiTokenAccount>> {
+        NetworkRpc::get_token_account(self, pubkey)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn get_token_account_balance(&self, pubkey: &Pubkey) -> GatewayResult<UiTokenAmount> {
+        NetworkRpc::get_token_account_balance(self, pubkey)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn get_token_largest_accounts(
+        &self,
+        mint: &Pubkey,
+    ) -> GatewayResult<Vec<RpcTokenAccountBalance>> {
+        NetworkRpc::get_token_largest_accounts(self, mint)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn get_latest_blockhash_with_commitment(
+        &self,
+        commitment: CommitmentConfig,
+    ) -> GatewayResult<(Hash, u64)> {
+        NetworkRpc::get_latest_blockhash_with_commitment(self, commitment)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn simulate_transaction_with_config(
+        &self,
+        tx: &Transaction,
+        config: RpcSimulateTransactionConfig,
+    ) -> GatewayResult<Simulation> {
+        let sim_res = NetworkRpc::simulate_transaction_with_config(self, tx, config)
+            .await
+            .map_err(GatewayError::from)?;
+        #[cfg(feature = "desktop")]
+        let sim_res = sim_res.value;
+        Ok(Simulation {
+            err: sim_res.err.map(|err| format!("{:?}", err)),
+            units_consumed: sim_res.units_consumed,
+        })
+    }
+
+    async fn send_transaction_with_config(
+        &self,
+        tx: &Transaction,
+        config: RpcSendTransactionConfig,
+    ) -> GatewayResult<Signature> {
+        NetworkRpc::send_transaction_with_config(self, tx, config)
+            .await
+            .map_err(GatewayError::from)
+    }
+
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> GatewayResult<Vec<Option<SignatureStatus>>> {
+        let statuses = NetworkRpc::get_signature_statuses(self, signatures)
+            .await
+            .map_err(GatewayError::from)?;
+        #[cfg(feature = "desktop")]
+        let statuses = statuses.value;
+        Ok(statuses
+            .into_iter()
+            .map(|status| {
+                status.map(|status| SignatureStatus {
+                    confirmation_status: status.confirmation_status,
+                    err: status.err.map(|err| format!("{:?}", err)),
+                })
+            })
+            .collect())
+    }
+}
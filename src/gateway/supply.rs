@@ -0,0 +1,71 @@
+#[cfg(feature = "web")]
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "desktop")]
+use crate::file::{read_file, write_file};
+
+/// Stored apart from the config, as it is rewritten with every sample.
+const SUPPLY_HISTORY_KEY: &str = "supply_history";
+const SUPPLY_HISTORY_LIMIT: usize = 2_000;
+
+/// A snapshot of the token supply and emission parameters at a point in time.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct SupplySample {
+    pub ts: i64,
+    /// Mars claimed from the treasury, in the token's smallest unit.
+    pub circulating_supply: u64,
+    /// Mars minted, in the token's smallest unit.
+    pub total_supply: u64,
+    /// Mars held by the treasury, in the token's smallest unit.
+    pub treasury_balance: u64,
+    pub reward_rate: u64,
+    /// Leading zero bits required of a valid hash.
+    pub difficulty: u32,
+}
+
+/// Returns the locally sampled supply history, oldest first.
+#[cfg(feature = "web")]
+pub fn supply_history() -> Vec<SupplySample> {
+    LocalStorage::get(SUPPLY_HISTORY_KEY).unwrap_or_default()
+}
+
+/// Returns the locally sampled supply history, oldest first.
+#[cfg(feature = "desktop")]
+pub fn supply_history() -> Vec<SupplySample> {
+    read_file(SUPPLY_HISTORY_KEY).unwrap_or_default()
+}
+
+/// Merges samples into the local history and persists it. Returns the merged history.
+pub fn record_supply_samples(samples: Vec<SupplySample>) -> Vec<SupplySample> {
+    let mut history = supply_history();
+    for sample in samples {
+        if !history.iter().any(|s| s.ts.eq(&sample.ts)) {
+            history.push(sample);
+        }
+    }
+    history.sort_by_key(|s| s.ts);
+    if history.len().gt(&SUPPLY_HISTORY_LIMIT) {
+        history.drain(..history.len() - SUPPLY_HISTORY_LIMIT);
+    }
+
+    #[cfg(feature = "web")]
+    LocalStorage::set(SUPPLY_HISTORY_KEY, &history).ok();
+
+    #[cfg(feature = "desktop")]
+    write_file(SUPPLY_HISTORY_KEY, &history).ok();
+
+    history
+}
+
+/// Counts the leading zero bits of a difficulty hash.
+pub fn difficulty_bits(difficulty: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in difficulty {
+        bits += byte.leading_zeros();
+        if byte.ne(&0) {
+            break;
+        }
+    }
+    bits
+}
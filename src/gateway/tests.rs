@@ -0,0 +1,337 @@
+use std::time::Duration;
+
+use async_std::task::block_on;
+use bytemuck::Zeroable;
+use mars::state::{Proof, Treasury};
+use ore_types::{Transfer, TransferType};
+use solana_sdk::{
+    keccak::Hash as KeccakHash, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair,
+    signer::Signer,
+};
+
+use super::{
+    backoff,
+    fake::{FakeApi, FakeChain, REWARD_RATE, SYSTEM_ACCOUNT_RENT},
+    merge_transfers, Gateway, GatewayError, SortOrder, SupplySample, TransferDirection,
+    TransferQuery, TransferSort, LAMPORTS_PER_SIGNATURE,
+};
+use crate::miner::{submit_solution, MiningResult};
+
+fn setup(lamports: u64) -> (FakeChain, Gateway, Pubkey) {
+    setup_with_api(lamports, FakeApi::default())
+}
+
+fn setup_with_api(lamports: u64, api: FakeApi) -> (FakeChain, Gateway, Pubkey) {
+    let chain = FakeChain::new();
+    let keypair = Keypair::new();
+    let pubkey = keypair.pubkey();
+    chain.airdrop(pubkey, lamports);
+    let gateway = Gateway::offline(&chain, api, keypair);
+    (chain, gateway, pubkey)
+}
+
+fn give_rewards(chain: &FakeChain, authority: Pubkey, amount: u64) {
+    let mut proof = Proof::zeroed();
+    proof.authority = authority;
+    proof.claimable_rewards = amount;
+    chain.state().proofs.insert(authority, proof);
+}
+
+fn solution() -> MiningResult {
+    MiningResult {
+        hash: KeccakHash::new_unique(),
+        nonce: 0,
+    }
+}
+
+fn treasury(chain: &FakeChain) -> Treasury {
+    chain.state().treasury
+}
+
+fn transfer(sig: &str, from: &Pubkey, to: &Pubkey, amount: u64, ts: i64) -> Transfer {
+    Transfer {
+        sig: sig.to_string(),
+        from_address: from.to_string(),
+        to_address: to.to_string(),
+        amount,
+        memo: None,
+        ts,
+        transfer_type: TransferType::Spl,
+    }
+}
+
+fn sigs(transfers: &[Transfer]) -> Vec<&str> {
+    transfers.iter().map(|t| t.sig.as_str()).collect()
+}
+
+#[test]
+fn register_creates_proof() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    block_on(gateway.register_mars()).unwrap();
+    let proof = chain.proof(pubkey).unwrap();
+    assert_eq!(proof.authority, pubkey);
+    assert_eq!(block_on(gateway.get_proof(pubkey)).unwrap(), proof);
+}
+
+#[test]
+fn register_is_noop_when_proof_exists() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 0);
+    block_on(gateway.register_mars()).unwrap();
+    assert_eq!(chain.sent_count(), 0);
+}
+
+#[test]
+fn claim_moves_rewards_to_token_account() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 500);
+    chain.mint_to(pubkey, 0);
+    block_on(gateway.claim_mars(300, 0)).unwrap();
+    assert_eq!(chain.token_balance(pubkey), Some(300));
+    assert_eq!(chain.proof(pubkey).unwrap().claimable_rewards, 200);
+}
+
+#[test]
+fn transfer_creates_recipient_token_account() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    let recipient = Pubkey::new_unique();
+    chain.mint_to(pubkey, 1_000);
+    block_on(gateway.transfer_mars(400, recipient, "gm".to_string())).unwrap();
+    assert_eq!(chain.token_balance(pubkey), Some(600));
+    assert_eq!(chain.token_balance(recipient), Some(400));
+}
+
+#[test]
+fn max_sol_transfer_leaves_the_account_rent_exempt() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    let recipient = Pubkey::new_unique();
+    let max = block_on(gateway.max_sol_transfer()).unwrap();
+    block_on(gateway.transfer_sol(max, recipient, String::new())).unwrap();
+    assert_eq!(chain.balance(recipient), max);
+    assert!(chain.balance(pubkey).ge(&SYSTEM_ACCOUNT_RENT));
+
+    // Anything more would leave too little for rent
+    let (_, gateway, _) = setup(LAMPORTS_PER_SOL);
+    let max = block_on(gateway.max_sol_transfer()).unwrap();
+    let amount = max + SYSTEM_ACCOUNT_RENT;
+    let res = block_on(gateway.transfer_sol(amount, recipient, String::new()));
+    assert_eq!(res, Err(GatewayError::SimulationFailed));
+}
+
+#[test]
+fn solution_is_accepted() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 0);
+    let submission = block_on(submit_solution(&gateway, &solution(), 0, treasury(&chain)));
+    submission.result.unwrap();
+    assert_eq!(submission.fees, LAMPORTS_PER_SIGNATURE);
+    let proof = chain.proof(pubkey).unwrap();
+    assert_eq!(proof.total_hashes, 1);
+    assert_eq!(proof.claimable_rewards, REWARD_RATE);
+}
+
+#[test]
+fn solution_is_retried_after_failed_sends() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 0);
+    chain.state().failing_sends = 2;
+    block_on(submit_solution(&gateway, &solution(), 0, treasury(&chain)))
+        .result
+        .unwrap();
+    assert_eq!(chain.proof(pubkey).unwrap().total_hashes, 1);
+    assert_eq!(chain.state().failing_sends, 0);
+}
+
+#[test]
+fn solution_is_given_up_after_failing_sends() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 0);
+    chain.state().failing_sends = usize::MAX;
+    let submission = block_on(submit_solution(&gateway, &solution(), 0, treasury(&chain)));
+    assert_eq!(submission.result, Err(GatewayError::TransactionTimeout));
+    assert_eq!(submission.fees, 0);
+    assert_eq!(chain.proof(pubkey).unwrap().total_hashes, 0);
+}
+
+#[test]
+fn backoff_doubles_up_to_a_minute() {
+    let base = Duration::from_secs(2);
+    let delays: Vec<_> = (1..=7)
+        .map(|attempt| backoff(base, attempt).as_secs())
+        .collect();
+    assert_eq!(delays, [2, 4, 8, 16, 32, 60, 60]);
+    assert_eq!(backoff(base, 0), base);
+    assert_eq!(backoff(base, u32::MAX), Duration::from_secs(60));
+}
+
+#[test]
+fn solution_without_sol_is_rejected() {
+    let (chain, gateway, pubkey) = setup(0);
+    give_rewards(&chain, pubkey, 0);
+    let submission = block_on(submit_solution(&gateway, &solution(), 0, treasury(&chain)));
+    assert_eq!(submission.result, Err(GatewayError::InsufficientFunds));
+    assert_eq!(chain.sent_count(), 0);
+}
+
+#[test]
+fn failed_simulations_are_retried() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 100);
+    chain.mint_to(pubkey, 0);
+    chain.state().failing_simulations = 2;
+    block_on(gateway.claim_mars(100, 0)).unwrap();
+    assert_eq!(chain.token_balance(pubkey), Some(100));
+}
+
+#[test]
+fn failing_simulation_is_reported() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 100);
+    chain.mint_to(pubkey, 0);
+    chain.state().failing_simulations = usize::MAX;
+    let res = block_on(gateway.claim_mars(100, 0));
+    assert_eq!(res, Err(GatewayError::SimulationFailed));
+    assert_eq!(chain.sent_count(), 0);
+}
+
+#[test]
+fn invalid_claim_fails_simulation() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 100);
+    chain.mint_to(pubkey, 0);
+    let res = block_on(gateway.claim_mars(101, 0));
+    assert_eq!(res, Err(GatewayError::SimulationFailed));
+    assert_eq!(chain.proof(pubkey).unwrap().claimable_rewards, 100);
+}
+
+#[test]
+fn dropped_transaction_times_out() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 100);
+    chain.mint_to(pubkey, 0);
+    chain.state().dropped_sends = usize::MAX;
+    let res = block_on(gateway.claim_mars(100, 0));
+    assert_eq!(res, Err(GatewayError::TransactionTimeout));
+    assert_eq!(chain.token_balance(pubkey), Some(0));
+}
+
+#[test]
+fn transaction_failing_on_chain_is_reported() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 100);
+    chain.mint_to(pubkey, 0);
+    chain.state().failing_landings = 1;
+    let res = block_on(gateway.claim_mars(100, 0));
+    assert_eq!(res, Err(GatewayError::TransactionFailed));
+    assert_eq!(chain.sent_count(), 1);
+    assert_eq!(chain.token_balance(pubkey), Some(0));
+    assert_eq!(
+        chain.balance(pubkey),
+        LAMPORTS_PER_SOL - LAMPORTS_PER_SIGNATURE
+    );
+}
+
+#[test]
+fn solution_fees_include_attempts_that_failed_on_chain() {
+    let (chain, gateway, pubkey) = setup(LAMPORTS_PER_SOL);
+    give_rewards(&chain, pubkey, 0);
+    chain.state().failing_landings = 1;
+    let submission = block_on(submit_solution(&gateway, &solution(), 0, treasury(&chain)));
+    submission.result.unwrap();
+    assert_eq!(submission.fees, LAMPORTS_PER_SIGNATURE * 2);
+    assert_eq!(chain.proof(pubkey).unwrap().total_hashes, 1);
+}
+
+#[test]
+fn supply_history_is_read_from_api() {
+    let samples = vec![SupplySample {
+        ts: 1,
+        circulating_supply: 10,
+        total_supply: 20,
+        ..Default::default()
+    }];
+    let api = FakeApi::default().with("/supply/history", serde_json::to_string(&samples).unwrap());
+    let (_, gateway, _) = setup_with_api(0, api);
+    assert_eq!(block_on(gateway.get_supply_history()), Some(samples));
+}
+
+#[test]
+fn unavailable_api_returns_none() {
+    let (_, gateway, _) = setup(0);
+    assert_eq!(block_on(gateway.get_supply_history()), None);
+    assert!(block_on(gateway.get_transfer("sig".to_string())).is_none());
+}
+
+#[test]
+fn transfer_query_filters_by_direction_relative_to_user() {
+    let (user, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let incoming = transfer("in", &other, &user, 10, 1);
+    let outgoing = transfer("out", &user, &other, 10, 2);
+    let unrelated = transfer("x", &other, &Pubkey::new_unique(), 10, 3);
+    let mut query = TransferQuery::user(Some(user));
+    assert!(query.matches(&incoming) && query.matches(&outgoing));
+    assert!(!query.matches(&unrelated));
+    query.direction = TransferDirection::Incoming;
+    assert!(query.matches(&incoming) && !query.matches(&outgoing));
+    query.direction = TransferDirection::Outgoing;
+    assert!(!query.matches(&incoming) && query.matches(&outgoing));
+}
+
+#[test]
+fn transfer_query_filters_by_amount_time_and_memo() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut t = transfer("t", &a, &b, 50, 100);
+    t.memo = Some("Rent for March".to_string());
+    let query = TransferQuery {
+        min_amount: Some(50),
+        max_amount: Some(50),
+        start_ts: Some(100),
+        end_ts: Some(100),
+        memo: Some("rent".to_string()),
+        ..Default::default()
+    };
+    assert!(query.matches(&t));
+    t.amount = 49;
+    assert!(!query.matches(&t));
+    t.amount = 50;
+    t.ts = 101;
+    assert!(!query.matches(&t));
+    t.ts = 100;
+    t.memo = Some("April".to_string());
+    assert!(!query.matches(&t));
+    t.memo = None;
+    assert!(!query.matches(&t));
+}
+
+#[test]
+fn transfer_query_sorts_by_amount_then_time() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut transfers = vec![
+        transfer("small", &a, &b, 1, 3),
+        transfer("big-old", &a, &b, 9, 1),
+        transfer("big-new", &a, &b, 9, 2),
+    ];
+    let mut query = TransferQuery::default();
+    query.sort(&mut transfers);
+    assert_eq!(sigs(&transfers), ["small", "big-new", "big-old"]);
+    query.sort = TransferSort::Amount;
+    query.sort(&mut transfers);
+    assert_eq!(sigs(&transfers), ["big-new", "big-old", "small"]);
+    query.order = SortOrder::Asc;
+    query.sort(&mut transfers);
+    assert_eq!(sigs(&transfers), ["small", "big-old", "big-new"]);
+}
+
+#[test]
+fn merge_transfers_skips_duplicates_and_keeps_most_recent() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut transfers = vec![transfer("1", &a, &b, 1, 1), transfer("2", &a, &b, 1, 2)];
+    merge_transfers(
+        &mut transfers,
+        vec![transfer("2", &a, &b, 1, 2), transfer("3", &a, &b, 1, 3)],
+        usize::MAX,
+    );
+    assert_eq!(sigs(&transfers), ["1", "2", "3"]);
+    merge_transfers(&mut transfers, vec![transfer("0", &a, &b, 1, 0)], 2);
+    assert_eq!(sigs(&transfers), ["3", "2"]);
+}
@@ -0,0 +1,146 @@
+use std::{
+    str::FromStr,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+#[cfg(feature = "web")]
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::{pubkey::Pubkey, signature::Signature};
+#[cfg(feature = "desktop")]
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+#[cfg(feature = "desktop")]
+use std::time::{SystemTime, UNIX_EPOCH};
+#[cfg(feature = "web")]
+use web_time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "desktop")]
+use crate::file::{read_file, write_file};
+
+pub const TIP_AMOUNT: u64 = 100_000;
+
+/// Stored apart from the config, as it is written after every tipped transaction.
+const TIP_LOG_KEY: &str = "tip_log";
+const TIP_LOG_LIMIT: usize = 100;
+
+/// Lamports tipped since the app was launched.
+static SESSION_TIPS: AtomicU64 = AtomicU64::new(0);
+
+/// User settings for the tip appended to transactions sent through the default rpc.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TipConfig {
+    pub enabled: bool,
+    /// Lamports tipped per transaction.
+    pub amount: u64,
+    /// Maximum lamports tipped per session. Zero means no cap.
+    pub session_cap: u64,
+}
+
+impl Default for TipConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            amount: TIP_AMOUNT,
+            session_cap: 0,
+        }
+    }
+}
+
+impl TipConfig {
+    /// Returns the tip for the next transaction, given the lamports already tipped this session.
+    pub fn next_tip(&self, session_tips: u64) -> u64 {
+        if !self.enabled {
+            return 0;
+        }
+        if self.session_cap.eq(&0) {
+            return self.amount;
+        }
+        self.amount
+            .min(self.session_cap.saturating_sub(session_tips))
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TipLog {
+    /// Lamports tipped across all sessions.
+    pub total: u64,
+    /// Most recent tips, newest first.
+    pub recent: Vec<TipRecord>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TipRecord {
+    pub signature: String,
+    pub amount: u64,
+    pub timestamp: u64,
+    /// Sent without waiting for confirmation, so it may not have landed.
+    #[serde(default)]
+    pub unconfirmed: bool,
+}
+
+pub fn tip_account() -> Pubkey {
+    // Miraland donation account
+    Pubkey::from_str("9h9TXFtSsDAiL5kpCRZuKUxPE4Nv3W56fcSyUC3zmQip").unwrap()
+}
+
+pub fn session_tips() -> u64 {
+    SESSION_TIPS.load(Ordering::Relaxed)
+}
+
+#[cfg(feature = "web")]
+pub fn tip_log() -> TipLog {
+    LocalStorage::get(TIP_LOG_KEY).unwrap_or_default()
+}
+
+#[cfg(feature = "desktop")]
+pub fn tip_log() -> TipLog {
+    read_file(TIP_LOG_KEY).unwrap_or_default()
+}
+
+/// Picks the tip for a transaction about to be sent, and counts it toward the session cap.
+pub(super) fn take_tip(config: &TipConfig) -> u64 {
+    let mut tip = 0;
+    SESSION_TIPS
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |tips| {
+            tip = config.next_tip(tips);
+            Some(tips.saturating_add(tip))
+        })
+        .ok();
+    tip
+}
+
+/// Gives back a tip taken for a transaction that did not land.
+pub(super) fn release_tip(amount: u64) {
+    SESSION_TIPS
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |tips| {
+            Some(tips.saturating_sub(amount))
+        })
+        .ok();
+}
+
+/// Records a tip sent with the given transaction. Its amount was already taken for the session.
+pub(super) fn record_tip(sig: Signature, amount: u64, is_confirmed: bool) {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let mut log = tip_log();
+    log.total = log.total.saturating_add(amount);
+    log.recent.insert(
+        0,
+        TipRecord {
+            signature: sig.to_string(),
+            amount,
+            timestamp,
+            unconfirmed: !is_confirmed,
+        },
+    );
+    log.recent.truncate(TIP_LOG_LIMIT);
+
+    #[cfg(feature = "web")]
+    LocalStorage::set(TIP_LOG_KEY, &log).ok();
+
+    #[cfg(feature = "desktop")]
+    write_file(TIP_LOG_KEY, &log).ok();
+}
@@ -0,0 +1,196 @@
+use ore_types::Transfer;
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "desktop")]
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransferDirection {
+    #[default]
+    Any,
+    Incoming,
+    Outgoing,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransferSort {
+    #[default]
+    Time,
+    Amount,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Desc,
+    Asc,
+}
+
+impl SortOrder {
+    pub fn toggle(self) -> Self {
+        match self {
+            SortOrder::Desc => SortOrder::Asc,
+            SortOrder::Asc => SortOrder::Desc,
+        }
+    }
+}
+
+/// Filters and ordering for listing transfers.
+///
+/// The api receives these as query parameters. Since it may ignore any it does not support,
+/// the same filters are applied again client-side to every page returned.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransferQuery {
+    pub user: Option<Pubkey>,
+    /// Counterparty address. The direction is relative to this address, or to the user if unset.
+    pub address: Option<String>,
+    pub direction: TransferDirection,
+    pub min_amount: Option<u64>,
+    pub max_amount: Option<u64>,
+    pub start_ts: Option<i64>,
+    pub end_ts: Option<i64>,
+    pub memo: Option<String>,
+    pub sort: TransferSort,
+    pub order: SortOrder,
+}
+
+impl TransferQuery {
+    pub fn user(user: Option<Pubkey>) -> Self {
+        Self {
+            user,
+            ..Default::default()
+        }
+    }
+
+    pub fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![];
+        if let Some(user) = self.user {
+            params.push(("user", user.to_string()));
+        }
+        if let Some(address) = &self.address {
+            params.push(("address", address.clone()));
+        }
+        match self.direction {
+            TransferDirection::Any => {}
+            TransferDirection::Incoming => params.push(("direction", "in".to_string())),
+            TransferDirection::Outgoing => params.push(("direction", "out".to_string())),
+        }
+        if let Some(min_amount) = self.min_amount {
+            params.push(("min_amount", min_amount.to_string()));
+        }
+        if let Some(max_amount) = self.max_amount {
+            params.push(("max_amount", max_amount.to_string()));
+        }
+        if let Some(start_ts) = self.start_ts {
+            params.push(("start_ts", start_ts.to_string()));
+        }
+        if let Some(end_ts) = self.end_ts {
+            params.push(("end_ts", end_ts.to_string()));
+        }
+        if let Some(memo) = &self.memo {
+            params.push(("memo", memo.clone()));
+        }
+        if self.sort.ne(&TransferSort::Time) || self.order.ne(&SortOrder::Desc) {
+            let sort = match self.sort {
+                TransferSort::Time => "ts",
+                TransferSort::Amount => "amount",
+            };
+            let order = match self.order {
+                SortOrder::Desc => "desc",
+                SortOrder::Asc => "asc",
+            };
+            params.push(("sort", sort.to_string()));
+            params.push(("order", order.to_string()));
+        }
+        params
+    }
+
+    pub fn matches(&self, transfer: &Transfer) -> bool {
+        let user = self.user.map(|u| u.to_string());
+        if let Some(user) = &user {
+            if transfer.from_address.ne(user) && transfer.to_address.ne(user) {
+                return false;
+            }
+        }
+        if let Some(address) = &self.address {
+            if !transfer.from_address.eq(address) && !transfer.to_address.eq(address) {
+                return false;
+            }
+        }
+        let subject = user.as_ref().or(self.address.as_ref());
+        if let Some(subject) = subject {
+            match self.direction {
+                TransferDirection::Any => {}
+                TransferDirection::Incoming => {
+                    if transfer.to_address.ne(subject) {
+                        return false;
+                    }
+                }
+                TransferDirection::Outgoing => {
+                    if transfer.from_address.ne(subject) {
+                        return false;
+                    }
+                }
+            }
+        }
+        if self.min_amount.is_some_and(|a| transfer.amount.lt(&a)) {
+            return false;
+        }
+        if self.max_amount.is_some_and(|a| transfer.amount.gt(&a)) {
+            return false;
+        }
+        if self.start_ts.is_some_and(|ts| transfer.ts.lt(&ts)) {
+            return false;
+        }
+        if self.end_ts.is_some_and(|ts| transfer.ts.gt(&ts)) {
+            return false;
+        }
+        if let Some(memo) = &self.memo {
+            let memo = memo.to_lowercase();
+            if !transfer
+                .memo
+                .as_ref()
+                .is_some_and(|m| m.to_lowercase().contains(&memo))
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub fn sort(&self, transfers: &mut [Transfer]) {
+        transfers.sort_by(|a, b| {
+            let ord = match self.sort {
+                TransferSort::Time => a.ts.cmp(&b.ts),
+                TransferSort::Amount => a.amount.cmp(&b.amount).then(a.ts.cmp(&b.ts)),
+            };
+            match self.order {
+                SortOrder::Asc => ord,
+                SortOrder::Desc => ord.reverse(),
+            }
+        });
+    }
+}
+
+pub fn filter_transfers(query: &TransferQuery, transfers: &[Transfer]) -> Vec<Transfer> {
+    let mut transfers: Vec<Transfer> = transfers
+        .iter()
+        .filter(|t| query.matches(t))
+        .cloned()
+        .collect();
+    query.sort(&mut transfers);
+    transfers
+}
+
+/// Adds new transfers to the list, skipping any already present, and keeps the most recent ones.
+pub fn merge_transfers(transfers: &mut Vec<Transfer>, new: Vec<Transfer>, limit: usize) {
+    for transfer in new {
+        if !transfers.iter().any(|t| t.sig.eq(&transfer.sig)) {
+            transfers.push(transfer);
+        }
+    }
+    if transfers.len().gt(&limit) {
+        transfers.sort_by(|a, b| b.ts.cmp(&a.ts));
+        transfers.truncate(limit);
+    }
+}
@@ -0,0 +1,137 @@
+//! Keccak-f[1600] over several states at once, one state per lane.
+//! Portable words are plain loops over the lanes, so the compiler can vectorize them.
+
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// State of `N` sponges. Word `i` of every sponge is stored together in `state[i]`.
+pub type State<const N: usize> = [[u64; N]; 25];
+
+/// One word of the state for each sponge hashed together.
+pub trait Word: Copy {
+    fn splat(value: u64) -> Self;
+    fn xor(self, other: Self) -> Self;
+    /// `!self & other`
+    fn andn(self, other: Self) -> Self;
+    fn rotl(self, n: u32) -> Self;
+}
+
+/// Applies the permutation to every lane. Always inlined, so callers compiled with wider
+/// vector instructions get a version that uses them.
+#[inline(always)]
+pub fn keccak_f<W: Word>(state: &mut [W; 25]) {
+    for rc in ROUND_CONSTANTS {
+        // Theta
+        let c = [0, 1, 2, 3, 4].map(|x| {
+            state[x]
+                .xor(state[x + 5])
+                .xor(state[x + 10])
+                .xor(state[x + 15])
+                .xor(state[x + 20])
+        });
+        for x in 0..5 {
+            let d = c[(x + 4) % 5].xor(c[(x + 1) % 5].rotl(1));
+            for y in 0..5 {
+                state[5 * y + x] = state[5 * y + x].xor(d);
+            }
+        }
+
+        // Rho and pi, with the rotations spelled out so they are constants
+        let b = [
+            state[0],
+            state[6].rotl(44),
+            state[12].rotl(43),
+            state[18].rotl(21),
+            state[24].rotl(14),
+            state[3].rotl(28),
+            state[9].rotl(20),
+            state[10].rotl(3),
+            state[16].rotl(45),
+            state[22].rotl(61),
+            state[1].rotl(1),
+            state[7].rotl(6),
+            state[13].rotl(25),
+            state[19].rotl(8),
+            state[20].rotl(18),
+            state[4].rotl(27),
+            state[5].rotl(36),
+            state[11].rotl(10),
+            state[17].rotl(15),
+            state[23].rotl(56),
+            state[2].rotl(62),
+            state[8].rotl(55),
+            state[14].rotl(39),
+            state[15].rotl(41),
+            state[21].rotl(2),
+        ];
+
+        // Chi
+        for y in 0..5 {
+            for x in 0..5 {
+                state[5 * y + x] =
+                    b[5 * y + x].xor(b[5 * y + (x + 1) % 5].andn(b[5 * y + (x + 2) % 5]));
+            }
+        }
+
+        // Iota
+        state[0] = state[0].xor(W::splat(rc));
+    }
+}
+
+impl<const N: usize> Word for [u64; N] {
+    #[inline(always)]
+    fn splat(value: u64) -> Self {
+        [value; N]
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        let mut out = [0; N];
+        for l in 0..N {
+            out[l] = self[l] ^ other[l];
+        }
+        out
+    }
+
+    #[inline(always)]
+    fn andn(self, other: Self) -> Self {
+        let mut out = [0; N];
+        for l in 0..N {
+            out[l] = !self[l] & other[l];
+        }
+        out
+    }
+
+    #[inline(always)]
+    fn rotl(self, n: u32) -> Self {
+        let mut out = [0; N];
+        for l in 0..N {
+            out[l] = self[l].rotate_left(n);
+        }
+        out
+    }
+}
@@ -0,0 +1,131 @@
+use std::ops::Range;
+
+use super::keccak::{keccak_f, State};
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+use super::simd128;
+
+/// Nonces hashed per permutation. Four 64-bit words fill an AVX2 register, or two wasm SIMD registers.
+pub const LANES: usize = 4;
+
+/// Bytes absorbed per block by Keccak-256.
+const RATE: usize = 136;
+
+/// Word of the state holding the nonce, after the 64 bytes of challenge and signer.
+const NONCE_WORD: usize = 8;
+
+/// Searches nonces for one challenge, signer and difficulty.
+/// The preimage `challenge || signer || nonce` fits in a single block, so everything but the
+/// nonce word is computed once up front.
+pub struct Kernel {
+    template: [u64; 25],
+    difficulty: [u8; 32],
+    /// First 8 bytes of the difficulty, to reject most hashes with one comparison.
+    difficulty_prefix: u64,
+    #[cfg(target_arch = "x86_64")]
+    avx2: bool,
+}
+
+impl Kernel {
+    pub fn new(challenge: &[u8; 32], signer: &[u8; 32], difficulty: &[u8; 32]) -> Self {
+        let mut block = [0u8; RATE];
+        block[..32].copy_from_slice(challenge);
+        block[32..64].copy_from_slice(signer);
+        // Keccak padding, after the 8 nonce bytes
+        block[72] = 0x01;
+        block[RATE - 1] |= 0x80;
+        let mut template = [0u64; 25];
+        for (word, bytes) in template.iter_mut().zip(block.chunks_exact(8)) {
+            *word = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        Kernel {
+            template,
+            difficulty: *difficulty,
+            difficulty_prefix: u64::from_be_bytes(difficulty[..8].try_into().unwrap()),
+            #[cfg(target_arch = "x86_64")]
+            avx2: has_avx2(),
+        }
+    }
+
+    /// Hash of a single nonce.
+    pub fn hash(&self, nonce: u64) -> [u8; 32] {
+        let mut state: State<1> = self.template.map(|word| [word]);
+        state[NONCE_WORD] = [nonce];
+        keccak_f(&mut state);
+        digest(&state, 0)
+    }
+
+    /// Returns the lowest nonce in the range whose hash meets the difficulty.
+    pub fn scan(&self, nonces: Range<u64>) -> Option<(u64, [u8; 32])> {
+        let mut nonce = nonces.start;
+        while nonce < nonces.end {
+            let count = (nonces.end - nonce).min(LANES as u64) as usize;
+            let mut state: State<LANES> = self.template.map(|word| [word; LANES]);
+            for (l, word) in state[NONCE_WORD].iter_mut().enumerate() {
+                *word = nonce.wrapping_add(l as u64);
+            }
+            self.permute(&mut state);
+            for l in 0..count {
+                if let Some(hash) = self.check(&state, l) {
+                    return Some((nonce + l as u64, hash));
+                }
+            }
+            nonce += count as u64;
+        }
+        None
+    }
+
+    fn permute(&self, state: &mut State<LANES>) {
+        #[cfg(target_arch = "x86_64")]
+        if self.avx2 {
+            // Safety: the cpu was checked for AVX2 when the kernel was created
+            return unsafe { keccak_f_avx2(state) };
+        }
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        simd128::keccak_f(state);
+        #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+        keccak_f(state);
+    }
+
+    /// The lane's hash, if it meets the difficulty.
+    fn check(&self, state: &State<LANES>, lane: usize) -> Option<[u8; 32]> {
+        // Hashes compare as big-endian numbers, while words hold their bytes little-endian
+        let prefix = state[0][lane].swap_bytes();
+        if prefix.gt(&self.difficulty_prefix) {
+            return None;
+        }
+        let hash = digest(state, lane);
+        hash.le(&self.difficulty).then_some(hash)
+    }
+}
+
+/// Which implementation kernels run on this cpu, for logs.
+pub fn kernel_name() -> &'static str {
+    #[cfg(target_arch = "x86_64")]
+    if has_avx2() {
+        return "avx2";
+    }
+    if cfg!(all(target_arch = "wasm32", target_feature = "simd128")) {
+        "simd128"
+    } else {
+        "portable"
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn has_avx2() -> bool {
+    std::is_x86_feature_detected!("avx2")
+}
+
+fn digest<const N: usize>(state: &State<N>, lane: usize) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    for (bytes, word) in hash.chunks_exact_mut(8).zip(state) {
+        bytes.copy_from_slice(&word[lane].to_le_bytes());
+    }
+    hash
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn keccak_f_avx2(state: &mut State<LANES>) {
+    keccak_f(state)
+}
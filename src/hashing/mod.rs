@@ -0,0 +1,105 @@
+//! Proof of work shared by the desktop threads, the web worker and the benchmarks.
+//! Depends on nothing else in the crate, so the benchmarks can include it directly.
+
+mod keccak;
+mod kernel;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod simd128;
+#[cfg(all(test, feature = "desktop"))]
+mod tests;
+#[cfg(test)]
+mod vectors;
+#[cfg(all(test, target_arch = "wasm32"))]
+mod wasm_tests;
+
+use std::ops::Range;
+
+pub use kernel::*;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::{
+    keccak::{hashv, Hash as KeccakHash},
+    pubkey::Pubkey,
+};
+#[cfg(feature = "desktop")]
+use solana_sdk::{
+    keccak::{hashv, Hash as KeccakHash},
+    pubkey::Pubkey,
+};
+
+/// Nonces hashed between progress reports. A few tens of milliseconds of work per thread.
+pub const BATCH_SIZE: u64 = 1 << 16;
+
+/// Mining response from web workers
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MiningResult {
+    pub hash: KeccakHash,
+    pub nonce: u64,
+}
+
+/// Hash of a nonce, as computed by the mars program. The reference for `Kernel`.
+pub fn hash_nonce(challenge: &KeccakHash, signer: &Pubkey, nonce: u64) -> KeccakHash {
+    hashv(&[
+        challenge.as_ref(),
+        signer.as_ref(),
+        nonce.to_le_bytes().as_slice(),
+    ])
+}
+
+/// Mirrors the checks the mars program makes before accepting a solution.
+pub fn validate(
+    challenge: &KeccakHash,
+    signer: &Pubkey,
+    difficulty: &KeccakHash,
+    res: &MiningResult,
+) -> bool {
+    hash_nonce(challenge, signer, res.nonce).eq(&res.hash) && res.hash.le(difficulty)
+}
+
+/// Hashes the nonces in order until one meets the difficulty.
+/// `progress` is called with the number of nonces hashed after every batch, and stops the search by returning false.
+pub fn search(
+    challenge: &KeccakHash,
+    signer: &Pubkey,
+    difficulty: &KeccakHash,
+    nonces: Range<u64>,
+    mut progress: impl FnMut(u64) -> bool,
+) -> Option<MiningResult> {
+    let kernel = Kernel::new(
+        &challenge.to_bytes(),
+        &signer.to_bytes(),
+        &difficulty.to_bytes(),
+    );
+    let mut nonce = nonces.start;
+    while nonce < nonces.end {
+        let batch_end = nonce.saturating_add(BATCH_SIZE).min(nonces.end);
+        if let Some((found, hash)) = kernel.scan(nonce..batch_end) {
+            progress(found - nonce + 1);
+            return Some(MiningResult {
+                hash: KeccakHash::new_from_array(hash),
+                nonce: found,
+            });
+        }
+        if !progress(batch_end - nonce) {
+            return None;
+        }
+        nonce = batch_end;
+    }
+    None
+}
+
+/// Splits the nonce space into contiguous, non-overlapping ranges, one per thread.
+pub fn partition(threads: u64) -> Vec<Range<u64>> {
+    let size = u64::MAX.saturating_div(threads.max(1));
+    (0..threads)
+        .map(|i| {
+            let start = size.saturating_mul(i);
+            let end = if i + 1 == threads {
+                u64::MAX
+            } else {
+                start + size
+            };
+            start..end
+        })
+        .collect()
+}
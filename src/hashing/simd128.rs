@@ -0,0 +1,58 @@
+//! Words of wasm SIMD vectors, for builds with the `simd128` target feature.
+//! The worker loads such a build only where the browser supports it, see `public/worker.js`.
+
+use core::arch::wasm32::{
+    i64x2_shl, u64x2, u64x2_extract_lane, u64x2_shr, u64x2_splat, v128, v128_andnot, v128_or,
+    v128_xor,
+};
+
+use super::keccak::{self, State, Word};
+
+/// Four lanes, two to a vector.
+#[derive(Clone, Copy)]
+struct Quad([v128; 2]);
+
+impl Quad {
+    #[inline(always)]
+    fn map(self, other: Self, f: impl Fn(v128, v128) -> v128) -> Self {
+        Quad([f(self.0[0], other.0[0]), f(self.0[1], other.0[1])])
+    }
+}
+
+impl Word for Quad {
+    #[inline(always)]
+    fn splat(value: u64) -> Self {
+        Quad([u64x2_splat(value); 2])
+    }
+
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        self.map(other, |a, b| v128_xor(a, b))
+    }
+
+    #[inline(always)]
+    fn andn(self, other: Self) -> Self {
+        // `v128_andnot(a, b)` is `a & !b`
+        self.map(other, |a, b| v128_andnot(b, a))
+    }
+
+    #[inline(always)]
+    fn rotl(self, n: u32) -> Self {
+        let rotl = |a| v128_or(i64x2_shl(a, n), u64x2_shr(a, 64 - n));
+        Quad([rotl(self.0[0]), rotl(self.0[1])])
+    }
+}
+
+/// Same result as `keccak::keccak_f` on four lanes.
+pub fn keccak_f(state: &mut State<4>) {
+    let mut words = state.map(|w| Quad([u64x2(w[0], w[1]), u64x2(w[2], w[3])]));
+    keccak::keccak_f(&mut words);
+    for (word, Quad([lo, hi])) in state.iter_mut().zip(words) {
+        *word = [
+            u64x2_extract_lane::<0>(lo),
+            u64x2_extract_lane::<1>(lo),
+            u64x2_extract_lane::<0>(hi),
+            u64x2_extract_lane::<1>(hi),
+        ];
+    }
+}
@@ -0,0 +1,209 @@
+use proptest::prelude::*;
+use solana_sdk::{keccak::Hash as KeccakHash, pubkey::Pubkey};
+
+use super::{
+    hash_nonce, partition, search, validate,
+    vectors::{
+        difficulty, hex, CHALLENGE, FIRST_HARD_SOLUTION, FIRST_SOLUTION, KNOWN_ANSWERS, SIGNER,
+    },
+    Kernel, MiningResult, BATCH_SIZE, LANES,
+};
+
+/// Accepts roughly one hash in 256.
+const EASY: KeccakHash = KeccakHash::new_from_array([
+    0, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+    255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+]);
+
+fn challenge() -> KeccakHash {
+    KeccakHash::new_from_array(CHALLENGE)
+}
+
+fn signer() -> Pubkey {
+    Pubkey::new_from_array(SIGNER)
+}
+
+#[test]
+fn known_answers() {
+    for (challenge, signer, nonce, expected) in KNOWN_ANSWERS {
+        let challenge = KeccakHash::new_from_array(challenge);
+        let signer = Pubkey::new_from_array(signer);
+        assert_eq!(
+            hex(hash_nonce(&challenge, &signer, nonce).as_ref()),
+            expected
+        );
+    }
+}
+
+#[test]
+fn kernel_known_answers() {
+    for (challenge, signer, nonce, expected) in KNOWN_ANSWERS {
+        let kernel = Kernel::new(&challenge, &signer, &[0; 32]);
+        assert_eq!(hex(&kernel.hash(nonce)), expected);
+    }
+}
+
+#[test]
+fn finds_first_solution() {
+    let res = search(&challenge(), &signer(), &EASY, 0..u64::MAX, |_| true).unwrap();
+    assert_eq!((res.nonce, hex(res.hash.as_ref()).as_str()), FIRST_SOLUTION);
+    assert!(validate(&challenge(), &signer(), &EASY, &res));
+}
+
+#[test]
+fn finds_solution_across_batches() {
+    let difficulty = KeccakHash::new_from_array(difficulty(2));
+    let mut hashed = 0;
+    let res = search(&challenge(), &signer(), &difficulty, 0..u64::MAX, |n| {
+        hashed += n;
+        true
+    })
+    .unwrap();
+    assert_eq!(
+        (res.nonce, hex(res.hash.as_ref()).as_str()),
+        FIRST_HARD_SOLUTION
+    );
+    assert_eq!(hashed, res.nonce + 1);
+    assert!(validate(&challenge(), &signer(), &difficulty, &res));
+}
+
+#[test]
+fn rejects_invalid_solutions() {
+    let res = search(&challenge(), &signer(), &EASY, 0..u64::MAX, |_| true).unwrap();
+    let other_signer = Pubkey::new_unique();
+    assert!(!validate(&challenge(), &other_signer, &EASY, &res));
+    assert!(!validate(&KeccakHash::default(), &signer(), &EASY, &res));
+    assert!(!validate(
+        &challenge(),
+        &signer(),
+        &KeccakHash::default(),
+        &res
+    ));
+    let wrong_nonce = MiningResult {
+        nonce: res.nonce + 1,
+        ..res.clone()
+    };
+    assert!(!validate(&challenge(), &signer(), &EASY, &wrong_nonce));
+}
+
+#[test]
+fn solution_is_encoded_in_mine_instruction() {
+    let res = search(&challenge(), &signer(), &EASY, 0..u64::MAX, |_| true).unwrap();
+    let ix = mars::instruction::mine(signer(), mars::BUS_ADDRESSES[0], res.hash.into(), res.nonce);
+    assert_eq!(ix.program_id, mars::ID);
+    assert_eq!(ix.data[1..33], res.hash.to_bytes());
+    assert_eq!(ix.data[33..41], res.nonce.to_le_bytes());
+}
+
+#[test]
+fn stops_when_asked() {
+    let impossible = KeccakHash::default();
+    let mut batches = 0;
+    let res = search(&challenge(), &signer(), &impossible, 0..u64::MAX, |n| {
+        assert_eq!(n, BATCH_SIZE);
+        batches += 1;
+        batches < 3
+    });
+    assert_eq!(res, None);
+    assert_eq!(batches, 3);
+}
+
+#[test]
+fn kernel_checks_every_lane() {
+    // The first solution is found whichever lane it lands in
+    let kernel = Kernel::new(&CHALLENGE, &SIGNER, &difficulty(1));
+    let (first, _) = FIRST_SOLUTION;
+    for offset in 0..LANES as u64 {
+        let start = first - offset;
+        let (nonce, _) = kernel.scan(start..start + LANES as u64).unwrap();
+        assert_eq!(nonce, first);
+    }
+    assert_eq!(kernel.scan(0..first), None);
+    assert_eq!(kernel.scan(first..first), None);
+}
+
+#[test]
+fn partition_of_zero_threads_is_empty() {
+    assert!(partition(0).is_empty());
+}
+
+proptest! {
+    #[test]
+    fn partition_covers_nonce_space(threads in 1u64..=1024) {
+        let ranges = partition(threads);
+        prop_assert_eq!(ranges.len() as u64, threads);
+        prop_assert_eq!(ranges[0].start, 0);
+        prop_assert_eq!(ranges.last().unwrap().end, u64::MAX);
+        for pair in ranges.windows(2) {
+            prop_assert_eq!(pair[0].end, pair[1].start);
+        }
+        for range in &ranges {
+            prop_assert!(range.start < range.end);
+        }
+    }
+
+    #[test]
+    fn partition_is_balanced(threads in 1u64..=1024) {
+        let ranges = partition(threads);
+        let sizes: Vec<u64> = ranges.iter().map(|r| r.end - r.start).collect();
+        let min = *sizes.iter().min().unwrap();
+        let max = *sizes.iter().max().unwrap();
+        prop_assert!(max - min < threads);
+    }
+
+    #[test]
+    fn search_stays_in_range(start in 0u64..1_000_000, len in 1u64..2_000, seed in any::<[u8; 32]>()) {
+        let challenge = KeccakHash::new_from_array(seed);
+        let res = search(&challenge, &signer(), &EASY, start..start + len, |_| true);
+        if let Some(res) = res {
+            prop_assert!((start..start + len).contains(&res.nonce));
+            prop_assert!(validate(&challenge, &signer(), &EASY, &res));
+            for nonce in start..res.nonce {
+                prop_assert!(hash_nonce(&challenge, &signer(), nonce) > EASY);
+            }
+        } else {
+            for nonce in start..start + len {
+                prop_assert!(hash_nonce(&challenge, &signer(), nonce) > EASY);
+            }
+        }
+    }
+
+    #[test]
+    fn kernel_matches_reference(challenge in any::<[u8; 32]>(), signer in any::<[u8; 32]>(), nonce in any::<u64>()) {
+        let kernel = Kernel::new(&challenge, &signer, &[0; 32]);
+        let expected = hash_nonce(
+            &KeccakHash::new_from_array(challenge),
+            &Pubkey::new_from_array(signer),
+            nonce,
+        );
+        prop_assert_eq!(kernel.hash(nonce), expected.to_bytes());
+    }
+
+    #[test]
+    fn kernel_finds_first_solution(
+        challenge in any::<[u8; 32]>(),
+        offset in 0u64..1_000_000,
+        len in 0u64..1_000,
+        at_end in any::<bool>(),
+        threshold in 0u8..4,
+    ) {
+        // Odd lengths and the end of the nonce space leave some lanes unused
+        let start = if at_end { u64::MAX - len - offset % 8 } else { offset };
+        let mut difficulty = [255; 32];
+        difficulty[0] = threshold;
+        let kernel = Kernel::new(&challenge, &signer().to_bytes(), &difficulty);
+        let challenge = KeccakHash::new_from_array(challenge);
+        let expected = (start..start + len)
+            .map(|nonce| (nonce, hash_nonce(&challenge, &signer(), nonce).to_bytes()))
+            .find(|(_, hash)| hash.le(&difficulty));
+        prop_assert_eq!(kernel.scan(start..start + len), expected);
+    }
+
+    #[test]
+    fn partition_keeps_thread_offsets(threads in 1u64..=1024) {
+        // Threads start where they always have, so hashrates stay comparable across versions
+        for (i, range) in partition(threads).iter().enumerate() {
+            prop_assert_eq!(range.start, (u64::MAX / threads) * i as u64);
+        }
+    }
+}
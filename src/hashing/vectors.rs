@@ -0,0 +1,56 @@
+//! Expected hashes, computed on desktop with the reference path.
+//! Shared by the desktop and wasm tests, so every build is checked against the same answers.
+
+pub const CHALLENGE: [u8; 32] = [1; 32];
+pub const SIGNER: [u8; 32] = [2; 32];
+
+/// Challenge, signer, nonce and the hash in hex.
+pub const KNOWN_ANSWERS: [([u8; 32], [u8; 32], u64, &str); 4] = [
+    (
+        [0; 32],
+        [0; 32],
+        0,
+        "3cac317908c699fe873a7f6ee4e8cd63fbe9918b2315c97be91585590168e301",
+    ),
+    (
+        CHALLENGE,
+        SIGNER,
+        0,
+        "76d1f755d008eded218d2c94ec79ccb8c1a3891dfab3470a80490d86a6317ddd",
+    ),
+    (
+        CHALLENGE,
+        SIGNER,
+        1,
+        "3fbba8edf119dea8953eb775d20c8b1aab1fb56ca26b2c2e70c734f6b8c4b914",
+    ),
+    (
+        CHALLENGE,
+        SIGNER,
+        u64::MAX - 1,
+        "b07276a9137bdf08564eee3c76c0035f224511a2334b87d988ac7a320cf62449",
+    ),
+];
+
+/// First nonce for `CHALLENGE` and `SIGNER` whose hash starts with a zero byte.
+pub const FIRST_SOLUTION: (u64, &str) = (
+    69,
+    "00352f5a6c10e847788644fb356aff97de26b4e7f61f85fae490b0b7df5420b1",
+);
+
+/// First nonce for `CHALLENGE` and `SIGNER` whose hash starts with two zero bytes.
+pub const FIRST_HARD_SOLUTION: (u64, &str) = (
+    98_680,
+    "0000ceacba7962be995f1b1e35f6ff7dd3c3c0d145613b2a1048f1bc20c32351",
+);
+
+/// Accepts hashes starting with `zeros` zero bytes.
+pub fn difficulty(zeros: usize) -> [u8; 32] {
+    let mut difficulty = [255; 32];
+    difficulty[..zeros].fill(0);
+    difficulty
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,71 @@
+//! Runs the wasm builds against the answers checked on desktop. With `wasm-pack` installed:
+//! `wasm-pack test --node` for the scalar worker, and with
+//! `RUSTFLAGS="--cfg=web_sys_unstable_apis -C target-feature=+simd128"` for the SIMD worker.
+
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use super::{
+    hash_nonce, kernel_name, search,
+    vectors::{
+        difficulty, hex, CHALLENGE, FIRST_HARD_SOLUTION, FIRST_SOLUTION, KNOWN_ANSWERS, SIGNER,
+    },
+    KeccakHash, Kernel, Pubkey, LANES,
+};
+
+#[wasm_bindgen_test]
+fn runs_expected_kernel() {
+    let expected = if cfg!(target_feature = "simd128") {
+        "simd128"
+    } else {
+        "portable"
+    };
+    assert_eq!(kernel_name(), expected);
+}
+
+#[wasm_bindgen_test]
+fn known_answers() {
+    for (challenge, signer, nonce, expected) in KNOWN_ANSWERS {
+        let kernel = Kernel::new(&challenge, &signer, &[0; 32]);
+        assert_eq!(hex(&kernel.hash(nonce)), expected);
+        let reference = hash_nonce(
+            &KeccakHash::new_from_array(challenge),
+            &Pubkey::new_from_array(signer),
+            nonce,
+        );
+        assert_eq!(hex(reference.as_ref()), expected);
+    }
+}
+
+#[wasm_bindgen_test]
+fn finds_first_solutions() {
+    let challenge = KeccakHash::new_from_array(CHALLENGE);
+    let signer = Pubkey::new_from_array(SIGNER);
+    for (zeros, (nonce, hash)) in [(1, FIRST_SOLUTION), (2, FIRST_HARD_SOLUTION)] {
+        let difficulty = KeccakHash::new_from_array(difficulty(zeros));
+        let res = search(&challenge, &signer, &difficulty, 0..u64::MAX, |_| true).unwrap();
+        assert_eq!(res.nonce, nonce);
+        assert_eq!(hex(res.hash.as_ref()), hash);
+    }
+}
+
+#[wasm_bindgen_test]
+fn kernel_matches_reference_in_every_lane() {
+    let challenge = KeccakHash::new_from_array(CHALLENGE);
+    let signer = Pubkey::new_from_array(SIGNER);
+    let kernel = Kernel::new(&CHALLENGE, &SIGNER, &[255; 32]);
+    for start in 0..(2 * LANES as u64) {
+        // Every hash meets the difficulty, so each scan returns its first nonce
+        let (nonce, hash) = kernel.scan(start..start + LANES as u64).unwrap();
+        assert_eq!(nonce, start);
+        assert_eq!(hash, hash_nonce(&challenge, &signer, start).to_bytes());
+    }
+    let (first, _) = FIRST_SOLUTION;
+    let kernel = Kernel::new(&CHALLENGE, &SIGNER, &difficulty(1));
+    for offset in 0..LANES as u64 {
+        let start = first - offset;
+        assert_eq!(
+            kernel.scan(start..start + LANES as u64).map(|(n, _)| n),
+            Some(first)
+        );
+    }
+}
@@ -2,12 +2,20 @@ mod use_account;
 mod use_appearance;
 #[cfg(feature = "web")]
 mod use_clipboard;
+mod use_config;
 mod use_date;
 mod use_explorer;
 mod use_gateway;
+mod use_idle_mining;
 mod use_is_onboarded;
 mod use_keypair;
 mod use_miner;
+mod use_miner_recovery;
+mod use_mining_schedule;
+mod use_mining_settings;
+mod use_mining_usage;
+mod use_notification_center;
+mod use_notification_settings;
 mod use_mars_balance;
 mod use_mars_supply;
 mod use_persistent;
@@ -15,34 +23,58 @@ mod use_ping;
 mod use_power_level;
 mod use_priority_fee;
 mod use_proof;
+mod use_pubsub;
 mod use_rpc;
-mod use_show_backup_warning;
+mod use_scheduled_mining;
 mod use_sol_balance;
+mod use_stale_challenges;
+mod use_supply_history;
+mod use_tip_config;
 mod use_transfers;
 mod use_transfers_websocket;
 mod use_treasury;
+#[cfg(feature = "desktop")]
+mod use_tray;
+mod use_wallet;
+mod use_watchlist;
 mod use_window_width;
 
 pub use use_account::*;
 pub use use_appearance::*;
 #[cfg(feature = "web")]
 pub use use_clipboard::*;
+pub use use_config::*;
 pub use use_date::*;
 pub use use_explorer::*;
 pub use use_gateway::*;
+pub use use_idle_mining::*;
 pub use use_is_onboarded::*;
 pub use use_keypair::*;
 pub use use_miner::*;
+pub use use_miner_recovery::*;
+pub use use_mining_schedule::*;
+pub use use_mining_settings::*;
+pub use use_mining_usage::*;
+pub use use_notification_center::*;
+pub use use_notification_settings::*;
 pub use use_mars_balance::*;
 pub use use_mars_supply::*;
 pub use use_ping::*;
 pub use use_power_level::*;
 pub use use_priority_fee::*;
 pub use use_proof::*;
+pub use use_pubsub::*;
 pub use use_rpc::*;
-pub use use_show_backup_warning::*;
+pub use use_scheduled_mining::*;
 pub use use_sol_balance::*;
+pub use use_stale_challenges::*;
+pub use use_supply_history::*;
+pub use use_tip_config::*;
 pub use use_transfers::*;
 pub use use_transfers_websocket::*;
 pub use use_treasury::*;
+#[cfg(feature = "desktop")]
+pub use use_tray::*;
+pub use use_wallet::*;
+pub use use_watchlist::*;
 pub use use_window_width::*;
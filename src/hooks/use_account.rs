@@ -11,9 +11,12 @@ use solana_sdk::pubkey::Pubkey;
 #[cfg(feature = "web")]
 use web_time::Duration;
 
-use crate::gateway::AsyncResult;
+use crate::{
+    gateway::AsyncResult,
+    pubsub::{Topic, Update},
+};
 
-use super::use_gateway;
+use super::{use_gateway, use_pubsub};
 
 pub fn use_account<
     T: AccountDeserialize + Send + Sync + Clone + Copy + std::fmt::Debug + 'static,
@@ -24,6 +27,7 @@ pub fn use_account<
 ) -> (&mut UseRw<AsyncResult<T>>, &UseFuture<()>) {
     let acc = use_rw::<AsyncResult<T>>(cx, || AsyncResult::Loading);
     let gateway = use_gateway(cx);
+    let pubsub = use_pubsub(cx);
 
     let f = use_future(cx, (), |_| {
         let acc = acc.clone();
@@ -37,13 +41,37 @@ pub fn use_account<
         }
     });
 
+    // Follow changes over the pubsub connection
+    use_future(cx, (), |_| {
+        let acc = acc.clone();
+        let f = f.clone();
+        let rx = pubsub.subscribe(Topic::Account(address));
+        async move {
+            while let Ok(update) = rx.recv().await {
+                match update {
+                    Update::Account(account) => {
+                        if let Ok(t) = T::try_from_bytes(account.data.as_ref()) {
+                            acc.write(AsyncResult::Ok(*t)).unwrap();
+                        }
+                    }
+                    Update::Resubscribed => f.restart(),
+                    Update::Signature(_) => {}
+                }
+            }
+        }
+    });
+
+    // Poll while the connection is down
     use_future(cx, (), |_| {
         let f = f.clone();
+        let pubsub = pubsub.clone();
         async move {
             if let Some(d) = poll {
                 loop {
                     async_std::task::sleep(Duration::from_secs(d)).await;
-                    f.restart();
+                    if !pubsub.is_connected() {
+                        f.restart();
+                    }
                 }
             }
         }
@@ -51,63 +79,3 @@ pub fn use_account<
 
     (acc, f)
 }
-
-// NOTE: For some reason, the rpc subscription only works with one account globally.
-// Even if a second rpc client is used for the second subscription, generating different subscription ids,
-// one of them will inevitably throw "dropped closure" errors. It's not clear why this is.
-// So for now, this hook can only be used once globally throughout the app.
-// We'll use it to subscribe to the treasury account in the miner toolbar and manually refresh all other accounts.
-// pub fn use_account_subscribe<
-//     'a,
-//     T: AccountDeserialize + Send + Sync + Clone + Copy + std::fmt::Debug + 'static,
-// >(
-//     cx: &'a ScopeState,
-//     address: Pubkey,
-//     rw: &'a mut UseRw<AsyncResult<T>>,
-// ) -> &'a Coroutine<()> {
-//     #[cfg(feature = "web")]
-//     let gateway = use_gateway(cx);
-
-//     use_coroutine(cx, |mut _rx| {
-//         #[cfg(feature = "web")]
-//         let gateway = gateway.clone();
-//         let rw = rw.clone();
-//         async move {
-//             #[cfg(feature = "web")]
-//             let _ = gateway
-//                 .rpc
-//                 .account_subscribe(address, move |account| {
-//                     if let Some(ui_account) = account.value {
-//                         if let Some(account) = ui_account.decode::<Account>() {
-//                             if let Ok(t) = T::try_from_bytes(account.data.as_ref()) {
-//                                 rw.write(AsyncResult::Ok(*t)).ok();
-//                             }
-//                         }
-//                     }
-//                 })
-//                 .await;
-
-//             #[cfg(feature = "desktop")]
-//             std::thread::spawn(move || {
-//                 match PubsubClient::account_subscribe(
-//                     RPC_WSS_URL,
-//                     &address,
-//                     Some(solana_client::rpc_config::RpcAccountInfoConfig::default()),
-//                 ) {
-//                     Ok((mut _sub, rx)) => {
-//                         while let Ok(message) = rx.recv() {
-//                             if let UiAccountData::LegacyBinary(data) = message.value.data {
-//                                 if let Ok(t) = T::try_from_bytes(data.into_bytes().as_ref()) {
-//                                     rw.write(AsyncResult::Ok(*t)).ok();
-//                                 }
-//                             }
-//                         }
-//                     }
-//                     Err(err) => {
-//                         log::error!("Failed to subscribe to account: {:?}", err)
-//                     }
-//                 };
-//             });
-//         }
-//     })
-// }
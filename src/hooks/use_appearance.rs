@@ -1,20 +1,20 @@
 use dioxus::prelude::*;
 
-use crate::{components::Appearance, hooks::use_persistent::use_persistent};
+use crate::components::Appearance;
 
-const KEY: &str = "appearance";
+use super::use_config;
 
 pub fn use_appearance(cx: &ScopeState) -> &UseSharedState<Appearance> {
     let appearance = use_shared_state::<Appearance>(cx).unwrap();
-    let appearance_persistent = use_persistent(cx, KEY, || Appearance::Dark);
+    let config = use_config(cx);
     use_effect(cx, appearance, |_| {
-        appearance_persistent.set(*appearance.read());
+        config.update(|c| c.appearance = *appearance.read());
         async move {}
     });
     appearance
 }
 
 pub fn use_appearance_provider(cx: &ScopeState) {
-    let appearance = use_persistent(cx, KEY, || Appearance::Dark).get();
+    let appearance = use_config(cx).get().appearance;
     use_shared_state_provider(cx, || appearance);
 }
@@ -0,0 +1,97 @@
+use std::{cell::RefCell, rc::Rc};
+
+use dioxus::prelude::*;
+
+use crate::{
+    components::{Appearance, Explorer},
+    config::{load_config, save_config, AppConfig},
+    gateway::TipConfig,
+    miner::MiningSettings,
+    notifications::{publish, NotificationSettings, Severity},
+    schedule::MiningSchedule,
+};
+
+use super::{IsOnboarded, PowerLevel, PriorityFee, RpcUrl, Watchlist};
+
+/// Handle to the persisted config. Each update is saved in a single write.
+#[derive(Clone)]
+pub struct ConfigHandle(Rc<RefCell<AppConfig>>);
+
+impl ConfigHandle {
+    pub fn get(&self) -> AppConfig {
+        self.0.borrow().clone()
+    }
+
+    pub fn update(&self, f: impl FnOnce(&mut AppConfig)) {
+        let mut config = self.0.borrow_mut();
+        let prev = config.clone();
+        f(&mut config);
+        if config.ne(&prev) {
+            if let Err(err) = save_config(&config) {
+                log::error!("{}", err);
+                publish(Severity::Error, "Failed to save settings", err);
+            }
+        }
+    }
+}
+
+pub fn use_config(cx: &ScopeState) -> &ConfigHandle {
+    use_context::<ConfigHandle>(cx).unwrap()
+}
+
+pub fn use_config_provider(cx: &ScopeState) {
+    use_context_provider(cx, || ConfigHandle(Rc::new(RefCell::new(load_config()))));
+}
+
+/// Replaces every setting at once, such as with an imported config.
+#[derive(Clone)]
+pub struct ConfigApplier {
+    config: ConfigHandle,
+    appearance: UseSharedState<Appearance>,
+    explorer: UseSharedState<Explorer>,
+    power_level: UseSharedState<PowerLevel>,
+    mining_settings: UseSharedState<MiningSettings>,
+    mining_schedule: UseSharedState<MiningSchedule>,
+    priority_fee: UseSharedState<PriorityFee>,
+    rpc_url: UseSharedState<RpcUrl>,
+    is_onboarded: UseSharedState<IsOnboarded>,
+    tip_config: UseSharedState<TipConfig>,
+    notification_settings: UseSharedState<NotificationSettings>,
+    watchlist: UseSharedState<Watchlist>,
+}
+
+impl ConfigApplier {
+    pub fn apply(&self, config: AppConfig) {
+        *self.appearance.write() = config.appearance;
+        *self.explorer.write() = config.explorer;
+        *self.power_level.write() = config.power_level;
+        *self.mining_settings.write() = config.mining_settings;
+        *self.mining_schedule.write() = config.mining_schedule;
+        *self.priority_fee.write() = config.priority_fee;
+        *self.rpc_url.write() = config.rpc_url.clone();
+        *self.is_onboarded.write() = config.is_onboarded;
+        *self.tip_config.write() = config.tip_config;
+        *self.notification_settings.write() = config.notification_settings;
+        *self.watchlist.write() = config.watchlist.clone();
+        self.config.update(|c| *c = config);
+    }
+}
+
+pub fn use_config_applier(cx: &ScopeState) -> ConfigApplier {
+    ConfigApplier {
+        config: use_config(cx).clone(),
+        appearance: use_shared_state::<Appearance>(cx).unwrap().clone(),
+        explorer: use_shared_state::<Explorer>(cx).unwrap().clone(),
+        power_level: use_shared_state::<PowerLevel>(cx).unwrap().clone(),
+        mining_settings: use_shared_state::<MiningSettings>(cx).unwrap().clone(),
+        mining_schedule: use_shared_state::<MiningSchedule>(cx).unwrap().clone(),
+        priority_fee: use_shared_state::<PriorityFee>(cx).unwrap().clone(),
+        rpc_url: use_shared_state::<RpcUrl>(cx).unwrap().clone(),
+        is_onboarded: use_shared_state::<IsOnboarded>(cx).unwrap().clone(),
+        tip_config: use_shared_state::<TipConfig>(cx).unwrap().clone(),
+        notification_settings: use_shared_state::<NotificationSettings>(cx)
+            .unwrap()
+            .clone(),
+        watchlist: use_shared_state::<Watchlist>(cx).unwrap().clone(),
+    }
+}
@@ -2,22 +2,22 @@ use std::ops::Deref;
 
 use dioxus::prelude::*;
 
-use crate::{components::Explorer, hooks::use_persistent::use_persistent};
+use crate::components::Explorer;
 
-const KEY: &str = "explorer";
+use super::use_config;
 
 pub fn use_explorer(cx: &ScopeState) -> &UseSharedState<Explorer> {
     let explorer = use_shared_state::<Explorer>(cx).unwrap();
-    let explorer_persistent = use_persistent(cx, KEY, || Explorer::Solana);
+    let config = use_config(cx);
     use_effect(cx, explorer, |_| {
-        explorer_persistent.set(*explorer.read());
+        config.update(|c| c.explorer = *explorer.read());
         async move {}
     });
     explorer
 }
 
 pub fn use_explorer_provider(cx: &ScopeState) {
-    let explorer = use_persistent(cx, KEY, || Explorer::Solana).get();
+    let explorer = use_config(cx).get().explorer;
     use_shared_state_provider(cx, || explorer);
 }
 
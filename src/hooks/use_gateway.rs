@@ -2,11 +2,24 @@ use std::rc::Rc;
 
 use dioxus::prelude::*;
 
-use crate::gateway::{Gateway, API_URL};
+use crate::{
+    gateway::{Gateway, API_URL},
+    pubsub::PubsubHandle,
+};
 
-use super::use_rpc_url;
+use super::{use_rpc_url, use_tip_config};
 
 pub fn use_gateway(cx: &ScopeState) -> Rc<Gateway> {
     let rpc_url = use_rpc_url(cx);
-    Rc::new(Gateway::new(API_URL.to_string(), rpc_url.read().0.clone()))
+    let tip_config = use_tip_config(cx);
+    // The pubsub connection is only up once the wallet is ready
+    let pubsub = cx.consume_context::<PubsubHandle>();
+    Rc::new(
+        Gateway::new(
+            API_URL.to_string(),
+            rpc_url.read().0.clone(),
+            *tip_config.read(),
+        )
+        .with_pubsub(pubsub),
+    )
 }
@@ -0,0 +1,41 @@
+use dioxus::prelude::*;
+
+use crate::{
+    idle::{IdleMonitor, IDLE_POLL_INTERVAL},
+    miner::Miner,
+    miner_state::{MinerEvent, MinerState, MinerStatus},
+};
+
+use super::use_mining_settings;
+
+/// Pauses the miner while the machine is in use, and resumes it once idle, when idle-only mining is on.
+pub fn use_idle_mining(cx: &ScopeState, miner: &UseState<Miner>) {
+    let state = use_shared_state::<MinerState>(cx).unwrap();
+    let settings = use_mining_settings(cx);
+    use_future(cx, (), |_| {
+        let state = state.clone();
+        let settings = settings.clone();
+        let miner = miner.clone();
+        async move {
+            let mut monitor = IdleMonitor::default();
+            loop {
+                let settings = *settings.read();
+                let is_idle = !settings.idle_only || monitor.is_idle(&settings).await;
+                let (is_active, is_paused) = match state.read().status {
+                    MinerStatus::Active => (true, false),
+                    MinerStatus::Paused => (false, true),
+                    _ => (false, false),
+                };
+                if is_active && !is_idle {
+                    log::info!("Pausing the miner while the machine is in use");
+                    miner.get().stop();
+                    state.write().apply(MinerEvent::Pause);
+                } else if is_paused && is_idle {
+                    log::info!("Resuming the miner");
+                    state.write().apply(MinerEvent::Resume);
+                }
+                async_std::task::sleep(IDLE_POLL_INTERVAL).await;
+            }
+        }
+    });
+}
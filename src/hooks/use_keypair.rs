@@ -4,22 +4,17 @@ use solana_client_wasm::solana_sdk::{pubkey::Pubkey, signature::Keypair, signatu
 #[cfg(feature = "desktop")]
 use solana_sdk::{pubkey::Pubkey, signature::Keypair, signature::Signer};
 
-use crate::hooks::use_persistent::use_persistent;
-
-use super::use_persistent::UsePersistent;
-
-const KEY: &str = "keypair";
+/// Base58 private key of the loaded wallet, provided to everything rendered within a session.
+#[derive(Clone)]
+pub struct SessionKeypair(pub String);
 
 pub fn use_pubkey(cx: &ScopeState) -> Pubkey {
     let kp = use_keypair(cx);
     kp.pubkey()
 }
 
+/// Only usable within a session, which the app starts once the wallet is ready.
 pub fn use_keypair(cx: &ScopeState) -> Keypair {
-    let kp_str = use_persistent(cx, KEY, || Keypair::new().to_base58_string());
-    Keypair::from_base58_string(&kp_str.get())
-}
-
-pub fn use_keypair_persistent(cx: &ScopeState) -> &UsePersistent<String> {
-    use_persistent(cx, KEY, || Keypair::new().to_base58_string())
+    let keypair = use_context::<SessionKeypair>(cx).unwrap();
+    Keypair::from_base58_string(&keypair.0)
 }
@@ -42,22 +42,19 @@ pub fn use_mars_balance_provider(cx: &ScopeState) {
                 Ok(token_account_balance) => {
                     *balance.write() = AsyncResult::Ok(token_account_balance);
                 }
-                Err(err) => {
-                    let err = GatewayError::from(err);
-                    match err {
-                        GatewayError::AccountNotFound => {
-                            *balance.write() = AsyncResult::Ok(UiTokenAmount {
-                                ui_amount: Some(0f64),
-                                decimals: mars::TOKEN_DECIMALS,
-                                amount: "0.00".to_string(),
-                                ui_amount_string: "0.00".to_string(),
-                            });
-                        }
-                        _ => {
-                            *balance.write() = AsyncResult::Error(err);
-                        }
+                Err(err) => match err {
+                    GatewayError::AccountNotFound => {
+                        *balance.write() = AsyncResult::Ok(UiTokenAmount {
+                            ui_amount: Some(0f64),
+                            decimals: mars::TOKEN_DECIMALS,
+                            amount: "0.00".to_string(),
+                            ui_amount_string: "0.00".to_string(),
+                        });
                     }
-                }
+                    _ => {
+                        *balance.write() = AsyncResult::Error(err);
+                    }
+                },
             }
         }
     });
@@ -95,22 +92,17 @@ pub fn use_mars_balance_user(cx: &ScopeState, pubkey: Pubkey) -> AsyncResult<UiT
                 Ok(token_account_balance) => {
                     balance.set(AsyncResult::Ok(token_account_balance));
                 }
-                Err(err) => {
-                    let err = GatewayError::from(err);
-                    match err {
-                        GatewayError::AccountNotFound => {
-                            balance.set(AsyncResult::Ok(UiTokenAmount {
-                                ui_amount: Some(0f64),
-                                decimals: mars::TOKEN_DECIMALS,
-                                amount: "0.00".to_string(),
-                                ui_amount_string: "0.00".to_string(),
-                            }))
-                        }
-                        _ => {
-                            balance.set(AsyncResult::Error(err));
-                        }
+                Err(err) => match err {
+                    GatewayError::AccountNotFound => balance.set(AsyncResult::Ok(UiTokenAmount {
+                        ui_amount: Some(0f64),
+                        decimals: mars::TOKEN_DECIMALS,
+                        amount: "0.00".to_string(),
+                        ui_amount_string: "0.00".to_string(),
+                    })),
+                    _ => {
+                        balance.set(AsyncResult::Error(err));
                     }
-                }
+                },
             }
         }
     });
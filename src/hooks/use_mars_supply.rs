@@ -22,7 +22,7 @@ pub fn use_mars_supply(cx: &ScopeState) -> (AsyncResult<UiTokenAmount>, &UseFutu
                     supply.set(AsyncResult::Ok(token_amount));
                 }
                 Err(err) => {
-                    supply.set(AsyncResult::Error(err.into()));
+                    supply.set(AsyncResult::Error(err));
                 }
             }
         }
@@ -3,9 +3,19 @@ use dioxus_std::utils::channel::UseChannel;
 
 use crate::miner::{Miner, MiningResult};
 
-use super::use_power_level;
+use super::{use_mining_settings, use_power_level};
 
 pub fn use_miner<'a>(cx: &'a ScopeState, ch: &'a UseChannel<MiningResult>) -> &'a UseState<Miner> {
     let power_level = use_power_level(cx);
-    use_state(cx, || Miner::new(ch, power_level))
+    let mining_settings = use_mining_settings(cx);
+    let miner = use_state(cx, || Miner::new(ch, power_level, mining_settings));
+
+    // Running threads pick up new settings without waiting for the next challenge
+    #[cfg(feature = "desktop")]
+    use_effect(cx, (power_level, mining_settings), |_| {
+        miner.get().configure();
+        async move {}
+    });
+
+    miner
 }
@@ -0,0 +1,44 @@
+use dioxus::prelude::*;
+
+use crate::miner_state::{MinerEvent, MinerState, MinerStatus, RECOVERY_POLL_INTERVAL};
+
+use super::use_gateway;
+
+/// Starts the miner again after a failure: once the backoff has passed, or once the network
+/// is back after the retries ran out.
+pub fn use_miner_recovery(cx: &ScopeState) {
+    let state = use_shared_state::<MinerState>(cx).unwrap();
+    let gateway = use_gateway(cx);
+    let current = *state.read();
+    use_future(cx, &current, |current| {
+        let state = state.clone();
+        let gateway = gateway.clone();
+        async move {
+            match current.status {
+                MinerStatus::Retrying(error) => {
+                    let delay = current.retry_delay().unwrap_or_default();
+                    log::info!("Restarting the miner in {:?} after: {}", delay, error);
+                    async_std::task::sleep(delay).await;
+                }
+                MinerStatus::Error(error) if error.is_retryable() => {
+                    // Only an outage that ends is worth resuming for
+                    let mut was_offline = false;
+                    loop {
+                        let is_online = gateway.rpc.get_slot().await.is_ok();
+                        if is_online && was_offline {
+                            break;
+                        }
+                        was_offline |= !is_online;
+                        async_std::task::sleep(RECOVERY_POLL_INTERVAL).await;
+                    }
+                    log::info!("Network is back, resuming the miner");
+                }
+                _ => return,
+            }
+            let is_current = state.read().eq(&current);
+            if is_current {
+                state.write().apply(MinerEvent::Retry);
+            }
+        }
+    });
+}
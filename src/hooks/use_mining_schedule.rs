@@ -0,0 +1,20 @@
+use dioxus::prelude::*;
+
+use crate::schedule::MiningSchedule;
+
+use super::use_config;
+
+pub fn use_mining_schedule(cx: &ScopeState) -> &UseSharedState<MiningSchedule> {
+    let schedule = use_shared_state::<MiningSchedule>(cx).unwrap();
+    let config = use_config(cx);
+    use_effect(cx, schedule, |_| {
+        config.update(|c| c.mining_schedule = *schedule.read());
+        async move {}
+    });
+    schedule
+}
+
+pub fn use_mining_schedule_provider(cx: &ScopeState) {
+    let schedule = use_config(cx).get().mining_schedule;
+    use_shared_state_provider(cx, || schedule);
+}
@@ -0,0 +1,20 @@
+use dioxus::prelude::*;
+
+use crate::miner::MiningSettings;
+
+use super::use_config;
+
+pub fn use_mining_settings(cx: &ScopeState) -> &UseSharedState<MiningSettings> {
+    let settings = use_shared_state::<MiningSettings>(cx).unwrap();
+    let config = use_config(cx);
+    use_effect(cx, settings, |_| {
+        config.update(|c| c.mining_settings = *settings.read());
+        async move {}
+    });
+    settings
+}
+
+pub fn use_mining_settings_provider(cx: &ScopeState) {
+    let settings = use_config(cx).get().mining_settings;
+    use_shared_state_provider(cx, || settings);
+}
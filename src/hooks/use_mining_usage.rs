@@ -0,0 +1,65 @@
+use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use gloo_storage::{LocalStorage, Storage};
+
+#[cfg(feature = "desktop")]
+use crate::file::{read_file, write_file};
+use crate::schedule::{MiningUsage, SCHEDULE_POLL_INTERVAL};
+
+/// Stored apart from the config, as it changes while mining.
+const USAGE_KEY: &str = "mining_usage";
+
+/// Today's hashes, fees and solutions.
+pub fn use_mining_usage(cx: &ScopeState) -> &UseSharedState<MiningUsage> {
+    use_shared_state::<MiningUsage>(cx).unwrap()
+}
+
+#[cfg(feature = "web")]
+fn load_usage() -> MiningUsage {
+    LocalStorage::get(USAGE_KEY).unwrap_or_default()
+}
+
+#[cfg(feature = "desktop")]
+fn load_usage() -> MiningUsage {
+    read_file(USAGE_KEY).unwrap_or_default()
+}
+
+fn save_usage(usage: &MiningUsage) {
+    #[cfg(feature = "web")]
+    LocalStorage::set(USAGE_KEY, usage).ok();
+
+    #[cfg(feature = "desktop")]
+    if let Err(err) = write_file(USAGE_KEY, usage) {
+        log::error!("Failed to save mining usage: {}", err);
+    }
+}
+
+/// Counts in memory while mining. Saves when fees are paid, the day rolls over or hashing stops,
+/// rather than on every count.
+pub fn use_mining_usage_provider(cx: &ScopeState) {
+    use_shared_state_provider(cx, load_usage);
+    let usage = use_shared_state::<MiningUsage>(cx).unwrap();
+    use_future(cx, (), |_| {
+        let usage = usage.clone();
+        async move {
+            let mut saved = usage.read().clone();
+            let mut last_hashes = saved.hashes;
+            loop {
+                // Hashes are counted every poll, so two polls without any means hashing stopped
+                async_std::task::sleep(SCHEDULE_POLL_INTERVAL * 2).await;
+                let current = usage.read().clone();
+                let is_hashing = current.hashes.ne(&last_hashes);
+                last_hashes = current.hashes;
+                if current.ne(&saved)
+                    && (!is_hashing
+                        || current.day.ne(&saved.day)
+                        || current.fees.ne(&saved.fees)
+                        || current.solutions.ne(&saved.solutions))
+                {
+                    save_usage(&current);
+                    saved = current;
+                }
+            }
+        }
+    });
+}
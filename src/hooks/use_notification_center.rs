@@ -0,0 +1,170 @@
+#[cfg(feature = "desktop")]
+use std::time::Duration;
+
+use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use gloo_storage::{LocalStorage, Storage};
+#[cfg(feature = "web")]
+use web_time::Duration;
+
+#[cfg(feature = "desktop")]
+use crate::file::{read_file, write_file};
+use crate::notifications::{published, AppNotification, Severity};
+
+/// Stored apart from the config, as it changes with every notification.
+const HISTORY_KEY: &str = "notification_history";
+
+/// Notifications kept in the history panel.
+const HISTORY_LIMIT: usize = 100;
+
+/// Seconds a toast is shown.
+const TOAST_DURATION: i64 = 6;
+
+/// Seconds an error toast is shown, so there is time to read it.
+const ERROR_TOAST_DURATION: i64 = 60;
+
+/// Seconds between saves of the history, so bursts of notifications are written once.
+const SAVE_INTERVAL: u64 = 5;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NotificationCenter {
+    /// Currently shown as toasts, oldest first.
+    pub toasts: Vec<AppNotification>,
+    /// Newest first.
+    pub history: Vec<AppNotification>,
+    /// Received since the history was last viewed.
+    pub unread: usize,
+    /// Id for the next notification. Never reused, even once the history is cleared.
+    next_id: u64,
+}
+
+impl NotificationCenter {
+    pub fn new(history: Vec<AppNotification>) -> Self {
+        let next_id = history.iter().map(|h| h.id + 1).max().unwrap_or(0);
+        Self {
+            history,
+            next_id,
+            ..Default::default()
+        }
+    }
+
+    /// Adds a notification, or bumps the count of an identical one still showing.
+    pub fn push(&mut self, mut notification: AppNotification) {
+        if let Some(toast) = self.toasts.iter_mut().find(|t| t.is_same(&notification)) {
+            toast.count = toast.count.saturating_add(1);
+            toast.ts = notification.ts;
+            if let Some(entry) = self.history.iter_mut().find(|h| h.id.eq(&toast.id)) {
+                entry.count = toast.count;
+                entry.ts = toast.ts;
+            }
+            return;
+        }
+        notification.id = self.next_id;
+        self.next_id = self.next_id.saturating_add(1);
+        self.toasts.push(notification.clone());
+        self.history.insert(0, notification);
+        self.history.truncate(HISTORY_LIMIT);
+        self.unread = self.unread.saturating_add(1);
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.toasts.retain(|t| t.id.ne(&id));
+    }
+
+    /// True if any toast is due to be hidden.
+    pub fn has_expired(&self, now: i64) -> bool {
+        self.toasts.iter().any(|t| is_expired(t, now))
+    }
+
+    pub fn expire(&mut self, now: i64) {
+        self.toasts.retain(|t| !is_expired(t, now));
+    }
+
+    pub fn mark_read(&mut self) {
+        self.unread = 0;
+    }
+
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+        self.unread = 0;
+    }
+}
+
+fn is_expired(toast: &AppNotification, now: i64) -> bool {
+    let duration = match toast.severity {
+        Severity::Error => ERROR_TOAST_DURATION,
+        _ => TOAST_DURATION,
+    };
+    toast.ts.saturating_add(duration).le(&now)
+}
+
+#[cfg(feature = "web")]
+fn load_history() -> Vec<AppNotification> {
+    LocalStorage::get(HISTORY_KEY).unwrap_or_default()
+}
+
+#[cfg(feature = "desktop")]
+fn load_history() -> Vec<AppNotification> {
+    read_file(HISTORY_KEY).unwrap_or_default()
+}
+
+fn save_history(history: &[AppNotification]) {
+    #[cfg(feature = "web")]
+    LocalStorage::set(HISTORY_KEY, history).ok();
+
+    #[cfg(feature = "desktop")]
+    if let Err(err) = write_file(HISTORY_KEY, &history) {
+        log::error!("Failed to save notification history: {}", err);
+    }
+}
+
+pub fn use_notification_center(cx: &ScopeState) -> &UseSharedState<NotificationCenter> {
+    use_shared_state::<NotificationCenter>(cx).unwrap()
+}
+
+/// Provides the notification center and fills it with everything published.
+pub fn use_notification_center_provider(cx: &ScopeState) {
+    use_shared_state_provider(cx, || NotificationCenter::new(load_history()));
+    let center = use_shared_state::<NotificationCenter>(cx).unwrap();
+
+    // Receive published notifications
+    use_future(cx, (), |_| {
+        let rx = published();
+        let center = center.clone();
+        async move {
+            while let Ok(notification) = rx.recv().await {
+                center.write().push(notification);
+            }
+        }
+    });
+
+    // Hide expired toasts
+    use_future(cx, (), |_| {
+        let center = center.clone();
+        async move {
+            loop {
+                async_std::task::sleep(Duration::from_secs(1)).await;
+                let now = chrono::Utc::now().timestamp();
+                if center.read().has_expired(now) {
+                    center.write().expire(now);
+                }
+            }
+        }
+    });
+
+    // Save the history when it changed
+    use_future(cx, (), |_| {
+        let center = center.clone();
+        async move {
+            let mut saved = center.read().history.clone();
+            loop {
+                async_std::task::sleep(Duration::from_secs(SAVE_INTERVAL)).await;
+                let history = center.read().history.clone();
+                if history.ne(&saved) {
+                    save_history(&history);
+                    saved = history;
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,20 @@
+use dioxus::prelude::*;
+
+use crate::notifications::NotificationSettings;
+
+use super::use_config;
+
+pub fn use_notification_settings(cx: &ScopeState) -> &UseSharedState<NotificationSettings> {
+    let settings = use_shared_state::<NotificationSettings>(cx).unwrap();
+    let config = use_config(cx);
+    use_effect(cx, settings, |_| {
+        config.update(|c| c.notification_settings = *settings.read());
+        async move {}
+    });
+    settings
+}
+
+pub fn use_notification_settings_provider(cx: &ScopeState) {
+    let settings = use_config(cx).get().notification_settings;
+    use_shared_state_provider(cx, || settings);
+}
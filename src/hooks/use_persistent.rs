@@ -5,6 +5,7 @@ use serde::{de::DeserializeOwned, Serialize};
 
 #[cfg(feature = "desktop")]
 use crate::file::{get_value, set_key_value};
+use crate::notifications::{publish, Severity};
 
 // TODO Wrap this with a useState so all writes auto-update throughout the app
 
@@ -30,14 +31,21 @@ pub fn use_persistent<T: Serialize + DeserializeOwned + Default + 'static>(
         });
 
         #[cfg(feature = "desktop")]
-        let value = {
-            get_value(key.as_str()).ok().unwrap_or_else(|| {
+        let value = match get_value(key.as_str()) {
+            Ok(value) => value,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
                 let value = init();
                 if let Ok(v) = serde_json::to_value(&value) {
                     set_key_value(key.as_str(), &v).ok();
                 }
                 value
-            })
+            }
+            // Keep the stored value intact, so it can still be recovered
+            Err(err) => {
+                log::error!("Failed to read {}: {}", key, err);
+                publish(Severity::Error, "Failed to load data", err);
+                init()
+            }
         };
 
         StorageEntry { key, value }
@@ -60,6 +68,14 @@ pub struct UsePersistent<T: 'static> {
     inner: UseRef<StorageEntry<T>>,
 }
 
+impl<T: 'static> Clone for UsePersistent<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
 impl<T: Serialize + DeserializeOwned + Clone + 'static> UsePersistent<T> {
     /// Returns a reference to the value
     pub fn get(&self) -> T {
@@ -72,12 +88,15 @@ impl<T: Serialize + DeserializeOwned + Clone + 'static> UsePersistent<T> {
 
         // Write the new value to local storage
         #[cfg(feature = "web")]
-        LocalStorage::set(inner.key.as_str(), &value).unwrap();
+        let res = LocalStorage::set(inner.key.as_str(), &value).map_err(|e| e.to_string());
 
-        // TODO Handle desktop
         #[cfg(feature = "desktop")]
-        {
-            set_key_value(inner.key.as_str(), &value).unwrap();
+        let res = set_key_value(inner.key.as_str(), &value).map_err(|e| e.to_string());
+
+        // Keep the value in memory even if it could not be saved
+        if let Err(err) = res {
+            log::error!("Failed to save {}: {}", inner.key, err);
+            publish(Severity::Error, "Failed to save data", err);
         }
 
         inner.value = value;
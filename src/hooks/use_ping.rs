@@ -15,7 +15,7 @@ pub fn use_ping(cx: &ScopeState) -> AsyncResult<u64> {
             loop {
                 match gateway.rpc.get_slot().await {
                     Ok(slot) => ping_status.set(AsyncResult::Ok(slot)),
-                    Err(err) => ping_status.set(AsyncResult::Error(err.into())),
+                    Err(err) => ping_status.set(AsyncResult::Error(err)),
                 }
                 async_std::task::sleep(std::time::Duration::from_secs(180)).await;
             }
@@ -61,3 +61,20 @@ pub fn use_user_proof(cx: &ScopeState, authority: Pubkey) -> AsyncResult<Proof>
     });
     *proof.get()
 }
+
+/// Loads every miner's proof.
+pub fn use_proofs(cx: &ScopeState) -> AsyncResult<Vec<Proof>> {
+    let proofs = use_state::<AsyncResult<Vec<Proof>>>(cx, || AsyncResult::Loading);
+    let gateway = use_gateway(cx);
+    use_future(cx, (), |_| {
+        let proofs = proofs.clone();
+        let gateway = gateway.clone();
+        async move {
+            match gateway.get_proofs().await {
+                Ok(p) => proofs.set(AsyncResult::Ok(p)),
+                Err(err) => proofs.set(AsyncResult::Error(err)),
+            }
+        }
+    });
+    proofs.get().clone()
+}
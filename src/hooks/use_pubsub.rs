@@ -0,0 +1,20 @@
+use dioxus::prelude::*;
+
+use crate::pubsub::PubsubHandle;
+
+use super::use_rpc_url;
+
+pub fn use_pubsub(cx: &ScopeState) -> &PubsubHandle {
+    use_context::<PubsubHandle>(cx).unwrap()
+}
+
+pub fn use_pubsub_provider(cx: &ScopeState) {
+    let pubsub = use_context_provider(cx, PubsubHandle::default);
+    let rpc_url = use_rpc_url(cx).read().0.clone();
+
+    // Reconnects to the new endpoint when the RPC changes
+    use_future(cx, &rpc_url, |rpc_url| {
+        let pubsub = pubsub.clone();
+        async move { pubsub.run(rpc_url).await }
+    });
+}
@@ -0,0 +1,71 @@
+use chrono::Local;
+use dioxus::prelude::*;
+
+#[cfg(feature = "desktop")]
+use crate::miner::hash_count;
+use crate::{
+    miner::Miner,
+    miner_state::{MinerEvent, MinerState, MinerStatus},
+    notifications::{publish, Severity},
+    schedule::{StopReason, SCHEDULE_POLL_INTERVAL},
+};
+
+use super::{use_mining_schedule, use_mining_usage};
+
+/// Starts the miner when a scheduled window opens, and stops it once the window closes or a daily budget runs out.
+pub fn use_scheduled_mining(cx: &ScopeState, miner: &UseState<Miner>) {
+    let state = use_shared_state::<MinerState>(cx).unwrap();
+    let schedule = use_mining_schedule(cx);
+    let usage = use_mining_usage(cx);
+    use_future(cx, (), |_| {
+        let state = state.clone();
+        let schedule = schedule.clone();
+        let usage = usage.clone();
+        let miner = miner.clone();
+        async move {
+            // Starting within a window at launch counts as the window opening
+            let mut was_open = false;
+            #[cfg(feature = "desktop")]
+            let mut last_count = hash_count();
+            loop {
+                let schedule = *schedule.read();
+                let now = Local::now().naive_local();
+                #[cfg(feature = "desktop")]
+                {
+                    let count = hash_count();
+                    if schedule.enabled && count.gt(&last_count) {
+                        usage.write().record_hashes(now, count - last_count);
+                    }
+                    last_count = count;
+                }
+                let reason = schedule.stop_reason(now, &usage.read());
+                let is_open = schedule.enabled && reason.is_none();
+                if let Some(reason) = reason {
+                    stop_on_schedule(reason, miner.get(), &state);
+                } else if is_open
+                    && !was_open
+                    && matches!(state.read().status, MinerStatus::NotStarted)
+                {
+                    log::info!("Starting the miner on schedule");
+                    state.write().apply(MinerEvent::Start);
+                }
+                was_open = is_open;
+                async_std::task::sleep(SCHEDULE_POLL_INTERVAL).await;
+            }
+        }
+    });
+}
+
+/// Stops the miner for the schedule, unless it is already stopped.
+pub fn stop_on_schedule(reason: StopReason, miner: &Miner, state: &UseSharedState<MinerState>) {
+    if matches!(
+        state.read().status,
+        MinerStatus::NotStarted | MinerStatus::Error(_)
+    ) {
+        return;
+    }
+    log::info!("Stopping the miner: {}", reason.message());
+    miner.stop();
+    state.write().apply(MinerEvent::Stop);
+    publish(Severity::Info, "Mining stopped", reason.message());
+}
@@ -9,15 +9,18 @@ use std::time::Duration;
 #[cfg(feature = "web")]
 use web_time::Duration;
 
-use crate::gateway::{AsyncResult, GatewayError};
+use crate::{
+    gateway::AsyncResult,
+    pubsub::{Topic, Update},
+};
 
-use super::{use_gateway, use_pubkey};
+use super::{use_gateway, use_pubkey, use_pubsub};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct SolBalance(pub u64);
 
 #[derive(Clone)]
-pub struct SolBalanceHandle(UseFuture<()>);
+pub struct SolBalanceHandle(UseFuture<()>, UseFuture<()>);
 
 impl SolBalanceHandle {
     pub fn restart(&self) {
@@ -26,6 +29,9 @@ impl SolBalanceHandle {
     pub fn cancel(&self, cx: &ScopeState) {
         self.0.cancel(cx);
     }
+    pub fn refresh(&self) {
+        self.1.restart();
+    }
 }
 
 pub fn use_sol_balance(cx: &ScopeState) -> AsyncResult<SolBalance> {
@@ -40,6 +46,7 @@ pub fn use_sol_balance_provider(cx: &ScopeState) {
     let balance = use_shared_state::<AsyncResult<SolBalance>>(cx).unwrap();
     let address = use_pubkey(cx);
     let gateway = use_gateway(cx);
+    let pubsub = use_pubsub(cx);
 
     // Fetch initial balance.
     let f = use_future(cx, (), |_| {
@@ -52,9 +59,28 @@ pub fn use_sol_balance_provider(cx: &ScopeState) {
         }
     });
 
-    // Poll for future balance changes
+    // Follow balance changes over the pubsub connection
+    use_future(cx, (), |_| {
+        let balance = balance.clone();
+        let f = f.clone();
+        let rx = pubsub.subscribe(Topic::Account(address));
+        async move {
+            while let Ok(update) = rx.recv().await {
+                match update {
+                    Update::Account(account) => {
+                        *balance.write() = AsyncResult::Ok(SolBalance(account.lamports));
+                    }
+                    Update::Resubscribed => f.restart(),
+                    Update::Signature(_) => {}
+                }
+            }
+        }
+    });
+
+    // Poll for future balance changes while the connection is down
     let sub = use_future(cx, balance, |_| {
         let f = f.clone();
+        let pubsub = pubsub.clone();
         let poll = 3;
         let b = *balance.read();
         async move {
@@ -62,7 +88,9 @@ pub fn use_sol_balance_provider(cx: &ScopeState) {
                 if b.0.eq(&0) {
                     loop {
                         async_std::task::sleep(Duration::from_secs(poll)).await;
-                        f.restart();
+                        if !pubsub.is_connected() {
+                            f.restart();
+                        }
                     }
                 }
             }
@@ -70,7 +98,7 @@ pub fn use_sol_balance_provider(cx: &ScopeState) {
     });
 
     sub.cancel(cx);
-    cx.provide_context(SolBalanceHandle(sub.clone()));
+    cx.provide_context(SolBalanceHandle(sub.clone(), f.clone()));
 
     // Write balance_ changes to shared state
     let balance__ = *balance_.read().unwrap();
@@ -80,7 +108,7 @@ pub fn use_sol_balance_provider(cx: &ScopeState) {
     });
 }
 
-pub fn _use_sol_account_balance(cx: &ScopeState, address: Pubkey) -> AsyncResult<SolBalance> {
+pub fn use_sol_balance_user(cx: &ScopeState, address: Pubkey) -> AsyncResult<SolBalance> {
     let balance = use_state::<AsyncResult<SolBalance>>(cx, || AsyncResult::Loading);
     let gateway = use_gateway(cx);
 
@@ -91,7 +119,7 @@ pub fn _use_sol_account_balance(cx: &ScopeState, address: Pubkey) -> AsyncResult
             // TODO Handle error
             match gateway.rpc.get_balance(&address).await {
                 Ok(b) => balance.set(AsyncResult::Ok(SolBalance(b))),
-                Err(err) => balance.set(AsyncResult::Error(GatewayError::from(err))),
+                Err(err) => balance.set(AsyncResult::Error(err)),
             }
         }
     });
@@ -0,0 +1,63 @@
+use dioxus::prelude::*;
+
+use crate::{
+    challenge::{Challenge, Staleness, CHALLENGE_POLL_INTERVAL},
+    components::MinerStatusMessage,
+    miner::Miner,
+    miner_state::{MinerState, MinerStatus},
+    notifications::{publish, Severity},
+};
+
+use super::{use_gateway, use_pubkey};
+
+/// Restarts the search when the proof or treasury changes under it, and warns when the same
+/// keypair is mining somewhere else.
+pub fn use_stale_challenges(cx: &ScopeState, miner: &UseState<Miner>) {
+    let state = use_shared_state::<MinerState>(cx).unwrap();
+    let status_message = use_shared_state::<MinerStatusMessage>(cx).unwrap();
+    let gateway = use_gateway(cx);
+    let pubkey = use_pubkey(cx);
+    use_future(cx, (), |_| {
+        let state = state.clone();
+        let status_message = status_message.clone();
+        let gateway = gateway.clone();
+        let miner = miner.clone();
+        async move {
+            // Only a search in progress can go stale. Submissions restart it themselves.
+            let is_searching = || {
+                matches!(state.read().status, MinerStatus::Active)
+                    && matches!(*status_message.read(), MinerStatusMessage::Searching)
+            };
+            loop {
+                async_std::task::sleep(CHALLENGE_POLL_INTERVAL).await;
+                let Some(mining) = miner.get().challenge().filter(|_| is_searching()) else {
+                    continue;
+                };
+                let Ok(latest) = Challenge::fetch(&gateway, pubkey).await else {
+                    continue;
+                };
+                // The search may have moved on while fetching
+                if !is_searching() || miner.get().challenge().ne(&Some(mining)) {
+                    continue;
+                }
+                match mining.staleness(&latest, miner.get().solved()) {
+                    Staleness::Fresh => continue,
+                    Staleness::ExternalSolution => {
+                        log::warn!("Proof was updated from elsewhere");
+                        publish(
+                            Severity::Warning,
+                            "Mining from two places",
+                            "This keypair is also mining somewhere else. Both miners race for the same challenge, and pay fees for solutions that fail.",
+                        );
+                    }
+                    staleness => log::info!("Challenge is stale: {:?}", staleness),
+                }
+                miner.get().stop();
+                miner
+                    .get()
+                    .start_mining(latest.hash, latest.difficulty, pubkey)
+                    .await;
+            }
+        }
+    });
+}
@@ -0,0 +1,54 @@
+#[cfg(feature = "desktop")]
+use std::time::Duration;
+
+use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use web_time::Duration;
+
+use crate::gateway::{record_supply_samples, supply_history, SupplySample};
+
+use super::use_gateway;
+
+/// Seconds between local supply samples.
+const SAMPLE_INTERVAL: u64 = 600;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SupplyHistory(pub Vec<SupplySample>);
+
+pub fn use_supply_history(cx: &ScopeState) -> &UseSharedState<SupplyHistory> {
+    use_shared_state::<SupplyHistory>(cx).unwrap()
+}
+
+pub fn use_supply_history_provider(cx: &ScopeState) {
+    use_shared_state_provider(cx, || SupplyHistory(supply_history()));
+    let history = use_shared_state::<SupplyHistory>(cx).unwrap();
+    let gateway = use_gateway(cx);
+
+    // Backfill from the api, if it has any history
+    use_future(cx, (), |_| {
+        let history = history.clone();
+        let gateway = gateway.clone();
+        async move {
+            if let Some(samples) = gateway.get_supply_history().await {
+                *history.write() = SupplyHistory(record_supply_samples(samples));
+            }
+        }
+    });
+
+    // Sample periodically while the app is open
+    use_future(cx, (), |_| {
+        let history = history.clone();
+        let gateway = gateway.clone();
+        async move {
+            loop {
+                match gateway.get_supply_sample().await {
+                    Ok(sample) => {
+                        *history.write() = SupplyHistory(record_supply_samples(vec![sample]));
+                    }
+                    Err(err) => log::error!("Failed to sample supply: {:?}", err),
+                }
+                async_std::task::sleep(Duration::from_secs(SAMPLE_INTERVAL)).await;
+            }
+        }
+    });
+}
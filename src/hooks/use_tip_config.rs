@@ -0,0 +1,20 @@
+use dioxus::prelude::*;
+
+use crate::gateway::TipConfig;
+
+use super::use_config;
+
+pub fn use_tip_config(cx: &ScopeState) -> &UseSharedState<TipConfig> {
+    let tip_config = use_shared_state::<TipConfig>(cx).unwrap();
+    let config = use_config(cx);
+    use_effect(cx, tip_config, |_| {
+        config.update(|c| c.tip_config = *tip_config.read());
+        async move {}
+    });
+    tip_config
+}
+
+pub fn use_tip_config_provider(cx: &ScopeState) {
+    let tip_config = use_config(cx).get().tip_config;
+    use_shared_state_provider(cx, || tip_config);
+}
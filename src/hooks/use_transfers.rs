@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use dioxus::prelude::*;
 use dioxus_std::utils::rw::use_rw;
 use ore_types::Transfer;
@@ -6,12 +8,28 @@ use solana_client_wasm::solana_sdk::pubkey::Pubkey;
 #[cfg(feature = "desktop")]
 use solana_sdk::pubkey::Pubkey;
 
-use crate::{components::ActivityFilter, gateway::AsyncResult};
+use crate::{
+    components::ActivityFilter,
+    gateway::{filter_transfers, merge_transfers, AsyncResult, TransferQuery},
+};
 
-use super::{use_gateway, use_pubkey, use_transfers_websocket};
+use super::{use_gateway, use_pubkey, use_transfers_channel, use_transfers_websocket};
 
 pub const ACTIVITY_TABLE_PAGE_LIMIT: usize = 8;
 
+/// Transfers kept in memory for client-side filtering when the api is unavailable.
+const TRANSFER_CACHE_LIMIT: usize = 1_000;
+
+/// Pages fetched in a row while looking for a match, for apis that ignore the query filters.
+const MAX_EMPTY_PAGES: usize = 5;
+
+/// Wait for typing to settle before searching. A newer query cancels the wait.
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// Transfers loaded per page, and pages loaded, for a user's transfer history.
+const USER_HISTORY_PAGE_LIMIT: usize = 100;
+const USER_HISTORY_MAX_PAGES: usize = 5;
+
 pub fn use_transfer(cx: &ScopeState, sig: String) -> AsyncResult<Transfer> {
     let gateway = use_gateway(cx);
     let transfer = use_state(cx, || AsyncResult::Loading);
@@ -45,7 +63,12 @@ pub fn use_user_transfers(
         let offset = *offset.current();
         async move {
             if let Some(res) = gateway
-                .list_transfers(Some(user_id), offset, ACTIVITY_TABLE_PAGE_LIMIT)
+                .list_transfers(
+                    &TransferQuery::user(Some(user_id)),
+                    None,
+                    offset,
+                    ACTIVITY_TABLE_PAGE_LIMIT,
+                )
                 .await
             {
                 transfers.write(AsyncResult::Ok(res.data)).unwrap();
@@ -57,6 +80,39 @@ pub fn use_user_transfers(
     (transfers.read().unwrap().clone(), *has_more.get())
 }
 
+/// Loads a user's most recent transfers, newest first.
+pub fn use_user_transfer_history(cx: &ScopeState, user_id: Pubkey) -> AsyncResult<Vec<Transfer>> {
+    let gateway = use_gateway(cx);
+    let transfers = use_state::<AsyncResult<Vec<Transfer>>>(cx, || AsyncResult::Loading);
+
+    let _ = use_future(cx, (), |_| {
+        let gateway = gateway.clone();
+        let transfers = transfers.clone();
+        async move {
+            let query = TransferQuery::user(Some(user_id));
+            let mut data: Vec<Transfer> = vec![];
+            for page in 0..USER_HISTORY_MAX_PAGES {
+                let cursor = data.last().map(|t| t.sig.clone());
+                let offset = (page * USER_HISTORY_PAGE_LIMIT) as u64;
+                let Some(res) = gateway
+                    .list_transfers(&query, cursor.as_deref(), offset, USER_HISTORY_PAGE_LIMIT)
+                    .await
+                else {
+                    break;
+                };
+                data.extend(res.data.into_iter().filter(|t| query.matches(t)));
+                if !res.has_more {
+                    break;
+                }
+            }
+            data.sort_by(|a, b| b.ts.cmp(&a.ts));
+            transfers.set(AsyncResult::Ok(data));
+        }
+    });
+
+    transfers.get().clone()
+}
+
 pub fn use_transfers(
     cx: &ScopeState,
     filter: &UseState<ActivityFilter>,
@@ -87,7 +143,12 @@ pub fn use_transfers(
         };
         async move {
             if let Some(res) = gateway
-                .list_transfers(user, offset, ACTIVITY_TABLE_PAGE_LIMIT)
+                .list_transfers(
+                    &TransferQuery::user(user),
+                    None,
+                    offset,
+                    ACTIVITY_TABLE_PAGE_LIMIT,
+                )
                 .await
             {
                 transfers.write(AsyncResult::Ok(res.data)).unwrap();
@@ -98,3 +159,153 @@ pub fn use_transfers(
 
     (transfers.read().unwrap().clone(), *has_more.get())
 }
+
+/// Position in the api's pages. Counted apart from the results, which also receive live
+/// transfers from the websocket.
+#[derive(Default)]
+struct FetchedPages {
+    count: u64,
+    cursor: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct TransferSearch {
+    transfers: UseState<AsyncResult<Vec<Transfer>>>,
+    has_more: UseState<bool>,
+    is_loading: UseState<bool>,
+    page: UseState<u64>,
+}
+
+impl TransferSearch {
+    pub fn transfers(&self) -> AsyncResult<Vec<Transfer>> {
+        self.transfers.current().as_ref().clone()
+    }
+
+    pub fn has_more(&self) -> bool {
+        *self.has_more.current()
+    }
+
+    pub fn is_loading(&self) -> bool {
+        *self.is_loading.current()
+    }
+
+    pub fn load_more(&self) {
+        if self.has_more() && !self.is_loading() {
+            self.page.modify(|p| p.saturating_add(1));
+        }
+    }
+}
+
+/// Lists transfers matching the query, a page at a time, with live updates from the websocket.
+pub fn use_transfer_search(cx: &ScopeState, query: &UseState<TransferQuery>) -> TransferSearch {
+    let gateway = use_gateway(cx);
+    let transfers = use_state(cx, || AsyncResult::Loading);
+    let has_more = use_state(cx, || false);
+    let is_loading = use_state(cx, || false);
+    let page = use_state(cx, || 0u64);
+    let last_query = use_ref::<Option<TransferQuery>>(cx, || None);
+    let results = use_ref(cx, Vec::<Transfer>::new);
+    let fetched = use_ref(cx, FetchedPages::default);
+    let cache = use_ref(cx, Vec::<Transfer>::new);
+    let ch = use_transfers_channel(cx);
+
+    let _ = use_future(cx, (&query.clone(), &page.clone()), |_| {
+        let gateway = gateway.clone();
+        let transfers = transfers.clone();
+        let has_more = has_more.clone();
+        let is_loading = is_loading.clone();
+        let page = page.clone();
+        let last_query = last_query.clone();
+        let results = results.clone();
+        let fetched = fetched.clone();
+        let cache = cache.clone();
+        let query = query.current().as_ref().clone();
+        async move {
+            // Start over from the first page when the query changes
+            if last_query.read().as_ref().ne(&Some(&query)) {
+                if last_query.read().is_some() {
+                    async_std::task::sleep(SEARCH_DEBOUNCE).await;
+                }
+                *last_query.write() = Some(query.clone());
+                results.write().clear();
+                *fetched.write() = FetchedPages::default();
+                if !cache.read().is_empty() {
+                    transfers.set(AsyncResult::Ok(filter_transfers(&query, &cache.read())));
+                }
+                if (*page.current()).ne(&0) {
+                    page.set(0);
+                    return;
+                }
+            }
+
+            is_loading.set(true);
+            let matches = filter_transfers(&query, &results.read()).len();
+            for _ in 0..MAX_EMPTY_PAGES {
+                let (cursor, offset) = {
+                    let fetched = fetched.read();
+                    (fetched.cursor.clone(), fetched.count)
+                };
+                match gateway
+                    .list_transfers(&query, cursor.as_deref(), offset, ACTIVITY_TABLE_PAGE_LIMIT)
+                    .await
+                {
+                    Some(res) => {
+                        fetched.with_mut(|f| {
+                            f.count = f.count.saturating_add(res.data.len() as u64);
+                            if let Some(last) = res.data.last() {
+                                f.cursor = Some(last.sig.clone());
+                            }
+                        });
+                        merge_transfers(&mut results.write(), res.data.clone(), usize::MAX);
+                        merge_transfers(&mut cache.write(), res.data, TRANSFER_CACHE_LIMIT);
+                        has_more.set(res.has_more);
+                        let filtered = filter_transfers(&query, &results.read());
+                        let found = filtered.len().gt(&matches);
+                        transfers.set(AsyncResult::Ok(filtered));
+                        if found || !res.has_more {
+                            break;
+                        }
+                    }
+                    None => {
+                        // Fall back to filtering the pages already fetched
+                        has_more.set(false);
+                        transfers.set(AsyncResult::Ok(filter_transfers(&query, &cache.read())));
+                        break;
+                    }
+                }
+            }
+            is_loading.set(false);
+        }
+    });
+
+    let _ = use_future(cx, (), |_| {
+        let mut rx = ch.receiver();
+        let query = query.clone();
+        let transfers = transfers.clone();
+        let results = results.clone();
+        let cache = cache.clone();
+        async move {
+            while let Ok(transfer) = rx.recv().await {
+                merge_transfers(
+                    &mut cache.write(),
+                    vec![transfer.clone()],
+                    TRANSFER_CACHE_LIMIT,
+                );
+                let query = query.current();
+                if query.matches(&transfer)
+                    && !results.read().iter().any(|t| t.sig.eq(&transfer.sig))
+                {
+                    results.write().insert(0, transfer);
+                    transfers.set(AsyncResult::Ok(filter_transfers(&query, &results.read())));
+                }
+            }
+        }
+    });
+
+    TransferSearch {
+        transfers: transfers.clone(),
+        has_more: has_more.clone(),
+        is_loading: is_loading.clone(),
+        page: page.clone(),
+    }
+}
@@ -1,7 +1,10 @@
 #[cfg(feature = "desktop")]
 use async_tungstenite::async_std::connect_async;
 use dioxus::prelude::*;
-use dioxus_std::utils::{channel::use_channel, rw::UseRw};
+use dioxus_std::utils::{
+    channel::{use_channel, UseChannel},
+    rw::UseRw,
+};
 use futures::StreamExt;
 #[cfg(feature = "web")]
 use gloo::net::websocket::{futures::WebSocket, Message, WebSocketError};
@@ -12,15 +15,22 @@ use url::Url;
 #[cfg(feature = "web")]
 use wasm_bindgen_futures::spawn_local;
 
-use crate::{components::ActivityFilter, gateway::AsyncResult};
+use crate::{
+    components::ActivityFilter,
+    gateway::AsyncResult,
+    notifications::{publish, Severity},
+};
 
 use super::use_pubkey;
 
 const URL: &str = "wss://mars-websockets.onrender.com/ws";
 
+/// Transfers buffered for listeners that fall behind.
+const TRANSFERS_CHANNEL_SIZE: usize = 16;
+
 // TODO Attempt reconnect if connection is lost
 
-/// Spawn a coroutine with a websocket connection
+/// Insert live transfers into the first page of results
 pub fn use_transfers_websocket(
     cx: &ScopeState,
     filter: &UseState<ActivityFilter>,
@@ -30,7 +40,7 @@ pub fn use_transfers_websocket(
     limit: usize,
 ) {
     let pubkey = use_pubkey(cx);
-    let ch = use_channel::<Transfer>(cx, 1);
+    let ch = use_transfers_channel(cx);
 
     let _ = use_future(cx, (), |_| {
         let mut rx = ch.clone().receiver();
@@ -66,6 +76,18 @@ pub fn use_transfers_websocket(
             }
         }
     });
+}
+
+/// The shared channel of live transfers.
+pub fn use_transfers_channel(cx: &ScopeState) -> UseChannel<Transfer> {
+    use_context::<UseChannel<Transfer>>(cx).unwrap().clone()
+}
+
+/// Opens the one websocket connection to the transfers feed and broadcasts every transfer it
+/// receives to all listeners.
+pub fn use_transfers_channel_provider(cx: &ScopeState) {
+    let ch = use_channel::<Transfer>(cx, TRANSFERS_CHANNEL_SIZE);
+    use_context_provider(cx, || ch.clone());
 
     // TODO Support desktop
     #[cfg(feature = "desktop")]
@@ -73,9 +95,14 @@ pub fn use_transfers_websocket(
         let ch = ch.clone();
         async move {
             let url = Url::parse(URL).expect("Invalid WebSocket URL");
-            let (mut ws, _) = connect_async(url)
-                .await
-                .expect("Failed to connect to websocket server");
+            let mut ws = match connect_async(url).await {
+                Ok((ws, _)) => ws,
+                Err(err) => {
+                    log::error!("Failed to connect to websocket server: {:?}", err);
+                    publish(Severity::Warning, "Live activity unavailable", err);
+                    return;
+                }
+            };
 
             async_std::task::spawn({
                 async move {
@@ -88,7 +115,12 @@ pub fn use_transfers_websocket(
                                             ch.send(transfer).await.ok();
                                         }
                                         Err(err) => {
-                                            log::error!("Failed to parse transfer: {:?}", err)
+                                            log::error!("Failed to parse transfer: {:?}", err);
+                                            publish(
+                                                Severity::Warning,
+                                                "Failed to parse transfer",
+                                                err,
+                                            );
                                         }
                                     }
                                 }
@@ -100,6 +132,7 @@ pub fn use_transfers_websocket(
                             },
                             Err(e) => {
                                 log::error!("Error during receiving a message: {}", e);
+                                publish(Severity::Warning, "Live activity disconnected", e);
                                 break;
                             }
                         }
@@ -123,14 +156,18 @@ pub fn use_transfers_websocket(
                                 ch.send(transfer).await.ok();
                             }
                             Err(err) => {
-                                log::error!("Failed to parse transfer: {:?}", err)
+                                log::error!("Failed to parse transfer: {:?}", err);
+                                publish(Severity::Warning, "Failed to parse transfer", err);
                             }
                         },
                         Ok(Message::Bytes(_)) => {}
                         Err(WebSocketError::ConnectionClose(event)) => {
                             log::info!("[WebSocket]: {:#?}", event);
                         }
-                        Err(err) => log::error!("[WebSocket]: {:#?}", err),
+                        Err(err) => {
+                            log::error!("[WebSocket]: {:#?}", err);
+                            publish(Severity::Warning, "Live activity disconnected", err);
+                        }
                     }
                 }
             });
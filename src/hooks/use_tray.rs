@@ -0,0 +1,208 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use dioxus::prelude::*;
+use dioxus_desktop::{
+    tao::{
+        event::{Event, TrayEvent, WindowEvent},
+        event_loop::EventLoopWindowTarget,
+        menu::{ContextMenu, CustomMenuItem, MenuItem, MenuItemAttributes, MenuType},
+        system_tray::{SystemTray, SystemTrayBuilder},
+        window::Icon,
+    },
+    use_window, use_wry_event_handler, DesktopContext,
+};
+
+use crate::{
+    components::{format_value, IsToolbarOpen},
+    gateway::AsyncResult,
+    hooks::SolBalance,
+    metrics::{track, AppEvent},
+    miner::{hash_count, Miner},
+    miner_state::{MinerEvent, MinerState, MinerStatus},
+    notifications::NotificationSettings,
+};
+
+const ICON: &[u8] = include_bytes!("../../public/icon.png");
+
+/// Seconds between hashrate samples.
+const REFRESH_INTERVAL: u64 = 5;
+
+/// Whether a tray icon is up to bring a hidden window back.
+static HAS_TRAY: AtomicBool = AtomicBool::new(false);
+
+struct Tray {
+    tray: SystemTray,
+    status: CustomMenuItem,
+    start: CustomMenuItem,
+    stop: CustomMenuItem,
+    open: CustomMenuItem,
+    quit: CustomMenuItem,
+}
+
+impl Tray {
+    fn build<T: 'static>(target: &EventLoopWindowTarget<T>) -> Option<Self> {
+        let mut menu = ContextMenu::new();
+        let status = menu.add_item(MenuItemAttributes::new("Stopped").with_enabled(false));
+        menu.add_native_item(MenuItem::Separator);
+        let start = menu.add_item(MenuItemAttributes::new("Start mining"));
+        let stop = menu.add_item(MenuItemAttributes::new("Stop mining").with_enabled(false));
+        menu.add_native_item(MenuItem::Separator);
+        let open = menu.add_item(MenuItemAttributes::new("Open Mars"));
+        let quit = menu.add_item(MenuItemAttributes::new("Quit"));
+        let tray = SystemTrayBuilder::new(icon()?, Some(menu))
+            .with_tooltip("Mars")
+            .build(target)
+            .map_err(|err| log::error!("Failed to create tray icon: {:?}", err))
+            .ok()?;
+        HAS_TRAY.store(true, Ordering::Relaxed);
+        Some(Self {
+            tray,
+            status,
+            start,
+            stop,
+            open,
+            quit,
+        })
+    }
+
+    fn update(&mut self, status: &str, is_mining: bool) {
+        self.tray.set_tooltip(&format!("Mars · {}", status));
+        self.status.set_title(status);
+        self.start.set_enabled(!is_mining);
+        self.stop.set_enabled(is_mining);
+    }
+}
+
+impl Drop for Tray {
+    fn drop(&mut self) {
+        HAS_TRAY.store(false, Ordering::Relaxed);
+    }
+}
+
+fn icon() -> Option<Icon> {
+    let mut reader = png::Decoder::new(ICON).read_info().ok()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).ok()?;
+    buf.truncate(info.buffer_size());
+    Icon::from_rgba(buf, info.width, info.height).ok()
+}
+
+fn show(window: &DesktopContext) {
+    window.set_visible(true);
+    window.set_focus();
+}
+
+/// Closing the window hides it while the tray is up and minimizing to it is enabled. Otherwise
+/// the app quits. Read on every close, so toggling the setting applies right away.
+pub fn use_close_behaviour(cx: &ScopeState) {
+    let settings = use_shared_state::<NotificationSettings>(cx)
+        .unwrap()
+        .clone();
+    use_wry_event_handler(cx, move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::CloseRequested,
+            ..
+        } = event
+        {
+            if !(settings.read().minimize_to_tray && HAS_TRAY.load(Ordering::Relaxed)) {
+                std::process::exit(0);
+            }
+        }
+    });
+}
+
+/// Shows the miner's state and hashrate in the system tray, with a menu to start and stop it.
+pub fn use_tray(cx: &ScopeState, miner: &UseState<Miner>) {
+    let window = use_window(cx);
+    let state = use_shared_state::<MinerState>(cx).unwrap();
+    let is_toolbar_open = use_shared_state::<IsToolbarOpen>(cx).unwrap();
+    let sol_balance = use_shared_state::<AsyncResult<SolBalance>>(cx).unwrap();
+    let tray = cx.use_hook(|| Rc::new(RefCell::new(None::<Tray>)));
+    let is_built = cx.use_hook(|| Rc::new(Cell::new(false)));
+
+    // Refresh the status line
+    use_future(cx, (), |_| {
+        let tray = tray.clone();
+        let state = state.clone();
+        async move {
+            let mut last_count = hash_count();
+            loop {
+                async_std::task::sleep(Duration::from_secs(REFRESH_INTERVAL)).await;
+                let count = hash_count();
+                let hashrate = count.saturating_sub(last_count) as f64 / REFRESH_INTERVAL as f64;
+                last_count = count;
+                let (label, is_mining) = match state.read().status {
+                    MinerStatus::Active => {
+                        (format!("Mining · {}H/s", format_value(hashrate)), true)
+                    }
+                    MinerStatus::Activating => ("Starting".to_string(), true),
+                    MinerStatus::Paused => ("Paused".to_string(), true),
+                    MinerStatus::Retrying(_) => ("Retrying".to_string(), true),
+                    MinerStatus::Error(_) => ("Error".to_string(), false),
+                    MinerStatus::NotStarted => ("Stopped".to_string(), false),
+                };
+                if let Some(tray) = tray.borrow_mut().as_mut() {
+                    tray.update(&label, is_mining);
+                }
+            }
+        }
+    });
+
+    // The tray can only be built from inside the event loop
+    let window = window.clone();
+    let miner = miner.clone();
+    let state = state.clone();
+    let is_toolbar_open = is_toolbar_open.clone();
+    let sol_balance = sol_balance.clone();
+    let tray = tray.clone();
+    let is_built = is_built.clone();
+    use_wry_event_handler(cx, move |event, target| {
+        let mut tray = tray.borrow_mut();
+        if !is_built.replace(true) {
+            *tray = Tray::build(target);
+        }
+        let Some(tray) = tray.as_mut() else {
+            return;
+        };
+        match event {
+            Event::MenuEvent {
+                menu_id,
+                origin: MenuType::ContextMenu,
+                ..
+            } => {
+                if menu_id.eq(&tray.start.id()) {
+                    // Starts like the toolbar does once funded. Without any SOL, the toolbar is
+                    // opened to ask for a top up instead.
+                    let is_funded =
+                        matches!(*sol_balance.read(), AsyncResult::Ok(SolBalance(b)) if b.gt(&0));
+                    if is_funded {
+                        state.write().apply(MinerEvent::Start);
+                    } else {
+                        *is_toolbar_open.write() = IsToolbarOpen(true);
+                        show(&window);
+                    }
+                } else if menu_id.eq(&tray.stop.id()) {
+                    track(AppEvent::StopMiner, None);
+                    miner.get().stop();
+                    state.write().apply(MinerEvent::Stop);
+                    *is_toolbar_open.write() = IsToolbarOpen(false);
+                    tray.update("Stopped", false);
+                } else if menu_id.eq(&tray.open.id()) {
+                    show(&window);
+                } else if menu_id.eq(&tray.quit.id()) {
+                    std::process::exit(0);
+                }
+            }
+            Event::TrayEvent {
+                event: TrayEvent::LeftClick,
+                ..
+            } => show(&window),
+            _ => {}
+        }
+    });
+}
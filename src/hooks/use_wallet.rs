@@ -0,0 +1,55 @@
+use dioxus::prelude::*;
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::signature::Signer;
+#[cfg(feature = "desktop")]
+use solana_sdk::signature::Signer;
+
+use crate::wallet::{load_wallet, WalletError};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Wallet {
+    Ready {
+        /// Base58 private key.
+        keypair: String,
+        pubkey: String,
+        is_backed_up: bool,
+    },
+    /// The app is gated behind wallet setup or recovery until this is resolved.
+    Unavailable(WalletError),
+}
+
+impl Wallet {
+    pub fn load() -> Self {
+        match load_wallet() {
+            Ok((keypair, record)) => Wallet::Ready {
+                keypair: keypair.to_base58_string(),
+                pubkey: keypair.pubkey().to_string(),
+                is_backed_up: record.is_backed_up,
+            },
+            Err(err) => {
+                if let WalletError::Storage(err) = &err {
+                    log::error!("{}", err);
+                }
+                Wallet::Unavailable(err)
+            }
+        }
+    }
+
+    pub fn is_backed_up(&self) -> bool {
+        matches!(
+            self,
+            Wallet::Ready {
+                is_backed_up: true,
+                ..
+            }
+        )
+    }
+}
+
+pub fn use_wallet(cx: &ScopeState) -> &UseSharedState<Wallet> {
+    use_shared_state::<Wallet>(cx).unwrap()
+}
+
+pub fn use_wallet_provider(cx: &ScopeState) {
+    use_shared_state_provider(cx, Wallet::load);
+}
@@ -0,0 +1,197 @@
+#[cfg(feature = "desktop")]
+use std::time::Duration;
+use std::{collections::HashSet, str::FromStr};
+
+use dioxus::prelude::*;
+use ore_types::{Transfer, TransferType};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "desktop")]
+use solana_sdk::pubkey::Pubkey;
+#[cfg(feature = "web")]
+use web_time::Duration;
+
+use crate::notifications::{notify, notify_event, NotificationEvent};
+
+use super::{
+    use_config, use_gateway, use_notification_settings, use_pubkey, use_transfers_channel,
+};
+
+/// Seconds between checks of watched proofs.
+const PROOF_POLL_INTERVAL: u64 = 60;
+
+/// Incoming transfers kept for the watchlist dashboard.
+const WATCH_ACTIVITY_LIMIT: usize = 50;
+
+/// A watch-only address. No secret key is held for it.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct WatchEntry {
+    pub address: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Notify once claimable rewards reach this amount, in the token's smallest unit.
+    #[serde(default)]
+    pub claimable_threshold: Option<u64>,
+}
+
+impl WatchEntry {
+    pub fn pubkey(&self) -> Option<Pubkey> {
+        Pubkey::from_str(&self.address).ok()
+    }
+
+    /// Label if set, otherwise the shortened address.
+    pub fn name(&self) -> String {
+        match &self.label {
+            Some(label) => label.clone(),
+            None => {
+                let len = self.address.len();
+                if len.gt(&8) {
+                    format!("{}…{}", &self.address[..4], &self.address[len - 4..])
+                } else {
+                    self.address.clone()
+                }
+            }
+        }
+    }
+}
+
+/// Addresses the user follows.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Watchlist(pub Vec<WatchEntry>);
+
+impl Watchlist {
+    pub fn contains(&self, address: &Pubkey) -> bool {
+        self.get(&address.to_string()).is_some()
+    }
+
+    pub fn get(&self, address: &str) -> Option<&WatchEntry> {
+        self.0.iter().find(|e| e.address.eq(address))
+    }
+
+    /// Adds the entry, or replaces the one with the same address.
+    pub fn insert(&mut self, entry: WatchEntry) {
+        match self.0.iter_mut().find(|e| e.address.eq(&entry.address)) {
+            Some(e) => *e = entry,
+            None => self.0.push(entry),
+        }
+    }
+
+    pub fn remove(&mut self, address: &str) {
+        self.0.retain(|e| e.address.ne(address));
+    }
+
+    pub fn toggle(&mut self, address: &Pubkey) {
+        let address = address.to_string();
+        if self.get(&address).is_some() {
+            self.remove(&address);
+        } else {
+            self.insert(WatchEntry {
+                address,
+                ..Default::default()
+            });
+        }
+    }
+}
+
+/// Recent transfers received by watched addresses, newest first.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WatchActivity(pub Vec<Transfer>);
+
+pub fn use_watchlist(cx: &ScopeState) -> &UseSharedState<Watchlist> {
+    let watchlist = use_shared_state::<Watchlist>(cx).unwrap();
+    let config = use_config(cx);
+    use_effect(cx, watchlist, |_| {
+        config.update(|c| c.watchlist = watchlist.read().clone());
+        async move {}
+    });
+    watchlist
+}
+
+pub fn use_watch_activity(cx: &ScopeState) -> &UseSharedState<WatchActivity> {
+    use_shared_state::<WatchActivity>(cx).unwrap()
+}
+
+/// Provides the watchlist and monitors its addresses for incoming funds and claimable rewards.
+/// Incoming funds to the user's own address are notified here too.
+pub fn use_watchlist_provider(cx: &ScopeState) {
+    let watchlist = use_config(cx).get().watchlist;
+    use_shared_state_provider(cx, || watchlist);
+    use_shared_state_provider(cx, WatchActivity::default);
+    let watchlist = use_shared_state::<Watchlist>(cx).unwrap();
+    let activity = use_shared_state::<WatchActivity>(cx).unwrap();
+    let gateway = use_gateway(cx);
+    let pubkey = use_pubkey(cx);
+    let notification_settings = use_notification_settings(cx);
+    let ch = use_transfers_channel(cx);
+
+    // Incoming transfers
+    use_future(cx, (), |_| {
+        let mut rx = ch.clone().receiver();
+        let watchlist = watchlist.clone();
+        let activity = activity.clone();
+        let notification_settings = notification_settings.clone();
+        async move {
+            while let Ok(transfer) = rx.recv().await {
+                if matches!(transfer.transfer_type, TransferType::Mine) {
+                    continue;
+                }
+                let amount = (transfer.amount as f64) / 10f64.powf(mars::TOKEN_DECIMALS as f64);
+                if transfer.to_address.eq(&pubkey.to_string()) {
+                    notify_event(
+                        &notification_settings.read(),
+                        NotificationEvent::IncomingTransfer,
+                        &format!("You received {} MARS", amount),
+                    );
+                }
+                let Some(entry) = watchlist.read().get(&transfer.to_address).cloned() else {
+                    continue;
+                };
+                notify_event(
+                    &notification_settings.read(),
+                    NotificationEvent::IncomingTransfer,
+                    &format!("{} received {} MARS", entry.name(), amount),
+                );
+                let mut activity = activity.write();
+                activity.0.insert(0, transfer);
+                activity.0.truncate(WATCH_ACTIVITY_LIMIT);
+            }
+        }
+    });
+
+    // Claimable rewards thresholds
+    use_future(cx, (), |_| {
+        let watchlist = watchlist.clone();
+        let gateway = gateway.clone();
+        async move {
+            // Addresses already above their threshold, so each crossing notifies once
+            let mut above: HashSet<String> = HashSet::new();
+            loop {
+                let entries = watchlist.read().0.clone();
+                for entry in entries {
+                    let (Some(threshold), Some(pubkey)) =
+                        (entry.claimable_threshold, entry.pubkey())
+                    else {
+                        continue;
+                    };
+                    let Ok(proof) = gateway.get_proof(pubkey).await else {
+                        continue;
+                    };
+                    if proof.claimable_rewards.ge(&threshold) {
+                        if above.insert(entry.address.clone()) {
+                            let amount = (proof.claimable_rewards as f64)
+                                / 10f64.powf(mars::TOKEN_DECIMALS as f64);
+                            notify(
+                                "Rewards ready to claim",
+                                &format!("{} has {} MARS to claim", entry.name(), amount),
+                            );
+                        }
+                    } else {
+                        above.remove(&entry.address);
+                    }
+                }
+                async_std::task::sleep(Duration::from_secs(PROOF_POLL_INTERVAL)).await;
+            }
+        }
+    });
+}
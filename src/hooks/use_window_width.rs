@@ -1,30 +1,69 @@
-// use dioxus::prelude::*;
-// use wasm_bindgen::prelude::*;
-
-// #[allow(dead_code)]
-// pub struct WindowWidth(i32);
-
-// pub fn _use_window_width(cx: &ScopeState) -> i32 {
-//     use_shared_state::<WindowWidth>(cx).unwrap().read().0
-// }
-
-// pub fn _track_window_width(cx: &ScopeState) {
-//     use_shared_state_provider(cx, || WindowWidth(0));
-//     let window_width = use_shared_state::<WindowWidth>(cx).unwrap();
-
-//     use_effect(cx, (), |_| {
-//         let window_width = window_width.clone();
-//         let closure = Closure::wrap(Box::new(move |width: i32| {
-//             *window_width.write() = WindowWidth(width);
-//         }) as Box<dyn FnMut(i32)>);
-//         setup_resize_listener(&closure);
-//         closure.forget();
-//         async move {}
-//     });
-// }
-
-// #[wasm_bindgen]
-// extern "C" {
-//     #[wasm_bindgen(js_name = setupResizeListener)]
-//     fn setup_resize_listener(closure: &Closure<dyn FnMut(i32)>);
-// }
+use dioxus::prelude::*;
+
+/// Widest the page content gets, in css pixels.
+const MAX_CONTENT_WIDTH: f64 = 1536.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WindowWidth(pub f64);
+
+impl WindowWidth {
+    /// Width available to page content, after the navbar's max width and padding.
+    pub fn content_width(&self) -> f64 {
+        let padding = if self.0.lt(&640.0) { 32.0 } else { 64.0 };
+        (self.0.min(MAX_CONTENT_WIDTH) - padding).max(0.0)
+    }
+}
+
+pub fn use_window_width(cx: &ScopeState) -> WindowWidth {
+    *use_shared_state::<WindowWidth>(cx).unwrap().read()
+}
+
+#[cfg(feature = "web")]
+pub fn use_window_width_provider(cx: &ScopeState) {
+    use gloo::events::EventListener;
+
+    let inner_width = || {
+        gloo::utils::window()
+            .inner_width()
+            .ok()
+            .and_then(|w| w.as_f64())
+            .unwrap_or(0.0)
+    };
+    use_shared_state_provider(cx, || WindowWidth(inner_width()));
+    let window_width = use_shared_state::<WindowWidth>(cx).unwrap();
+    use_effect(cx, (), |_| {
+        let window_width = window_width.clone();
+        EventListener::new(&gloo::utils::window(), "resize", move |_| {
+            *window_width.write() = WindowWidth(inner_width());
+        })
+        .forget();
+        async move {}
+    });
+}
+
+#[cfg(feature = "desktop")]
+pub fn use_window_width_provider(cx: &ScopeState) {
+    use dioxus_desktop::{
+        tao::event::{Event, WindowEvent},
+        use_window, use_wry_event_handler,
+    };
+
+    let window = use_window(cx);
+    use_shared_state_provider(cx, || {
+        let size = window.inner_size().to_logical::<f64>(window.scale_factor());
+        WindowWidth(size.width)
+    });
+    let window_width = use_shared_state::<WindowWidth>(cx).unwrap();
+    let window = window.clone();
+    let window_width = window_width.clone();
+    use_wry_event_handler(cx, move |event, _| {
+        if let Event::WindowEvent {
+            event: WindowEvent::Resized(size),
+            ..
+        } = event
+        {
+            let size = size.to_logical::<f64>(window.scale_factor());
+            *window_width.write() = WindowWidth(size.width);
+        }
+    });
+}
@@ -0,0 +1,117 @@
+use std::{fs, process::Command, ptr, time::Duration};
+
+use x11_dl::{xlib::Xlib, xss::Xss};
+
+/// Time since the last keyboard or mouse input. Read from Mutter on Wayland, and from the
+/// screen saver extension on X11. Other Wayland compositors don't share it.
+pub fn input_idle_time() -> Option<Duration> {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        return mutter_idle_time();
+    }
+    x11_idle_time()
+}
+
+fn mutter_idle_time() -> Option<Duration> {
+    let output = Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.gnome.Mutter.IdleMonitor",
+            "--object-path",
+            "/org/gnome/Mutter/IdleMonitor/Core",
+            "--method",
+            "org.gnome.Mutter.IdleMonitor.GetIdletime",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_gdbus_uint64(&String::from_utf8_lossy(&output.stdout)).map(Duration::from_millis)
+}
+
+/// Parses a single return value printed by gdbus, such as `(uint64 1234,)`.
+pub fn parse_gdbus_uint64(output: &str) -> Option<u64> {
+    output
+        .trim()
+        .strip_prefix("(uint64 ")?
+        .strip_suffix(",)")?
+        .parse()
+        .ok()
+}
+
+// The idle time is a c_ulong, which is only 32 bits on some targets
+#[allow(clippy::useless_conversion)]
+fn x11_idle_time() -> Option<Duration> {
+    std::env::var_os("DISPLAY")?;
+    let xlib = Xlib::open().ok()?;
+    let xss = Xss::open().ok()?;
+    // Safety: the display and info are checked for null, and freed before returning
+    unsafe {
+        let display = (xlib.XOpenDisplay)(ptr::null());
+        if display.is_null() {
+            return None;
+        }
+        let info = (xss.XScreenSaverAllocInfo)();
+        let mut idle = None;
+        if !info.is_null() {
+            let root = (xlib.XDefaultRootWindow)(display);
+            if (xss.XScreenSaverQueryInfo)(display, root, info).ne(&0) {
+                idle = Some(Duration::from_millis(u64::from((*info).idle)));
+            }
+            (xlib.XFree)(info.cast());
+        }
+        (xlib.XCloseDisplay)(display);
+        idle
+    }
+}
+
+/// Cpu time spent by the whole machine and by this process, in clock ticks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CpuSample {
+    busy: u64,
+    total: u64,
+    own: u64,
+}
+
+impl CpuSample {
+    pub fn read() -> Option<Self> {
+        let stat = fs::read_to_string("/proc/stat").ok()?;
+        let own_stat = fs::read_to_string("/proc/self/stat").ok()?;
+        Self::parse(&stat, &own_stat)
+    }
+
+    pub fn parse(stat: &str, own_stat: &str) -> Option<Self> {
+        let times = stat.lines().next()?.strip_prefix("cpu ")?;
+        let times: Vec<u64> = times
+            .split_whitespace()
+            .map(|t| t.parse().ok())
+            .collect::<Option<_>>()?;
+        // user, nice, system, idle, iowait, irq, softirq and steal. Guest time is already in user.
+        let total = times.iter().take(8).sum();
+        let idle = times.get(3)? + times.get(4).unwrap_or(&0);
+
+        // The command name may contain spaces, so fields are counted after it. utime and stime
+        // are fields 14 and 15, while the first one after the name is field 3.
+        let fields: Vec<&str> = own_stat.rsplit_once(')')?.1.split_whitespace().collect();
+        let utime: u64 = fields.get(11)?.parse().ok()?;
+        let stime: u64 = fields.get(12)?.parse().ok()?;
+        Some(Self {
+            busy: total - idle,
+            total,
+            own: utime + stime,
+        })
+    }
+
+    /// Share of the cpu used by other processes since an earlier sample, from 0 to 1.
+    pub fn load_since(&self, earlier: &Self) -> Option<f32> {
+        let total = self.total.checked_sub(earlier.total)?;
+        if total.eq(&0) {
+            return None;
+        }
+        let busy = self.busy.saturating_sub(earlier.busy);
+        let own = self.own.saturating_sub(earlier.own);
+        Some(busy.saturating_sub(own) as f32 / total as f32)
+    }
+}
@@ -0,0 +1,59 @@
+//! Tells whether the machine is idle, for mining only while nobody needs it.
+//! Linux desktops read input idle time and cpu load. The web reads page visibility and idle callbacks.
+
+#[cfg(all(feature = "desktop", target_os = "linux"))]
+mod linux;
+#[cfg(all(test, feature = "desktop", target_os = "linux"))]
+mod tests;
+#[cfg(feature = "web")]
+mod web;
+
+use std::time::Duration;
+
+use crate::miner::MiningSettings;
+
+/// How often idleness is checked while idle-only mining is on.
+pub const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Samples idleness over time. Cpu load is measured between calls.
+#[derive(Default)]
+pub struct IdleMonitor {
+    #[cfg(all(feature = "desktop", target_os = "linux"))]
+    cpu: Option<linux::CpuSample>,
+}
+
+impl IdleMonitor {
+    #[cfg(all(feature = "desktop", target_os = "linux"))]
+    pub async fn is_idle(&mut self, settings: &MiningSettings) -> bool {
+        let input_idle = linux::input_idle_time();
+        let cpu = linux::CpuSample::read();
+        let load = self
+            .cpu
+            .zip(cpu)
+            .and_then(|(earlier, later)| later.load_since(&earlier));
+        self.cpu = cpu;
+        is_idle(input_idle, load, settings)
+    }
+
+    /// Idle detection is only implemented for Linux, so other desktops always count as idle.
+    #[cfg(all(feature = "desktop", not(target_os = "linux")))]
+    pub async fn is_idle(&mut self, _settings: &MiningSettings) -> bool {
+        true
+    }
+
+    #[cfg(feature = "web")]
+    pub async fn is_idle(&mut self, _settings: &MiningSettings) -> bool {
+        web::is_page_idle().await
+    }
+}
+
+/// Idle once input has stopped for long enough and other programs leave the cpu mostly free.
+/// Whatever can't be measured does not hold mining back.
+#[cfg(all(feature = "desktop", target_os = "linux"))]
+fn is_idle(input_idle: Option<Duration>, load: Option<f32>, settings: &MiningSettings) -> bool {
+    let idle_after = Duration::from_secs(settings.idle_minutes as u64 * 60);
+    let is_away = input_idle.unwrap_or(Duration::MAX).ge(&idle_after);
+    let is_quiet =
+        settings.max_load.eq(&0) || (load.unwrap_or(0.0) * 100.0).le(&(settings.max_load as f32));
+    is_away && is_quiet
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use super::{
+    is_idle,
+    linux::{parse_gdbus_uint64, CpuSample},
+};
+use crate::miner::MiningSettings;
+
+const STAT: &str = "cpu  1000 0 500 8000 500 0 0 0 0 0\ncpu0 500 0 250 4000 250 0 0 0 0 0\n";
+const LATER_STAT: &str = "cpu  1600 0 700 9000 700 0 0 0 0 0\ncpu0 800 0 350 4500 350 0 0 0 0 0\n";
+
+fn own_stat(utime: u64, stime: u64) -> String {
+    // The command name holds spaces and parentheses
+    format!(
+        "4242 (mars (app) x) S 1 4242 4242 0 -1 4194560 100 0 0 0 {} {} 0 0 20 0 9 0 100 0 0",
+        utime, stime
+    )
+}
+
+fn settings() -> MiningSettings {
+    MiningSettings {
+        idle_only: true,
+        idle_minutes: 2,
+        max_load: 25,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn parses_mutter_idle_time() {
+    assert_eq!(parse_gdbus_uint64("(uint64 61234,)\n"), Some(61_234));
+    assert_eq!(parse_gdbus_uint64("(uint32 61234,)\n"), None);
+    assert_eq!(parse_gdbus_uint64(""), None);
+}
+
+#[test]
+fn load_excludes_own_threads() {
+    let earlier = CpuSample::parse(STAT, &own_stat(100, 0)).unwrap();
+    // 2000 ticks pass, 800 busy, of which 500 are ours
+    let later = CpuSample::parse(LATER_STAT, &own_stat(550, 50)).unwrap();
+    let load = later.load_since(&earlier).unwrap();
+    assert!((load - 300.0 / 2000.0).abs() < 1e-6);
+    assert_eq!(earlier.load_since(&earlier), None);
+    assert_eq!(earlier.load_since(&later), None);
+}
+
+#[test]
+fn rejects_malformed_stat() {
+    assert_eq!(CpuSample::parse("intr 1 2 3", &own_stat(0, 0)), None);
+    assert_eq!(CpuSample::parse(STAT, "4242 (mars) S 1"), None);
+}
+
+#[test]
+fn idle_needs_time_away_and_a_quiet_cpu() {
+    let settings = settings();
+    let away = Some(Duration::from_secs(180));
+    let typing = Some(Duration::from_secs(10));
+    assert!(is_idle(away, Some(0.1), &settings));
+    assert!(!is_idle(typing, Some(0.1), &settings));
+    assert!(!is_idle(away, Some(0.5), &settings));
+}
+
+#[test]
+fn unknown_readings_do_not_hold_mining_back() {
+    let settings = settings();
+    assert!(is_idle(None, Some(0.1), &settings));
+    assert!(!is_idle(None, Some(0.5), &settings));
+    assert!(is_idle(Some(Duration::from_secs(180)), None, &settings));
+    let no_load_limit = MiningSettings {
+        max_load: 0,
+        ..settings
+    };
+    assert!(is_idle(None, Some(1.0), &no_load_limit));
+}
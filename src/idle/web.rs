@@ -0,0 +1,47 @@
+use js_sys::Promise;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdleDeadline, IdleRequestOptions, VisibilityState, Window};
+
+/// How long the browser has to find an idle period before it counts as busy.
+const IDLE_CALLBACK_TIMEOUT_MS: u32 = 1000;
+
+/// Idle while the page is hidden, as the user has moved on to other work. A visible page is idle
+/// only while the browser has spare time between tasks.
+pub async fn is_page_idle() -> bool {
+    let Some(window) = web_sys::window() else {
+        return true;
+    };
+    let visibility = window
+        .document()
+        .map(|document| document.visibility_state());
+    if matches!(visibility, Some(VisibilityState::Hidden)) {
+        return true;
+    }
+    has_idle_period(&window).await
+}
+
+async fn has_idle_period(window: &Window) -> bool {
+    let promise = Promise::new(&mut |resolve, _| {
+        let callback = Closure::once_into_js({
+            let resolve = resolve.clone();
+            move |deadline: IdleDeadline| {
+                resolve
+                    .call1(&JsValue::NULL, &JsValue::from_bool(!deadline.did_timeout()))
+                    .ok();
+            }
+        });
+        let mut options = IdleRequestOptions::new();
+        options.timeout(IDLE_CALLBACK_TIMEOUT_MS);
+        if window
+            .request_idle_callback_with_options(callback.unchecked_ref(), &options)
+            .is_err()
+        {
+            // Not supported, as in Safari
+            resolve.call1(&JsValue::NULL, &JsValue::TRUE).ok();
+        }
+    });
+    JsFuture::from(promise)
+        .await
+        .map_or(true, |is_idle| is_idle.is_truthy())
+}
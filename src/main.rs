@@ -4,24 +4,41 @@
 use dioxus::prelude::*;
 use dioxus_router::prelude::*;
 
+mod challenge;
 mod components;
+mod config;
+mod export;
 #[cfg(feature = "desktop")]
 mod file;
 mod gateway;
+mod hashing;
 mod hooks;
+mod idle;
 mod metrics;
 mod miner;
+mod miner_state;
+mod notifications;
+mod pubsub;
 mod route;
+mod schedule;
 mod utils;
+mod wallet;
 #[cfg(feature = "web")]
 mod worker;
+#[cfg(feature = "desktop")]
+mod worker_pool;
 
 use crate::{
+    components::WalletSetup,
     hooks::{
-        use_appearance_provider, use_explorer_provider, use_is_onboarded_provider,
-        use_mars_balance_provider, use_power_level_provider, use_priority_fee_provider,
-        use_proof_provider, use_rpc_url_provider, use_show_backup_warning_provider,
-        use_sol_balance_provider, ProofHandle,
+        use_appearance_provider, use_config_provider, use_explorer_provider,
+        use_is_onboarded_provider, use_mars_balance_provider, use_mining_schedule_provider,
+        use_mining_settings_provider, use_mining_usage_provider, use_notification_center_provider,
+        use_notification_settings_provider, use_power_level_provider, use_priority_fee_provider,
+        use_proof_provider, use_pubsub_provider, use_rpc_url_provider, use_sol_balance_provider,
+        use_supply_history_provider, use_tip_config_provider, use_transfers_channel_provider,
+        use_wallet, use_wallet_provider, use_watchlist_provider, use_window_width_provider,
+        ProofHandle, SessionKeypair, Wallet,
     },
     route::Route,
 };
@@ -34,31 +51,73 @@ fn main() {
 
 #[cfg(feature = "desktop")]
 fn main() {
+    use dioxus_desktop::WindowCloseBehaviour;
     use utils::asset_path;
     env_logger::init();
     let tailwind_path = asset_path("tailwind.css");
     let custom_head = format!(r#"<link rel="stylesheet" href="{}">"#, tailwind_path);
+
+    // Closing only hides the window. use_close_behaviour quits unless minimizing to the tray.
     dioxus_desktop::launch_cfg(
         App,
-        dioxus_desktop::Config::new().with_custom_head(custom_head),
+        dioxus_desktop::Config::new()
+            .with_custom_head(custom_head)
+            .with_close_behaviour(WindowCloseBehaviour::LastWindowHides),
     );
 }
 
 #[component]
 fn App(cx: Scope) -> Element {
     // Global variables
+    use_config_provider(cx);
     use_appearance_provider(cx);
     use_explorer_provider(cx);
     use_power_level_provider(cx);
+    use_mining_settings_provider(cx);
+    use_mining_schedule_provider(cx);
+    use_mining_usage_provider(cx);
     use_is_onboarded_provider(cx);
     use_priority_fee_provider(cx);
-    use_show_backup_warning_provider(cx);
     use_rpc_url_provider(cx);
+    use_tip_config_provider(cx);
+    use_window_width_provider(cx);
+    use_notification_center_provider(cx);
+    use_notification_settings_provider(cx);
+    use_wallet_provider(cx);
+    #[cfg(feature = "desktop")]
+    hooks::use_close_behaviour(cx);
+
+    // Render
+    let wallet = use_wallet(cx).read().clone();
+    match wallet {
+        // Keyed by the wallet, so a replaced keypair starts a new session
+        Wallet::Ready {
+            keypair, pubkey, ..
+        } => render! {
+            Session {
+                key: "{pubkey}",
+                keypair: keypair
+            }
+        },
+        Wallet::Unavailable(_) => render! {
+            WalletSetup {}
+        },
+    }
+}
+
+/// Everything that needs the user's keypair.
+#[component]
+fn Session(cx: Scope, keypair: String) -> Element {
+    use_context_provider(cx, || SessionKeypair(keypair.clone()));
+    use_transfers_channel_provider(cx);
+    use_watchlist_provider(cx);
 
     // Network variables
+    use_pubsub_provider(cx);
     use_proof_provider(cx);
     use_mars_balance_provider(cx);
     use_sol_balance_provider(cx);
+    use_supply_history_provider(cx);
 
     // Render
     render! {
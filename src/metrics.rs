@@ -18,6 +18,8 @@ pub enum AppEvent {
     StopMiner,
     Claim,
     Transfer,
+    TransferSol,
+    Sweep,
     SetPriorityFee,
 }
 
@@ -30,6 +32,8 @@ impl Display for AppEvent {
             AppEvent::StopMiner => "Stop miner",
             AppEvent::Claim => "Claim",
             AppEvent::Transfer => "Transfer",
+            AppEvent::TransferSol => "Transfer SOL",
+            AppEvent::Sweep => "Sweep",
             AppEvent::SetPriorityFee => "Set priority fee",
         };
         write!(f, "{:}", str)
@@ -1,15 +1,9 @@
-use std::rc::Rc;
+use std::cell::{Cell, RefCell};
 #[cfg(feature = "desktop")]
-use std::{
-    sync::{
-        atomic::{AtomicBool, Ordering},
-        Arc, Mutex,
-    },
-    time::Duration,
-};
+use std::{rc::Rc, time::Duration};
 
 use dioxus::prelude::UseSharedState;
-use dioxus_std::utils::{channel::UseChannel, rw::UseRw};
+use dioxus_std::utils::channel::UseChannel;
 use mars::{state::Treasury, BUS_COUNT, EPOCH_DURATION};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
@@ -17,16 +11,12 @@ use serde::{Deserialize, Serialize};
 use serde_wasm_bindgen::to_value;
 #[cfg(feature = "web")]
 use solana_client_wasm::solana_sdk::{
-    compute_budget::ComputeBudgetInstruction,
-    keccak::{hashv, Hash as KeccakHash},
-    pubkey::Pubkey,
+    compute_budget::ComputeBudgetInstruction, keccak::Hash as KeccakHash, pubkey::Pubkey,
     signer::Signer,
 };
 #[cfg(feature = "desktop")]
 use solana_sdk::{
-    compute_budget::ComputeBudgetInstruction,
-    keccak::{hashv, Hash as KeccakHash},
-    pubkey::Pubkey,
+    compute_budget::ComputeBudgetInstruction, keccak::Hash as KeccakHash, pubkey::Pubkey,
     signer::Signer,
 };
 #[cfg(feature = "web")]
@@ -34,14 +24,32 @@ use web_sys::Worker;
 #[cfg(feature = "web")]
 use web_time::Duration;
 
-#[cfg(feature = "web")]
-use crate::worker::create_worker;
+pub use crate::hashing::MiningResult;
+#[cfg(feature = "desktop")]
+pub use crate::worker_pool::hash_count;
 use crate::{
+    challenge::Challenge,
     gateway::{
-        signer, AsyncResult, Gateway, GatewayError, GatewayResult, CU_LIMIT_MINE, CU_LIMIT_RESET,
+        network_fee, record_history, Gateway, GatewayError, GatewayResult, HistoryKind,
+        CU_LIMIT_MINE, CU_LIMIT_RESET,
     },
     hooks::PowerLevel,
+    notifications::{publish, Severity},
 };
+#[cfg(feature = "desktop")]
+use crate::{
+    hashing::kernel_name,
+    worker_pool::{threads_for, PoolOptions, WorkerPool},
+};
+#[cfg(feature = "web")]
+use crate::{hashing::search, worker::create_worker};
+
+/// Attempts to submit a solution before giving up on it.
+const SUBMIT_ATTEMPTS: u32 = 5;
+
+/// Buses sampled for rewards before giving up on an attempt. All of them may be drained until
+/// the epoch resets.
+const BUS_SEARCH_ATTEMPTS: usize = BUS_COUNT * 2;
 
 /// Mining request for web workers
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,42 +59,130 @@ pub struct MineRequest {
     pub pubkey: Pubkey,
 }
 
-/// Mining response from web workers
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
-pub struct MiningResult {
-    pub hash: KeccakHash,
-    pub nonce: u64,
+/// How the miner shares the machine with everything else.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MiningSettings {
+    /// Keep each mining thread on its own core.
+    pub pin_threads: bool,
+    /// Run mining threads below normal priority.
+    pub low_priority: bool,
+    pub throttle_on_battery: bool,
+    /// Power level used instead while on battery.
+    pub battery_power_level: PowerLevel,
+    /// Shed threads while the cpu is at least this hot, in degrees Celsius. 0 disables it.
+    pub max_temperature: u8,
+    /// Pause mining while the machine is in use.
+    pub idle_only: bool,
+    /// Minutes without input before the machine counts as idle.
+    pub idle_minutes: u32,
+    /// Percent of the cpu other programs may use while idle. 0 disables it.
+    pub max_load: u8,
+}
+
+impl Default for MiningSettings {
+    fn default() -> Self {
+        Self {
+            pin_threads: false,
+            low_priority: true,
+            throttle_on_battery: true,
+            battery_power_level: PowerLevel(30),
+            max_temperature: 85,
+            idle_only: false,
+            idle_minutes: 2,
+            max_load: 25,
+        }
+    }
+}
+
+/// Mining threads, started with the first challenge and kept for the session.
+#[cfg(feature = "desktop")]
+#[derive(Clone, Default)]
+struct PoolHandle(Rc<RefCell<Option<WorkerPool>>>);
+
+#[cfg(feature = "desktop")]
+impl PartialEq for PoolHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
 }
 
 /// Miner encapsulates the logic needed to efficiently mine for valid hashes according to the application runtime and hardware.
 #[derive(PartialEq)]
 pub struct Miner {
     #[cfg(feature = "web")]
-    worker: Worker,
-    #[cfg(feature = "desktop")]
+    worker: RefCell<Worker>,
     ch: UseChannel<MiningResult>,
     power_level: UseSharedState<PowerLevel>,
+    mining_settings: UseSharedState<MiningSettings>,
+    #[cfg(feature = "desktop")]
+    pool: PoolHandle,
+    /// The challenge being searched.
+    challenge: Cell<Option<Challenge>>,
+    /// Hash of the last challenge a solution was accepted for.
+    solved: Cell<Option<KeccakHash>>,
 }
 
 impl Miner {
-    pub fn new(ch: &UseChannel<MiningResult>, power_level: &UseSharedState<PowerLevel>) -> Self {
+    pub fn new(
+        ch: &UseChannel<MiningResult>,
+        power_level: &UseSharedState<PowerLevel>,
+        mining_settings: &UseSharedState<MiningSettings>,
+    ) -> Self {
         Self {
             #[cfg(feature = "web")]
-            worker: create_worker(ch),
-            #[cfg(feature = "desktop")]
+            worker: RefCell::new(create_worker(ch)),
             ch: ch.clone(),
             power_level: power_level.clone(),
+            mining_settings: mining_settings.clone(),
+            #[cfg(feature = "desktop")]
+            pool: PoolHandle::default(),
+            challenge: Cell::new(None),
+            solved: Cell::new(None),
         }
     }
 
+    pub fn challenge(&self) -> Option<Challenge> {
+        self.challenge.get()
+    }
+
+    pub fn solved(&self) -> Option<KeccakHash> {
+        self.solved.get()
+    }
+
+    /// Remembers the current challenge as solved, once its solution is accepted.
+    pub fn mark_solved(&self) {
+        self.solved.set(self.challenge.get().map(|c| c.hash));
+    }
+
     pub fn stop(&self) {
-        // TODO interrupt current work (optimization)
+        self.challenge.set(None);
+        // Workers can't be interrupted, so a fresh one replaces it
+        #[cfg(feature = "web")]
+        {
+            let worker = self.worker.replace(create_worker(&self.ch));
+            worker.terminate();
+        }
+        #[cfg(feature = "desktop")]
+        if let Some(pool) = self.pool.0.borrow().as_ref() {
+            pool.cancel();
+        }
+    }
+
+    /// Applies the power level and throttling settings to running threads.
+    #[cfg(feature = "desktop")]
+    pub fn configure(&self) {
+        if let Some(pool) = self.pool.0.borrow().as_ref() {
+            pool.configure(*self.power_level.read(), *self.mining_settings.read());
+        }
     }
 
     pub async fn start_mining(&self, hash: KeccakHash, difficulty: KeccakHash, signer: Pubkey) {
+        self.challenge.set(Some(Challenge { hash, difficulty }));
         #[cfg(feature = "web")]
         {
             self.worker
+                .borrow()
                 .post_message(
                     &to_value(
                         &(MineRequest {
@@ -102,131 +198,89 @@ impl Miner {
 
         #[cfg(feature = "desktop")]
         {
+            let power_level = *self.power_level.read();
+            let settings = *self.mining_settings.read();
+            let options = PoolOptions::new(&settings);
+            let mut pool = self.pool.0.borrow_mut();
+            if pool.as_ref().map(WorkerPool::options).ne(&Some(options)) {
+                // Pinning and priority are set as threads start, so changing them restarts the pool
+                *pool = Some(WorkerPool::new(options, power_level, settings));
+            }
+            let pool = pool.as_ref().unwrap();
+            pool.configure(power_level, settings);
+            log::info!(
+                "Mining on up to {} threads with the {} kernel",
+                threads_for(power_level, options.threads),
+                kernel_name()
+            );
+            let rx = pool.mine(hash, difficulty, signer);
             let ch = self.ch.clone();
-            let flag = Arc::new(AtomicBool::new(false));
-            let result = Arc::new(Mutex::new(MiningResult::default()));
-            let power_percent = ((self.power_level.read().0 + 1) as f64) / 8f64;
-            let concurrency = num_cpus::get() as u64;
-            let tuned_concurrency = ((concurrency as f64) * power_percent).round() as u64;
-            let handles: Vec<_> = (0..tuned_concurrency)
-                .map(|i| {
-                    std::thread::spawn({
-                        let flag = flag.clone();
-                        let result = result.clone();
-                        move || {
-                            let nonce =
-                                u64::MAX.saturating_div(tuned_concurrency).saturating_mul(i);
-                            if let Some(res) =
-                                find_next_hash_par(hash, difficulty, signer, nonce, flag.clone())
-                            {
-                                flag.store(true, Ordering::Relaxed);
-                                let mut w_result = result.lock().unwrap();
-                                *w_result = res;
-                            }
-                        }
-                    })
-                })
-                .collect();
             async_std::task::spawn(async move {
-                for h in handles {
-                    h.join().unwrap();
+                // Closed when the job is cancelled or replaced
+                if let Ok(res) = rx.recv().await {
+                    ch.send(res).await.ok();
                 }
-                let res = {
-                    let r_result = result.lock().unwrap();
-                    r_result.clone()
-                };
-                ch.send(res).await.ok();
             });
         }
     }
 }
 
-#[cfg(feature = "desktop")]
-fn find_next_hash_par(
-    hash: KeccakHash,
-    difficulty: KeccakHash,
-    signer: Pubkey,
-    nonce: u64,
-    flag: Arc<AtomicBool>,
-) -> Option<MiningResult> {
-    let mut next_hash: KeccakHash;
-    let mut nonce = nonce;
-    loop {
-        if nonce % 10_000 == 0 && flag.load(Ordering::Relaxed) {
-            return None;
-        }
-        next_hash = hashv(&[
-            hash.as_ref(),
-            signer.as_ref(),
-            nonce.to_le_bytes().as_slice(),
-        ]);
-        if next_hash.le(&difficulty) {
-            break;
-        }
-        nonce += 1;
-    }
-    Some(MiningResult {
-        hash: next_hash,
-        nonce,
-    })
-}
-
 #[cfg(feature = "web")]
 pub fn find_next_hash(hash: KeccakHash, difficulty: KeccakHash, signer: Pubkey) -> MiningResult {
-    let mut next_hash: KeccakHash;
-    let mut nonce = 0u64;
-    loop {
-        next_hash = hashv(&[
-            hash.as_ref(),
-            signer.as_ref(),
-            nonce.to_le_bytes().as_slice(),
-        ]);
-        if next_hash.le(&difficulty) {
-            break;
-        }
-        nonce += 1;
-    }
-    MiningResult {
-        hash: next_hash,
-        nonce,
-    }
+    search(&hash, &signer, &difficulty, 0..u64::MAX, |_| true).unwrap_or_default()
 }
 
-async fn find_open_bus(gateway: &Rc<Gateway>, reward_rate: u64) -> usize {
+async fn find_open_bus(gateway: &Gateway, reward_rate: u64) -> GatewayResult<usize> {
     // Find a valid bus
     let mut rng = rand::thread_rng();
-    loop {
+    for _ in 0..BUS_SEARCH_ATTEMPTS {
         let bus_id = rng.gen_range(0..BUS_COUNT);
-        if let Ok(bus) = gateway.get_bus(bus_id).await {
-            // MI
-            // if bus.rewards.gt(&reward_rate.saturating_mul(4)) {
-            if bus.rewards.ge(&reward_rate.saturating_mul(1)) {
-                return bus_id;
-            }
+        let bus = gateway.get_bus(bus_id).await?;
+        // MI
+        // if bus.rewards.gt(&reward_rate.saturating_mul(4)) {
+        if bus.rewards.ge(&reward_rate.saturating_mul(1)) {
+            return Ok(bus_id);
         }
         async_std::task::sleep(Duration::from_secs(1)).await;
     }
+    Err(GatewayError::NoOpenBus)
+}
+
+/// Outcome of submitting a solution.
+#[derive(Debug)]
+pub struct Submission {
+    pub result: GatewayResult<()>,
+    /// Lamports of fees and tips paid along the way, including attempts that landed but failed
+    /// and epoch resets.
+    pub fees: u64,
 }
 
+/// Submits the solution, retrying failed attempts.
 pub async fn submit_solution(
-    gateway: &Rc<Gateway>,
+    gateway: &Gateway,
     res: &MiningResult,
     priority_fee: u64,
-    treasury_rw: UseRw<AsyncResult<Treasury>>,
+    treasury: Treasury,
+) -> Submission {
+    let mut fees = 0;
+    let result = try_submit_solution(gateway, res, priority_fee, treasury, &mut fees).await;
+    Submission { result, fees }
+}
+
+async fn try_submit_solution(
+    gateway: &Gateway,
+    res: &MiningResult,
+    priority_fee: u64,
+    treasury: Treasury,
+    fees: &mut u64,
 ) -> GatewayResult<()> {
     // Submit mine tx.
     let next_hash = res.hash;
     let nonce = res.nonce;
-    let signer = signer();
+    let signer = gateway.signer()?;
 
-    // Read current treasury value
-    let treasury = match *treasury_rw.read().unwrap() {
-        AsyncResult::Ok(treasury) => treasury,
-        _ => return Err(GatewayError::Unknown), // TODO
-    };
-
-    // Find a valid bus
     let mut rng = rand::thread_rng();
+    let mut attempts = 0;
     loop {
         // Check if epoch needs to be reset
         if let Ok(clock) = gateway.get_clock().await {
@@ -242,34 +296,69 @@ pub async fn submit_solution(
                     let cu_price_ix =
                         ComputeBudgetInstruction::set_compute_unit_price(priority_fee);
                     let ix = mars::instruction::reset(signer.pubkey());
-                    gateway
+                    let fee = gateway.estimate_fee(CU_LIMIT_RESET, priority_fee);
+                    // Sent without confirming, so counted once sent
+                    if gateway
                         .send_and_confirm(&[cu_limit_ix, cu_price_ix, ix], false, true)
                         .await
-                        .ok();
+                        .is_ok()
+                    {
+                        *fees = fees.saturating_add(fee);
+                    }
                 }
             }
         }
 
+        // Retrying cannot succeed without SOL to pay the fee
+        let fee = gateway.estimate_fee(CU_LIMIT_MINE, priority_fee);
+        if let Ok(balance) = gateway.rpc.get_balance(&signer.pubkey()).await {
+            if balance.lt(&fee) {
+                return Err(GatewayError::InsufficientFunds);
+            }
+        }
+
         // Submit mine tx
-        let bus_id = find_open_bus(gateway, treasury.reward_rate).await;
-        log::info!("Using bus {}", bus_id);
-        let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(CU_LIMIT_MINE);
-        let cu_price_ix = ComputeBudgetInstruction::set_compute_unit_price(priority_fee);
-        let ix = mars::instruction::mine(
-            signer.pubkey(),
-            mars::BUS_ADDRESSES[bus_id],
-            next_hash.into(),
-            nonce,
-        );
-        match gateway
-            .send_and_confirm(&[cu_limit_ix, cu_price_ix, ix], false, false)
-            .await
-        {
-            Ok(_sig) => return Ok(()),
+        let res = match find_open_bus(gateway, treasury.reward_rate).await {
+            Ok(bus_id) => {
+                log::info!("Using bus {}", bus_id);
+                let cu_limit_ix = ComputeBudgetInstruction::set_compute_unit_limit(CU_LIMIT_MINE);
+                let cu_price_ix = ComputeBudgetInstruction::set_compute_unit_price(priority_fee);
+                let ix = mars::instruction::mine(
+                    signer.pubkey(),
+                    mars::BUS_ADDRESSES[bus_id],
+                    next_hash.into(),
+                    nonce,
+                );
+                gateway
+                    .send_and_confirm(&[cu_limit_ix, cu_price_ix, ix], false, false)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        match res {
+            Ok(sig) => {
+                record_history(
+                    HistoryKind::Mine,
+                    sig,
+                    treasury.reward_rate,
+                    network_fee(CU_LIMIT_MINE, priority_fee),
+                );
+                *fees = fees.saturating_add(fee);
+                return Ok(());
+            }
             Err(err) => {
-                // TODO Retry
+                // Landed, so the network fee was charged even though the tip was rolled back
+                if err.eq(&GatewayError::TransactionFailed) {
+                    *fees = fees.saturating_add(network_fee(CU_LIMIT_MINE, priority_fee));
+                }
                 // TODO It seems this can error can occur sometimes, even while tx was submitted
                 log::error!("Error submitting: {:?}", err);
+                attempts += 1;
+                if attempts.ge(&SUBMIT_ATTEMPTS) {
+                    return Err(err);
+                }
+                publish(Severity::Warning, "Retrying hash submission", err);
+                async_std::task::sleep(gateway.retry_delay(attempts)).await;
             }
         }
     }
@@ -0,0 +1,136 @@
+//! The miner's lifecycle as a state machine. Every change goes through [`MinerState::apply`],
+//! so failures are counted in one place and retried with a growing backoff.
+
+#[cfg(all(test, feature = "desktop"))]
+mod tests;
+
+use std::{fmt, time::Duration};
+
+use crate::gateway::{backoff, GatewayError};
+
+/// Failures in a row the miner restarts after, before it gives up and shows the error.
+pub const MAX_RETRIES: u32 = 5;
+
+/// Wait before the first restart. Doubles with every failure after.
+pub const RESTART_BACKOFF: Duration = Duration::from_secs(5);
+
+/// How often the network is checked while the miner is stopped by an error.
+pub const RECOVERY_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MinerStatus {
+    #[default]
+    NotStarted,
+    Activating,
+    Active,
+    /// Waiting for the machine to be idle, in idle-only mode.
+    Paused,
+    /// Waiting out the backoff before starting again.
+    Retrying(MinerError),
+    /// Gave up. Resumes once the network is back, if it was the cause, or when retried by hand.
+    Error(MinerError),
+}
+
+/// What can happen to the miner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinerEvent {
+    Start,
+    Started,
+    /// A solution was accepted.
+    Submitted,
+    Pause,
+    Resume,
+    Stop,
+    Fail(MinerError),
+    Retry,
+}
+
+/// What the miner was doing when it failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinerStage {
+    Starting,
+    Submitting,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MinerError {
+    pub stage: MinerStage,
+    pub error: GatewayError,
+}
+
+impl MinerError {
+    pub fn new(stage: MinerStage, error: GatewayError) -> Self {
+        Self { stage, error }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self.stage {
+            MinerStage::Starting => "Failed to start mining",
+            MinerStage::Submitting => "Failed to submit hash",
+        }
+    }
+
+    /// Retrying cannot help until the wallet is topped up or recovered.
+    pub fn is_retryable(&self) -> bool {
+        !matches!(
+            self.error,
+            GatewayError::InsufficientFunds | GatewayError::WalletUnavailable
+        )
+    }
+}
+
+impl fmt::Display for MinerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.title(), self.error)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MinerState {
+    pub status: MinerStatus,
+    /// Failures in a row. Reset once a solution is accepted, or the miner is started by hand.
+    pub failures: u32,
+}
+
+impl MinerState {
+    /// Moves to the next status. Events that make no sense in the current status are ignored,
+    /// and return false.
+    pub fn apply(&mut self, event: MinerEvent) -> bool {
+        use MinerStatus::*;
+        let (status, failures) = match (self.status, event) {
+            (NotStarted, MinerEvent::Start) | (Error(_), MinerEvent::Start) => (Activating, 0),
+            (Activating, MinerEvent::Started) => (Active, self.failures),
+            (Active, MinerEvent::Submitted) => (Active, 0),
+            (Active, MinerEvent::Pause) => (Paused, self.failures),
+            (Paused, MinerEvent::Resume) => (Activating, self.failures),
+            (Activating | Active | Paused | Retrying(_) | Error(_), MinerEvent::Stop) => {
+                (NotStarted, 0)
+            }
+            (Activating | Active | Paused, MinerEvent::Fail(error)) => {
+                let failures = self.failures.saturating_add(1);
+                if error.is_retryable() && failures.le(&MAX_RETRIES) {
+                    (Retrying(error), failures)
+                } else {
+                    (Error(error), failures)
+                }
+            }
+            (Retrying(_), MinerEvent::Retry) => (Activating, self.failures),
+            (Error(_), MinerEvent::Retry) => (Activating, 0),
+            (status, event) => {
+                log::warn!("Ignoring miner event {:?} while {:?}", event, status);
+                return false;
+            }
+        };
+        self.status = status;
+        self.failures = failures;
+        true
+    }
+
+    /// How long to wait before restarting, while retrying.
+    pub fn retry_delay(&self) -> Option<Duration> {
+        match self.status {
+            MinerStatus::Retrying(_) => Some(backoff(RESTART_BACKOFF, self.failures)),
+            _ => None,
+        }
+    }
+}
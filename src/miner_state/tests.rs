@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use super::{MinerError, MinerEvent, MinerStage, MinerState, MinerStatus, MAX_RETRIES};
+use crate::gateway::GatewayError;
+
+fn offline() -> MinerError {
+    MinerError::new(MinerStage::Submitting, GatewayError::NetworkUnavailable)
+}
+
+fn active() -> MinerState {
+    let mut state = MinerState::default();
+    assert!(state.apply(MinerEvent::Start));
+    assert!(state.apply(MinerEvent::Started));
+    state
+}
+
+#[test]
+fn starts_pauses_and_stops() {
+    let mut state = active();
+    assert_eq!(state.status, MinerStatus::Active);
+    assert!(state.apply(MinerEvent::Pause));
+    assert_eq!(state.status, MinerStatus::Paused);
+    assert!(state.apply(MinerEvent::Resume));
+    assert_eq!(state.status, MinerStatus::Activating);
+    assert!(state.apply(MinerEvent::Stop));
+    assert_eq!(state, MinerState::default());
+}
+
+#[test]
+fn ignores_events_that_do_not_apply() {
+    let mut state = MinerState::default();
+    assert!(!state.apply(MinerEvent::Started));
+    assert!(!state.apply(MinerEvent::Stop));
+    assert!(!state.apply(MinerEvent::Fail(offline())));
+    assert!(!state.apply(MinerEvent::Retry));
+    assert_eq!(state, MinerState::default());
+    let mut state = active();
+    assert!(!state.apply(MinerEvent::Start));
+    assert!(!state.apply(MinerEvent::Resume));
+    assert_eq!(state.status, MinerStatus::Active);
+}
+
+#[test]
+fn retries_with_growing_backoff_then_gives_up() {
+    let mut state = active();
+    let mut delays = vec![];
+    for _ in 0..MAX_RETRIES {
+        assert!(state.apply(MinerEvent::Fail(offline())));
+        assert_eq!(state.status, MinerStatus::Retrying(offline()));
+        delays.push(state.retry_delay().unwrap().as_secs());
+        assert!(state.apply(MinerEvent::Retry));
+        assert_eq!(state.status, MinerStatus::Activating);
+    }
+    assert_eq!(delays, [5, 10, 20, 40, 60]);
+    assert!(state.apply(MinerEvent::Fail(offline())));
+    assert_eq!(state.status, MinerStatus::Error(offline()));
+    assert_eq!(state.retry_delay(), None);
+}
+
+#[test]
+fn accepted_solution_resets_failures() {
+    let mut state = active();
+    state.apply(MinerEvent::Fail(offline()));
+    state.apply(MinerEvent::Retry);
+    state.apply(MinerEvent::Started);
+    assert_eq!(state.failures, 1);
+    assert!(state.apply(MinerEvent::Submitted));
+    assert_eq!(state.failures, 0);
+    state.apply(MinerEvent::Fail(offline()));
+    assert_eq!(state.retry_delay(), Some(Duration::from_secs(5)));
+}
+
+#[test]
+fn insufficient_funds_is_not_retried() {
+    let error = MinerError::new(MinerStage::Submitting, GatewayError::InsufficientFunds);
+    let mut state = active();
+    assert!(state.apply(MinerEvent::Fail(error)));
+    assert_eq!(state.status, MinerStatus::Error(error));
+    // Topping up starts the miner again
+    assert!(state.apply(MinerEvent::Start));
+    assert_eq!(state.status, MinerStatus::Activating);
+    assert_eq!(state.failures, 0);
+}
+
+#[test]
+fn describes_errors() {
+    let error = MinerError::new(MinerStage::Starting, GatewayError::FailedRegister);
+    assert_eq!(
+        error.to_string(),
+        "Failed to start mining: Failed to register the miner"
+    );
+}
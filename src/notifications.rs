@@ -0,0 +1,191 @@
+use std::sync::OnceLock;
+
+use async_std::channel::{unbounded, Receiver, Sender};
+use serde::{Deserialize, Serialize};
+
+/// Events that can raise a system notification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationEvent {
+    SolutionAccepted,
+    MiningError,
+    InsufficientSol,
+    IncomingTransfer,
+    ClaimCompleted,
+}
+
+impl NotificationEvent {
+    pub const ALL: [NotificationEvent; 5] = [
+        NotificationEvent::SolutionAccepted,
+        NotificationEvent::MiningError,
+        NotificationEvent::InsufficientSol,
+        NotificationEvent::IncomingTransfer,
+        NotificationEvent::ClaimCompleted,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            NotificationEvent::SolutionAccepted => "Solution accepted",
+            NotificationEvent::MiningError => "Mining error",
+            NotificationEvent::InsufficientSol => "Insufficient SOL",
+            NotificationEvent::IncomingTransfer => "Funds received",
+            NotificationEvent::ClaimCompleted => "Claim completed",
+        }
+    }
+}
+
+/// Which events notify the user, and how the desktop app behaves when closed.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub solution_accepted: bool,
+    pub mining_error: bool,
+    pub insufficient_sol: bool,
+    pub incoming_transfer: bool,
+    pub claim_completed: bool,
+    /// Keep mining in the system tray when the window is closed. Desktop only.
+    pub minimize_to_tray: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            // Once per hash is too noisy to be on by default
+            solution_accepted: false,
+            mining_error: true,
+            insufficient_sol: true,
+            incoming_transfer: true,
+            claim_completed: true,
+            minimize_to_tray: false,
+        }
+    }
+}
+
+impl NotificationSettings {
+    pub fn is_enabled(&self, event: NotificationEvent) -> bool {
+        match event {
+            NotificationEvent::SolutionAccepted => self.solution_accepted,
+            NotificationEvent::MiningError => self.mining_error,
+            NotificationEvent::InsufficientSol => self.insufficient_sol,
+            NotificationEvent::IncomingTransfer => self.incoming_transfer,
+            NotificationEvent::ClaimCompleted => self.claim_completed,
+        }
+    }
+
+    pub fn set_enabled(&mut self, event: NotificationEvent, enabled: bool) {
+        match event {
+            NotificationEvent::SolutionAccepted => self.solution_accepted = enabled,
+            NotificationEvent::MiningError => self.mining_error = enabled,
+            NotificationEvent::InsufficientSol => self.insufficient_sol = enabled,
+            NotificationEvent::IncomingTransfer => self.incoming_transfer = enabled,
+            NotificationEvent::ClaimCompleted => self.claim_completed = enabled,
+        }
+    }
+}
+
+/// Notifies about the event, if the user has it enabled.
+pub fn notify_event(settings: &NotificationSettings, event: NotificationEvent, body: &str) {
+    if settings.is_enabled(event) {
+        notify(event.title(), body);
+    }
+}
+
+/// Asks for permission to show system notifications, if not yet decided.
+/// Browsers only prompt in response to a user action, so call this from an event handler.
+#[cfg(feature = "web")]
+pub fn request_notification_permission() {
+    use web_sys::{Notification, NotificationPermission};
+
+    if Notification::permission().eq(&NotificationPermission::Default) {
+        if let Ok(promise) = Notification::request_permission() {
+            wasm_bindgen_futures::spawn_local(async move {
+                wasm_bindgen_futures::JsFuture::from(promise).await.ok();
+            });
+        }
+    }
+}
+
+#[cfg(feature = "desktop")]
+pub fn request_notification_permission() {
+    // Noop
+}
+
+/// Shows a system notification. Dropped silently if permission was not granted.
+#[cfg(feature = "web")]
+pub fn notify(title: &str, body: &str) {
+    use web_sys::{Notification, NotificationOptions, NotificationPermission};
+
+    if Notification::permission().ne(&NotificationPermission::Granted) {
+        return;
+    }
+    let mut options = NotificationOptions::new();
+    options.body(body);
+    Notification::new_with_options(title, &options).ok();
+}
+
+#[cfg(feature = "desktop")]
+pub fn notify(title: &str, body: &str) {
+    if let Err(err) = notify_rust::Notification::new()
+        .appname("Mars")
+        .summary(title)
+        .body(body)
+        .show()
+    {
+        log::error!("Failed to show notification: {:?}", err);
+    }
+}
+
+/// How serious an in-app notification is.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+/// An entry in the in-app notification center.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct AppNotification {
+    /// Assigned by the notification center when received.
+    pub id: u64,
+    pub severity: Severity,
+    pub title: String,
+    pub body: String,
+    /// Unix timestamp of the latest occurrence, in seconds.
+    pub ts: i64,
+    /// Times it was published while its toast was still showing.
+    pub count: u32,
+}
+
+impl AppNotification {
+    pub fn is_same(&self, other: &AppNotification) -> bool {
+        self.severity.eq(&other.severity)
+            && self.title.eq(&other.title)
+            && self.body.eq(&other.body)
+    }
+}
+
+static CHANNEL: OnceLock<(Sender<AppNotification>, Receiver<AppNotification>)> = OnceLock::new();
+
+fn channel() -> &'static (Sender<AppNotification>, Receiver<AppNotification>) {
+    CHANNEL.get_or_init(unbounded)
+}
+
+/// Publishes to the in-app notification center. Callable from any task or thread,
+/// so gateway, miner and websocket code can report problems without a scope.
+pub fn publish(severity: Severity, title: impl ToString, body: impl ToString) {
+    let notification = AppNotification {
+        id: 0,
+        severity,
+        title: title.to_string(),
+        body: body.to_string(),
+        ts: chrono::Utc::now().timestamp(),
+        count: 1,
+    };
+    channel().0.try_send(notification).ok();
+}
+
+/// Everything published. Only the notification center should receive from this.
+pub fn published() -> Receiver<AppNotification> {
+    channel().1.clone()
+}
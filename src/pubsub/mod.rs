@@ -0,0 +1,223 @@
+//! One websocket to the RPC, shared by every account and signature subscription. Subscriptions
+//! are multiplexed over it and made again after it reconnects, so polling is only a fallback.
+
+mod registry;
+#[cfg(all(test, feature = "desktop"))]
+mod tests;
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+use async_std::channel::{unbounded, Receiver, RecvError, Sender};
+#[cfg(feature = "desktop")]
+use async_tungstenite::{async_std::connect_async, tungstenite::Message};
+use futures::{stream, SinkExt, StreamExt};
+#[cfg(feature = "web")]
+use gloo::net::websocket::{futures::WebSocket, Message, State};
+pub use registry::*;
+use url::Url;
+
+use crate::gateway::backoff;
+
+/// Wait before the first reconnect. Doubles with every failed attempt after.
+const RECONNECT_BACKOFF: Duration = Duration::from_secs(2);
+
+/// The pubsub endpoint of an RPC: the same host over ws, on the next port if one is given.
+pub fn websocket_url(rpc_url: &str) -> Option<String> {
+    let mut url = Url::parse(rpc_url).ok()?;
+    let scheme = match url.scheme() {
+        "https" => "wss",
+        "http" => "ws",
+        _ => return None,
+    };
+    url.set_scheme(scheme).ok()?;
+    if let Some(port) = url.port() {
+        url.set_port(Some(port.saturating_add(1))).ok()?;
+    }
+    Some(url.to_string())
+}
+
+/// Handle to the shared connection.
+#[derive(Clone)]
+pub struct PubsubHandle {
+    registry: Rc<RefCell<Registry>>,
+    connected: Rc<Cell<bool>>,
+    outgoing: (Sender<String>, Receiver<String>),
+}
+
+impl Default for PubsubHandle {
+    fn default() -> Self {
+        Self {
+            registry: Default::default(),
+            connected: Default::default(),
+            outgoing: unbounded(),
+        }
+    }
+}
+
+/// A listener on a topic. The server side is unsubscribed once every listener is dropped.
+pub struct Subscription {
+    rx: Receiver<Update>,
+    pubsub: PubsubHandle,
+}
+
+impl Subscription {
+    pub async fn recv(&self) -> Result<Update, RecvError> {
+        self.rx.recv().await
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.rx.close();
+        self.pubsub.release();
+    }
+}
+
+enum Event<T> {
+    Received(T),
+    Closed,
+    Outgoing(String),
+}
+
+impl PubsubHandle {
+    pub fn is_connected(&self) -> bool {
+        self.connected.get()
+    }
+
+    /// Follows a topic until the subscription is dropped. Ends by itself when a signature is
+    /// confirmed.
+    pub fn subscribe(&self, topic: Topic) -> Subscription {
+        let (tx, rx) = unbounded();
+        let request = self.registry.borrow_mut().subscribe(topic, tx);
+        if let Some(request) = request {
+            self.send(request);
+        }
+        Subscription {
+            rx,
+            pubsub: self.clone(),
+        }
+    }
+
+    /// Unsubscribes from the topics whose listeners were all dropped.
+    fn release(&self) {
+        let requests = self.registry.borrow_mut().release();
+        for request in requests {
+            self.send(request);
+        }
+    }
+
+    fn send(&self, request: String) {
+        // Otherwise it is covered by resubscribing on connecting
+        if self.is_connected() {
+            self.outgoing.0.try_send(request).ok();
+        }
+    }
+
+    /// Keeps a connection to the RPC open, reconnecting with a backoff whenever it drops.
+    pub async fn run(&self, rpc_url: String) {
+        self.connected.set(false);
+        let Some(url) = websocket_url(&rpc_url) else {
+            log::error!("No pubsub endpoint for {}", rpc_url);
+            return;
+        };
+        let mut attempt = 0;
+        loop {
+            if let Err(err) = self.session(&url).await {
+                log::warn!("Pubsub connection lost: {}", err);
+            }
+            if self.connected.replace(false) {
+                attempt = 0;
+            }
+            attempt += 1;
+            async_std::task::sleep(backoff(RECONNECT_BACKOFF, attempt)).await;
+        }
+    }
+
+    /// Marks the connection open and returns the requests that restore every subscription.
+    fn open(&self) -> Vec<String> {
+        // Requests queued for the last connection are covered by resubscribing
+        while self.outgoing.1.try_recv().is_ok() {}
+        self.connected.set(true);
+        self.registry.borrow_mut().resubscribe()
+    }
+
+    #[cfg(feature = "desktop")]
+    async fn session(&self, url: &str) -> Result<(), String> {
+        let (ws, _) = connect_async(url).await.map_err(|err| err.to_string())?;
+        let (mut write, read) = ws.split();
+        for request in self.open() {
+            write
+                .send(Message::Text(request))
+                .await
+                .map_err(|err| err.to_string())?;
+        }
+        let mut events = stream::select(
+            read.map(Event::Received)
+                .chain(stream::iter([Event::Closed])),
+            self.outgoing.1.clone().map(Event::Outgoing),
+        );
+        while let Some(event) = events.next().await {
+            let requests = match event {
+                Event::Received(Ok(Message::Text(text))) => {
+                    self.registry.borrow_mut().handle(&text)
+                }
+                Event::Received(Ok(Message::Close(_))) | Event::Closed => return Ok(()),
+                Event::Received(Ok(_)) => vec![],
+                Event::Received(Err(err)) => return Err(err.to_string()),
+                Event::Outgoing(request) => vec![request],
+            };
+            for request in requests {
+                write
+                    .send(Message::Text(request))
+                    .await
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "web")]
+    async fn session(&self, url: &str) -> Result<(), String> {
+        let ws = WebSocket::open(url).map_err(|err| err.to_string())?;
+        while matches!(ws.state(), State::Connecting) {
+            async_std::task::sleep(Duration::from_millis(100)).await;
+        }
+        if !matches!(ws.state(), State::Open) {
+            return Err("Failed to connect".to_string());
+        }
+        let (mut write, read) = ws.split();
+        for request in self.open() {
+            write
+                .send(Message::Text(request))
+                .await
+                .map_err(|err| err.to_string())?;
+        }
+        let mut events = stream::select(
+            read.map(Event::Received)
+                .chain(stream::iter([Event::Closed])),
+            self.outgoing.1.clone().map(Event::Outgoing),
+        );
+        while let Some(event) = events.next().await {
+            let requests = match event {
+                Event::Received(Ok(Message::Text(text))) => {
+                    self.registry.borrow_mut().handle(&text)
+                }
+                Event::Received(Ok(Message::Bytes(_))) => vec![],
+                Event::Received(Err(err)) => return Err(err.to_string()),
+                Event::Closed => return Ok(()),
+                Event::Outgoing(request) => vec![request],
+            };
+            for request in requests {
+                write
+                    .send(Message::Text(request))
+                    .await
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use async_std::channel::Sender;
+use serde_json::{json, Value};
+#[cfg(feature = "desktop")]
+use solana_account_decoder::UiAccount;
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::{account::Account, pubkey::Pubkey, signature::Signature};
+#[cfg(feature = "web")]
+use solana_extra_wasm::account_decoder::UiAccount;
+#[cfg(feature = "desktop")]
+use solana_sdk::{account::Account, pubkey::Pubkey, signature::Signature};
+
+/// What a subscription follows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Topic {
+    Account(Pubkey),
+    /// Ends with the first notification, once the transaction is confirmed.
+    Signature(Signature),
+}
+
+impl Topic {
+    fn subscribe_request(&self, id: u64) -> String {
+        let (method, params) = match self {
+            Topic::Account(address) => (
+                "accountSubscribe",
+                json!([address.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+            ),
+            Topic::Signature(signature) => (
+                "signatureSubscribe",
+                json!([signature.to_string(), { "commitment": "confirmed" }]),
+            ),
+        };
+        request(id, method, params)
+    }
+
+    fn unsubscribe_request(&self, id: u64, subscription: u64) -> String {
+        let method = match self {
+            Topic::Account(_) => "accountUnsubscribe",
+            Topic::Signature(_) => "signatureUnsubscribe",
+        };
+        request(id, method, json!([subscription]))
+    }
+
+    fn parse(&self, value: &Value) -> Option<Update> {
+        match self {
+            Topic::Account(_) => serde_json::from_value::<UiAccount>(value.clone())
+                .ok()?
+                .decode::<Account>()
+                .map(Update::Account),
+            Topic::Signature(_) => Some(Update::Signature(match &value["err"] {
+                Value::Null => Ok(()),
+                err => Err(err.to_string()),
+            })),
+        }
+    }
+}
+
+fn request(id: u64, method: &str, params: Value) -> String {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }).to_string()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Update {
+    Account(Account),
+    /// The transaction was confirmed, with its error if it failed.
+    Signature(Result<(), String>),
+    /// Subscribed again on a new connection. Changes in between were missed.
+    Resubscribed,
+}
+
+/// Every subscription on the connection, and who is listening to it. Holds no socket, so it
+/// outlives reconnects.
+#[derive(Default)]
+pub struct Registry {
+    last_id: u64,
+    listeners: HashMap<Topic, Vec<Sender<Update>>>,
+    /// Subscribe requests waiting for their subscription id, by request id.
+    pending: HashMap<u64, Topic>,
+    /// Confirmed subscriptions, by subscription id.
+    active: HashMap<u64, Topic>,
+}
+
+impl Registry {
+    /// Adds a listener. Returns the request to send, if nobody was following the topic yet.
+    pub fn subscribe(&mut self, topic: Topic, tx: Sender<Update>) -> Option<String> {
+        let listeners = self.listeners.entry(topic).or_default();
+        listeners.push(tx);
+        if listeners.len().eq(&1) {
+            Some(self.subscribe_request(topic))
+        } else {
+            None
+        }
+    }
+
+    /// Forgets the subscriptions of the last connection and returns the requests that make them
+    /// again on a new one.
+    pub fn resubscribe(&mut self) -> Vec<String> {
+        self.pending.clear();
+        self.active.clear();
+        self.listeners.retain(|_, listeners| {
+            listeners.retain(|tx| tx.try_send(Update::Resubscribed).is_ok());
+            !listeners.is_empty()
+        });
+        let topics: Vec<Topic> = self.listeners.keys().copied().collect();
+        topics
+            .into_iter()
+            .map(|topic| self.subscribe_request(topic))
+            .collect()
+    }
+
+    /// Handles a message from the socket. Returns any requests to send back.
+    pub fn handle(&mut self, text: &str) -> Vec<String> {
+        let msg = match serde_json::from_str::<Value>(text) {
+            Ok(msg) => msg,
+            Err(err) => {
+                log::error!("Failed to parse pubsub message: {:?}", err);
+                return vec![];
+            }
+        };
+
+        // Reply to a subscribe request
+        if let Some(id) = msg["id"].as_u64() {
+            if let Some(topic) = self.pending.remove(&id) {
+                match msg["result"].as_u64() {
+                    Some(subscription) if self.listeners.contains_key(&topic) => {
+                        self.active.insert(subscription, topic);
+                    }
+                    // Every listener left while the request was on its way
+                    Some(subscription) => {
+                        return vec![self.unsubscribe_request(topic, subscription)];
+                    }
+                    None => log::error!("Failed to subscribe to {:?}: {}", topic, msg["error"]),
+                }
+            }
+            return vec![];
+        }
+
+        // Notification
+        let params = &msg["params"];
+        let Some(subscription) = params["subscription"].as_u64() else {
+            return vec![];
+        };
+        let Some(topic) = self.active.get(&subscription).copied() else {
+            return vec![];
+        };
+        let Some(update) = topic.parse(&params["result"]["value"]) else {
+            log::error!("Failed to parse notification for {:?}", topic);
+            return vec![];
+        };
+        let listeners = self.listeners.entry(topic).or_default();
+        listeners.retain(|tx| tx.try_send(update.clone()).is_ok());
+        match topic {
+            // The RPC ends these itself after notifying
+            Topic::Signature(_) => {
+                self.active.remove(&subscription);
+                self.listeners.remove(&topic);
+                vec![]
+            }
+            Topic::Account(_) if listeners.is_empty() => {
+                self.active.remove(&subscription);
+                self.listeners.remove(&topic);
+                vec![self.unsubscribe_request(topic, subscription)]
+            }
+            Topic::Account(_) => vec![],
+        }
+    }
+
+    /// Forgets listeners whose receiver was dropped. Returns the requests that end the
+    /// subscriptions nobody follows anymore.
+    pub fn release(&mut self) -> Vec<String> {
+        self.listeners.retain(|_, listeners| {
+            listeners.retain(|tx| !tx.is_closed());
+            !listeners.is_empty()
+        });
+        let released: Vec<(u64, Topic)> = self
+            .active
+            .iter()
+            .filter(|(_, topic)| !self.listeners.contains_key(topic))
+            .map(|(subscription, topic)| (*subscription, *topic))
+            .collect();
+        released
+            .into_iter()
+            .map(|(subscription, topic)| {
+                self.active.remove(&subscription);
+                self.unsubscribe_request(topic, subscription)
+            })
+            .collect()
+    }
+
+    fn subscribe_request(&mut self, topic: Topic) -> String {
+        self.last_id += 1;
+        self.pending.insert(self.last_id, topic);
+        topic.subscribe_request(self.last_id)
+    }
+
+    fn unsubscribe_request(&mut self, topic: Topic, subscription: u64) -> String {
+        self.last_id += 1;
+        topic.unsubscribe_request(self.last_id, subscription)
+    }
+}
@@ -0,0 +1,183 @@
+use async_std::channel::{unbounded, Receiver};
+use serde_json::{json, Value};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+use super::{websocket_url, Registry, Topic, Update};
+
+fn subscribe(registry: &mut Registry, topic: Topic) -> (Option<Value>, Receiver<Update>) {
+    let (tx, rx) = unbounded();
+    let request = registry
+        .subscribe(topic, tx)
+        .map(|request| serde_json::from_str(&request).unwrap());
+    (request, rx)
+}
+
+fn reply(id: &Value, subscription: u64) -> String {
+    json!({ "jsonrpc": "2.0", "result": subscription, "id": id }).to_string()
+}
+
+fn notification(method: &str, subscription: u64, value: Value) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": {
+            "result": { "context": { "slot": 1 }, "value": value },
+            "subscription": subscription,
+        },
+    })
+    .to_string()
+}
+
+fn account_notification(subscription: u64, lamports: u64) -> String {
+    notification(
+        "accountNotification",
+        subscription,
+        json!({
+            "lamports": lamports,
+            "data": ["AQID", "base64"],
+            "owner": "11111111111111111111111111111111",
+            "executable": false,
+            "rentEpoch": 0,
+            "space": 3,
+        }),
+    )
+}
+
+#[test]
+fn subscribes_to_each_topic_once() {
+    let mut registry = Registry::default();
+    let topic = Topic::Account(Pubkey::new_unique());
+    let (request, _rx) = subscribe(&mut registry, topic);
+    let request = request.unwrap();
+    assert_eq!(request["method"], "accountSubscribe");
+    assert_eq!(request["params"][1]["encoding"], "base64");
+    let (request, _rx2) = subscribe(&mut registry, topic);
+    assert!(request.is_none());
+}
+
+#[test]
+fn delivers_account_updates_to_every_listener() {
+    let mut registry = Registry::default();
+    let topic = Topic::Account(Pubkey::new_unique());
+    let (request, rx) = subscribe(&mut registry, topic);
+    let (_, rx2) = subscribe(&mut registry, topic);
+    registry.handle(&reply(&request.unwrap()["id"], 7));
+    assert!(registry.handle(&account_notification(7, 42)).is_empty());
+    for rx in [rx, rx2] {
+        match rx.try_recv() {
+            Ok(Update::Account(account)) => {
+                assert_eq!(account.lamports, 42);
+                assert_eq!(account.data, vec![1, 2, 3]);
+            }
+            update => panic!("unexpected update {:?}", update),
+        }
+    }
+}
+
+#[test]
+fn ignores_unknown_subscriptions() {
+    let mut registry = Registry::default();
+    let (request, rx) = subscribe(&mut registry, Topic::Account(Pubkey::new_unique()));
+    registry.handle(&reply(&request.unwrap()["id"], 7));
+    registry.handle(&account_notification(8, 42));
+    registry.handle("not json");
+    assert!(rx.try_recv().is_err());
+}
+
+#[test]
+fn unsubscribes_once_every_listener_is_gone() {
+    let mut registry = Registry::default();
+    let topic = Topic::Account(Pubkey::new_unique());
+    let (request, rx) = subscribe(&mut registry, topic);
+    registry.handle(&reply(&request.unwrap()["id"], 7));
+    drop(rx);
+    let requests = registry.handle(&account_notification(7, 42));
+    assert_eq!(requests.len(), 1);
+    let request: Value = serde_json::from_str(&requests[0]).unwrap();
+    assert_eq!(request["method"], "accountUnsubscribe");
+    assert_eq!(request["params"], json!([7]));
+    let (request, _rx) = subscribe(&mut registry, topic);
+    assert!(request.is_some());
+}
+
+#[test]
+fn releases_topics_whose_listeners_were_dropped() {
+    let mut registry = Registry::default();
+    let topic = Topic::Signature(Signature::new_unique());
+    let (request, rx) = subscribe(&mut registry, topic);
+    let (_, rx2) = subscribe(&mut registry, topic);
+    registry.handle(&reply(&request.unwrap()["id"], 7));
+    drop(rx);
+    assert!(registry.release().is_empty());
+    drop(rx2);
+    let requests = registry.release();
+    assert_eq!(requests.len(), 1);
+    let request: Value = serde_json::from_str(&requests[0]).unwrap();
+    assert_eq!(request["method"], "signatureUnsubscribe");
+    assert_eq!(request["params"], json!([7]));
+    assert!(registry.release().is_empty());
+}
+
+#[test]
+fn unsubscribes_when_released_before_the_reply() {
+    let mut registry = Registry::default();
+    let topic = Topic::Account(Pubkey::new_unique());
+    let (request, rx) = subscribe(&mut registry, topic);
+    drop(rx);
+    assert!(registry.release().is_empty());
+    let requests = registry.handle(&reply(&request.unwrap()["id"], 7));
+    assert_eq!(requests.len(), 1);
+    let request: Value = serde_json::from_str(&requests[0]).unwrap();
+    assert_eq!(request["method"], "accountUnsubscribe");
+    assert_eq!(request["params"], json!([7]));
+}
+
+#[test]
+fn signature_subscriptions_end_with_their_notification() {
+    let mut registry = Registry::default();
+    let topic = Topic::Signature(Signature::new_unique());
+    let (request, rx) = subscribe(&mut registry, topic);
+    let request = request.unwrap();
+    assert_eq!(request["method"], "signatureSubscribe");
+    registry.handle(&reply(&request["id"], 9));
+    let failed = json!({ "err": { "InstructionError": [0, "InvalidArgument"] } });
+    assert!(registry
+        .handle(&notification("signatureNotification", 9, failed))
+        .is_empty());
+    assert!(matches!(rx.try_recv(), Ok(Update::Signature(Err(_)))));
+    assert!(rx.is_closed());
+}
+
+#[test]
+fn resubscribes_after_reconnecting() {
+    let mut registry = Registry::default();
+    let (request, rx) = subscribe(&mut registry, Topic::Account(Pubkey::new_unique()));
+    let (_, closed) = subscribe(&mut registry, Topic::Account(Pubkey::new_unique()));
+    drop(closed);
+    registry.handle(&reply(&request.unwrap()["id"], 7));
+
+    let requests = registry.resubscribe();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(rx.try_recv(), Ok(Update::Resubscribed));
+
+    // Subscription ids from the last connection mean nothing on the new one
+    registry.handle(&account_notification(7, 42));
+    assert!(rx.try_recv().is_err());
+    let request: Value = serde_json::from_str(&requests[0]).unwrap();
+    registry.handle(&reply(&request["id"], 3));
+    registry.handle(&account_notification(3, 42));
+    assert!(matches!(rx.try_recv(), Ok(Update::Account(_))));
+}
+
+#[test]
+fn derives_websocket_url_from_rpc_url() {
+    assert_eq!(
+        websocket_url("https://rpc.example.com/mainnet?key=1").unwrap(),
+        "wss://rpc.example.com/mainnet?key=1"
+    );
+    assert_eq!(
+        websocket_url("http://localhost:8899").unwrap(),
+        "ws://localhost:8900/"
+    );
+    assert!(websocket_url("not a url").is_none());
+}
@@ -3,7 +3,8 @@ use dioxus_router::prelude::*;
 
 use crate::components::{
     Claim, Download, ExportKey, Home, ImportKey, Landing, Miner, Navbar, MarsTokenomics,
-    PageNotFound, Send, Settings, SimpleNavbar, Stats, Tx, User, WhatIsMining,
+    NotificationHistory, PageNotFound, Send, Settings, SimpleNavbar, Stats, Sweep, Tx, User, WatchlistDashboard,
+    WhatIsMining,
 };
 
 #[rustfmt::skip]
@@ -35,6 +36,8 @@ pub enum Route {
             ExportKey {},
             #[route("/settings/import-key")]
             ImportKey {},
+            #[route("/settings/sweep")]
+            Sweep {},
             #[route("/send/:to")]
             Send {
                 to: String
@@ -47,6 +50,10 @@ pub enum Route {
             User {
                 id: String,
             },
+            #[route("/watchlist")]
+            WatchlistDashboard {},
+            #[route("/notifications")]
+            NotificationHistory {},
         #[end_layout]
     #[end_layout]
 
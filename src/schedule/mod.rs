@@ -0,0 +1,187 @@
+//! When the miner may run: weekly time windows, plus daily budgets of hashes, fees and solutions.
+
+#[cfg(all(test, feature = "desktop"))]
+mod tests;
+
+use std::time::Duration;
+
+use chrono::{Datelike, NaiveDateTime, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Weekdays in the order of the schedule's days.
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+/// How often the schedule is checked, and hashes are counted against the budget.
+pub const SCHEDULE_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The hours one weekday mines, in minutes after midnight. A window that ends before it starts
+/// runs past midnight into the next day, and one that ends where it starts lasts all day.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct DayWindow {
+    pub enabled: bool,
+    pub start: u16,
+    pub end: u16,
+}
+
+impl Default for DayWindow {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            start: 0,
+            end: 0,
+        }
+    }
+}
+
+impl DayWindow {
+    /// Whether the minute falls in the part of the window on its own day.
+    fn contains(&self, minute: u16) -> bool {
+        self.enabled
+            && match self.start.cmp(&self.end) {
+                std::cmp::Ordering::Equal => true,
+                std::cmp::Ordering::Less => minute.ge(&self.start) && minute.lt(&self.end),
+                std::cmp::Ordering::Greater => minute.ge(&self.start),
+            }
+    }
+
+    /// Whether the minute falls in the part of the window carried over from the day before.
+    fn carries_over(&self, minute: u16) -> bool {
+        self.enabled && self.start.gt(&self.end) && minute.lt(&self.end)
+    }
+}
+
+/// Limits on when the miner runs. Every limit of 0 is off.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MiningSchedule {
+    pub enabled: bool,
+    /// One window per weekday, Monday first.
+    pub days: [DayWindow; 7],
+    /// Hashes per day. Desktop only.
+    pub daily_hash_budget: u64,
+    /// Lamports of fees and tips per day.
+    pub daily_fee_budget: u64,
+    /// Accepted solutions per day.
+    pub daily_solution_limit: u32,
+}
+
+impl MiningSchedule {
+    pub fn is_open(&self, now: NaiveDateTime) -> bool {
+        let minute = (now.hour() * 60 + now.minute()) as u16;
+        let weekday = now.weekday();
+        self.day(weekday).contains(minute) || self.day(weekday.pred()).carries_over(minute)
+    }
+
+    pub fn day(&self, weekday: Weekday) -> &DayWindow {
+        &self.days[weekday.num_days_from_monday() as usize]
+    }
+
+    pub fn day_mut(&mut self, weekday: Weekday) -> &mut DayWindow {
+        &mut self.days[weekday.num_days_from_monday() as usize]
+    }
+
+    /// Why the miner should not run now, if anything. Usage from an earlier day does not count.
+    pub fn stop_reason(&self, now: NaiveDateTime, usage: &MiningUsage) -> Option<StopReason> {
+        if !self.enabled {
+            return None;
+        }
+        if !self.is_open(now) {
+            return Some(StopReason::OutsideWindow);
+        }
+        let mut usage = usage.clone();
+        usage.roll_over(now);
+        if self.daily_hash_budget.gt(&0) && usage.hashes.ge(&self.daily_hash_budget) {
+            return Some(StopReason::HashBudget);
+        }
+        if self.daily_fee_budget.gt(&0) && usage.fees.ge(&self.daily_fee_budget) {
+            return Some(StopReason::FeeBudget);
+        }
+        if self.daily_solution_limit.gt(&0) && usage.solutions.ge(&self.daily_solution_limit) {
+            return Some(StopReason::SolutionLimit);
+        }
+        None
+    }
+}
+
+/// Why the schedule stopped the miner.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    OutsideWindow,
+    HashBudget,
+    FeeBudget,
+    SolutionLimit,
+}
+
+impl StopReason {
+    pub fn message(&self) -> &'static str {
+        match self {
+            StopReason::OutsideWindow => "Outside of the mining schedule",
+            StopReason::HashBudget => "Reached the daily hash budget",
+            StopReason::FeeBudget => "Reached the daily fee budget",
+            StopReason::SolutionLimit => "Reached the daily solution limit",
+        }
+    }
+}
+
+/// Work done on one day, counted against the schedule's budgets.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MiningUsage {
+    /// Days since the common era, in local time.
+    pub day: i32,
+    pub hashes: u64,
+    /// Lamports of fees and tips.
+    pub fees: u64,
+    pub solutions: u32,
+}
+
+impl MiningUsage {
+    /// Starts counting from zero on a new day.
+    pub fn roll_over(&mut self, now: NaiveDateTime) {
+        let day = now.date().num_days_from_ce();
+        if self.day.ne(&day) {
+            *self = MiningUsage {
+                day,
+                ..Default::default()
+            };
+        }
+    }
+
+    pub fn record_hashes(&mut self, now: NaiveDateTime, hashes: u64) {
+        self.roll_over(now);
+        self.hashes = self.hashes.saturating_add(hashes);
+    }
+
+    pub fn record_solution(&mut self, now: NaiveDateTime, fee: u64) {
+        self.record_fees(now, fee);
+        self.solutions = self.solutions.saturating_add(1);
+    }
+
+    /// Counts fees paid without a solution landing, such as by failed attempts.
+    pub fn record_fees(&mut self, now: NaiveDateTime, fees: u64) {
+        self.roll_over(now);
+        self.fees = self.fees.saturating_add(fees);
+    }
+}
+
+/// Formats minutes after midnight as `HH:MM`, the value of a time input.
+pub fn format_minute(minute: u16) -> String {
+    format!("{:02}:{:02}", minute / 60, minute % 60)
+}
+
+/// Parses the `HH:MM` value of a time input.
+pub fn parse_minute(value: &str) -> Option<u16> {
+    let (hours, minutes) = value.split_once(':')?;
+    let hours: u16 = hours.parse().ok()?;
+    let minutes: u16 = minutes.get(..2)?.parse().ok()?;
+    (hours.lt(&24) && minutes.lt(&60)).then_some(hours * 60 + minutes)
+}
@@ -0,0 +1,176 @@
+use chrono::{NaiveDate, NaiveDateTime, Weekday};
+
+use super::{format_minute, parse_minute, DayWindow, MiningSchedule, MiningUsage, StopReason};
+
+/// 2024-06-03 was a Monday.
+fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 6, day)
+        .unwrap()
+        .and_hms_opt(hour, minute, 0)
+        .unwrap()
+}
+
+fn window(start: u16, end: u16) -> DayWindow {
+    DayWindow {
+        enabled: true,
+        start,
+        end,
+    }
+}
+
+fn schedule() -> MiningSchedule {
+    MiningSchedule {
+        enabled: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn default_schedule_is_always_open() {
+    let schedule = schedule();
+    for day in 3..10 {
+        assert!(schedule.is_open(at(day, 0, 0)));
+        assert!(schedule.is_open(at(day, 23, 59)));
+    }
+}
+
+#[test]
+fn window_within_a_day() {
+    let mut schedule = schedule();
+    *schedule.day_mut(Weekday::Mon) = window(9 * 60, 17 * 60);
+    assert!(!schedule.is_open(at(3, 8, 59)));
+    assert!(schedule.is_open(at(3, 9, 0)));
+    assert!(schedule.is_open(at(3, 16, 59)));
+    assert!(!schedule.is_open(at(3, 17, 0)));
+}
+
+#[test]
+fn window_past_midnight_carries_over() {
+    let mut schedule = schedule();
+    schedule.days = [DayWindow {
+        enabled: false,
+        ..Default::default()
+    }; 7];
+    // Cheap night rate from Friday 22:00 to Saturday 06:00
+    *schedule.day_mut(Weekday::Fri) = window(22 * 60, 6 * 60);
+    assert!(!schedule.is_open(at(7, 21, 59)));
+    assert!(schedule.is_open(at(7, 22, 0)));
+    assert!(schedule.is_open(at(8, 5, 59)));
+    assert!(!schedule.is_open(at(8, 6, 0)));
+    assert!(!schedule.is_open(at(8, 22, 0)));
+    // Friday morning belongs to Thursday's window, which is off
+    assert!(!schedule.is_open(at(7, 5, 0)));
+}
+
+#[test]
+fn disabled_schedule_never_stops() {
+    let schedule = MiningSchedule {
+        daily_solution_limit: 1,
+        ..Default::default()
+    };
+    let usage = MiningUsage {
+        solutions: 10,
+        ..Default::default()
+    };
+    assert_eq!(schedule.stop_reason(at(3, 12, 0), &usage), None);
+}
+
+#[test]
+fn budgets_stop_the_miner() {
+    let now = at(3, 12, 0);
+    let schedule = MiningSchedule {
+        daily_hash_budget: 1_000,
+        daily_fee_budget: 50_000,
+        daily_solution_limit: 3,
+        ..schedule()
+    };
+    let mut usage = MiningUsage::default();
+    usage.record_hashes(now, 999);
+    usage.record_solution(now, 20_000);
+    assert_eq!(schedule.stop_reason(now, &usage), None);
+    usage.record_hashes(now, 1);
+    assert_eq!(
+        schedule.stop_reason(now, &usage),
+        Some(StopReason::HashBudget)
+    );
+    let schedule = MiningSchedule {
+        daily_hash_budget: 0,
+        ..schedule
+    };
+    usage.record_solution(now, 20_000);
+    assert_eq!(schedule.stop_reason(now, &usage), None);
+    usage.record_solution(now, 20_000);
+    assert_eq!(
+        schedule.stop_reason(now, &usage),
+        Some(StopReason::FeeBudget)
+    );
+    let schedule = MiningSchedule {
+        daily_fee_budget: 0,
+        ..schedule
+    };
+    assert_eq!(
+        schedule.stop_reason(now, &usage),
+        Some(StopReason::SolutionLimit)
+    );
+}
+
+#[test]
+fn fees_of_failed_attempts_count_toward_budget() {
+    let now = at(3, 12, 0);
+    let schedule = MiningSchedule {
+        daily_fee_budget: 10_000,
+        ..schedule()
+    };
+    let mut usage = MiningUsage::default();
+    usage.record_fees(now, 5_000);
+    assert_eq!(schedule.stop_reason(now, &usage), None);
+    usage.record_fees(now, 5_000);
+    assert_eq!(
+        schedule.stop_reason(now, &usage),
+        Some(StopReason::FeeBudget)
+    );
+    assert_eq!(usage.solutions, 0);
+}
+
+#[test]
+fn usage_resets_each_day() {
+    let schedule = MiningSchedule {
+        daily_solution_limit: 1,
+        ..schedule()
+    };
+    let mut usage = MiningUsage::default();
+    usage.record_solution(at(3, 23, 0), 5_000);
+    assert_eq!(
+        schedule.stop_reason(at(3, 23, 30), &usage),
+        Some(StopReason::SolutionLimit)
+    );
+    assert_eq!(schedule.stop_reason(at(4, 0, 0), &usage), None);
+    usage.record_hashes(at(4, 0, 0), 10);
+    assert_eq!((usage.hashes, usage.fees, usage.solutions), (10, 0, 0));
+}
+
+#[test]
+fn outside_window_comes_first() {
+    let mut schedule = MiningSchedule {
+        daily_solution_limit: 1,
+        ..schedule()
+    };
+    *schedule.day_mut(Weekday::Mon) = window(60, 120);
+    let usage = MiningUsage::default();
+    assert_eq!(
+        schedule.stop_reason(at(3, 12, 0), &usage),
+        Some(StopReason::OutsideWindow)
+    );
+}
+
+#[test]
+fn time_inputs_round_trip() {
+    assert_eq!(parse_minute("06:30"), Some(390));
+    assert_eq!(parse_minute("23:59:00"), Some(23 * 60 + 59));
+    assert_eq!(parse_minute("24:00"), None);
+    assert_eq!(parse_minute("7"), None);
+    assert_eq!(format_minute(390), "06:30");
+    for minute in [0, 59, 600, 1439] {
+        assert_eq!(parse_minute(&format_minute(minute)), Some(minute));
+    }
+}
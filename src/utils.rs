@@ -11,3 +11,8 @@ pub fn asset_path(relative_path: &str) -> String {
 pub fn asset_path(relative_path: &str) -> String {
     relative_path.to_string()
 }
+
+/// Converts an amount in the token's smallest unit to Mars.
+pub fn token_amount(amount: u64) -> f64 {
+    (amount as f64) / 10f64.powf(mars::TOKEN_DECIMALS as f64)
+}
@@ -0,0 +1,155 @@
+use std::fmt;
+
+#[cfg(feature = "web")]
+use gloo_storage::{errors::StorageError, LocalStorage, Storage};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+#[cfg(feature = "web")]
+use solana_client_wasm::solana_sdk::{bs58, signature::Keypair, signer::Signer};
+#[cfg(feature = "desktop")]
+use solana_sdk::{bs58, signature::Keypair, signer::Signer};
+
+#[cfg(feature = "desktop")]
+use crate::file::{get_value, set_key_value, set_key_values};
+
+const KEYPAIR_KEY: &str = "keypair";
+const WALLET_KEY: &str = "wallet";
+
+/// Saved next to the keypair, so a lost keypair is noticed instead of silently replaced.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct WalletRecord {
+    pub pubkey: String,
+    #[serde(default)]
+    pub is_backed_up: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum WalletError {
+    /// No wallet has been created on this device.
+    NotCreated,
+    /// A wallet existed, but its keypair is gone or was replaced by another one.
+    Missing(WalletRecord),
+    /// Storage could not be read or written.
+    Storage(String),
+}
+
+impl fmt::Display for WalletError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WalletError::NotCreated => write!(f, "No wallet has been created"),
+            WalletError::Missing(record) => write!(f, "Keypair for {} is missing", record.pubkey),
+            WalletError::Storage(err) => write!(f, "Failed to access wallet: {}", err),
+        }
+    }
+}
+
+/// Loads the stored keypair. Never generates one.
+pub fn load_wallet() -> Result<(Keypair, WalletRecord), WalletError> {
+    let record = read::<WalletRecord>(WALLET_KEY)?;
+    let Some(keypair) = read::<String>(KEYPAIR_KEY)? else {
+        return Err(match record {
+            Some(record) => WalletError::Missing(record),
+            None => WalletError::NotCreated,
+        });
+    };
+    let keypair = parse_keypair(&keypair)
+        .ok_or_else(|| WalletError::Storage("Stored keypair is invalid".to_string()))?;
+
+    // A keypair replaced outside the app is not the wallet the user backed up
+    let pubkey = keypair.pubkey().to_string();
+    let record = match record {
+        Some(record) if record.pubkey.eq(&pubkey) => record,
+        Some(record) => return Err(WalletError::Missing(record)),
+        // Wallets created before the record was kept
+        None => {
+            let record = WalletRecord {
+                pubkey,
+                is_backed_up: false,
+            };
+            write(WALLET_KEY, &record)?;
+            record
+        }
+    };
+    Ok((keypair, record))
+}
+
+/// Generates and saves a new keypair, replacing any existing wallet.
+pub fn create_wallet() -> Result<Keypair, WalletError> {
+    let keypair = Keypair::new();
+    save_wallet(&keypair, false)?;
+    Ok(keypair)
+}
+
+/// Saves a keypair the user already holds, replacing any existing wallet.
+pub fn import_wallet(keypair: &Keypair) -> Result<(), WalletError> {
+    save_wallet(keypair, true)
+}
+
+pub fn mark_backed_up() -> Result<(), WalletError> {
+    let (_, mut record) = load_wallet()?;
+    if !record.is_backed_up {
+        record.is_backed_up = true;
+        write(WALLET_KEY, &record)?;
+    }
+    Ok(())
+}
+
+/// Decodes a base58 private key, as exported from this app.
+pub fn parse_keypair(value: &str) -> Option<Keypair> {
+    let bytes = bs58::decode(value.trim()).into_vec().ok()?;
+    Keypair::from_bytes(&bytes).ok()
+}
+
+fn save_wallet(keypair: &Keypair, is_backed_up: bool) -> Result<(), WalletError> {
+    let record = WalletRecord {
+        pubkey: keypair.pubkey().to_string(),
+        is_backed_up,
+    };
+    write_wallet(&record, &keypair.to_base58_string())
+}
+
+/// Local storage has no transactions. The record goes first, so a failed write leaves the
+/// old keypair to be recovered instead of a new one under the old record.
+#[cfg(feature = "web")]
+fn write_wallet(record: &WalletRecord, keypair: &str) -> Result<(), WalletError> {
+    write(WALLET_KEY, record)?;
+    write(KEYPAIR_KEY, &keypair)
+}
+
+#[cfg(feature = "desktop")]
+fn write_wallet(record: &WalletRecord, keypair: &str) -> Result<(), WalletError> {
+    let record =
+        serde_json::to_value(record).map_err(|err| WalletError::Storage(err.to_string()))?;
+    set_key_values(&[(WALLET_KEY, record), (KEYPAIR_KEY, keypair.into())])
+        .map_err(|err| WalletError::Storage(err.to_string()))
+}
+
+#[cfg(feature = "web")]
+fn read<T: DeserializeOwned>(key: &str) -> Result<Option<T>, WalletError> {
+    match LocalStorage::get(key) {
+        Ok(value) => Ok(Some(value)),
+        Err(StorageError::KeyNotFound(_)) => Ok(None),
+        Err(err) => Err(WalletError::Storage(err.to_string())),
+    }
+}
+
+#[cfg(feature = "desktop")]
+fn read<T: Serialize + DeserializeOwned + 'static>(key: &str) -> Result<Option<T>, WalletError> {
+    match get_value(key) {
+        Ok(value) => Ok(Some(value)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(WalletError::Storage(err.to_string())),
+    }
+}
+
+#[cfg(feature = "web")]
+fn write<T: Serialize>(key: &str, value: &T) -> Result<(), WalletError> {
+    LocalStorage::set(key, value).map_err(|err| WalletError::Storage(err.to_string()))
+}
+
+#[cfg(feature = "desktop")]
+fn write<T: Serialize + DeserializeOwned + 'static>(
+    key: &str,
+    value: &T,
+) -> Result<(), WalletError> {
+    set_key_value(key, value).map_err(|err| WalletError::Storage(err.to_string()))
+}
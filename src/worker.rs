@@ -3,7 +3,10 @@ use serde_wasm_bindgen::{from_value, to_value};
 use wasm_bindgen::prelude::*;
 use web_sys::{DedicatedWorkerGlobalScope, MessageEvent, Worker, WorkerOptions, WorkerType};
 
-use crate::miner::{find_next_hash, MineRequest, MiningResult};
+use crate::{
+    hashing::kernel_name,
+    miner::{find_next_hash, MineRequest, MiningResult},
+};
 
 pub fn create_worker(ch: &UseChannel<MiningResult>) -> Worker {
     let worker = Worker::new_with_options("worker.js", &worker_options()).unwrap();
@@ -36,7 +39,7 @@ pub fn create_worker(ch: &UseChannel<MiningResult>) -> Worker {
 
 #[wasm_bindgen]
 pub fn start_worker() {
-    log::info!("Starting webworker");
+    log::info!("Starting webworker with the {} kernel", kernel_name());
 
     let self_ = js_sys::global();
     let js_value = std::ops::Deref::deref(&self_);
@@ -0,0 +1,307 @@
+//! Mining threads that live for the whole session. New challenges are handed to the running
+//! threads instead of spawning fresh ones, and the number of threads hashing follows the power
+//! level, the battery and the cpu temperature.
+
+mod os;
+#[cfg(test)]
+mod tests;
+mod throttle;
+
+use std::{
+    ops::Range,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use async_std::channel::{bounded, Receiver, Sender};
+use solana_sdk::{keccak::Hash as KeccakHash, pubkey::Pubkey};
+
+pub use throttle::*;
+
+use crate::{
+    hashing::{partition, search, MiningResult},
+    hooks::PowerLevel,
+    miner::MiningSettings,
+};
+
+/// How often the battery and temperature are read.
+const THROTTLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Hashes computed by the desktop miner since launch.
+static HASH_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Total hashes computed since launch. Sample it over time to get the hashrate.
+pub fn hash_count() -> u64 {
+    HASH_COUNT.load(Ordering::Relaxed)
+}
+
+/// Threads to mine on at a power level, given as a percent of the cpus.
+pub fn threads_for(power_level: PowerLevel, cpus: usize) -> usize {
+    let threads = (cpus as f64 * power_level.0 as f64 / 100f64).round() as usize;
+    threads.clamp(1, cpus.max(1))
+}
+
+/// Settings that only take effect when the threads are spawned.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolOptions {
+    pub threads: usize,
+    pub pin_threads: bool,
+    pub low_priority: bool,
+}
+
+impl PoolOptions {
+    pub fn new(settings: &MiningSettings) -> Self {
+        Self {
+            threads: num_cpus::get(),
+            pin_threads: settings.pin_threads,
+            low_priority: settings.low_priority,
+        }
+    }
+}
+
+/// One challenge, split into a nonce range per thread.
+#[derive(Clone)]
+struct Job {
+    generation: u64,
+    challenge: KeccakHash,
+    difficulty: KeccakHash,
+    signer: Pubkey,
+    ranges: Vec<Range<u64>>,
+    result: Sender<MiningResult>,
+}
+
+struct Limits {
+    /// Threads the power level asks for.
+    threads: usize,
+    settings: MiningSettings,
+}
+
+struct Shared {
+    job: Mutex<Option<Job>>,
+    /// Wakes threads for a new job, a higher limit or shutdown.
+    wake: Condvar,
+    /// Bumped whenever the current job is replaced, solved or cancelled.
+    generation: AtomicU64,
+    /// Threads allowed to hash right now. Threads at or above this index wait.
+    active: AtomicUsize,
+    limits: Mutex<Limits>,
+    shutdown: AtomicBool,
+}
+
+impl Shared {
+    fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::Relaxed).eq(&generation)
+            && !self.shutdown.load(Ordering::Relaxed)
+    }
+
+    /// Ends the job. Only the first caller for a generation gets true.
+    fn finish(&self, generation: u64) -> bool {
+        let mut job = self.job.lock().unwrap();
+        let finished = self
+            .generation
+            .compare_exchange(
+                generation,
+                generation + 1,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok();
+        if finished {
+            *job = None;
+        }
+        finished
+    }
+
+    fn set_active(&self, active: usize) {
+        let _job = self.job.lock().unwrap();
+        if self.active.swap(active, Ordering::Relaxed).ne(&active) {
+            self.wake.notify_all();
+        }
+    }
+
+    /// Blocks a throttled thread until it may hash again. False once the job is over.
+    fn wait_turn(&self, index: usize, generation: u64) -> bool {
+        if index < self.active.load(Ordering::Relaxed) {
+            return self.is_current(generation);
+        }
+        let mut job = self.job.lock().unwrap();
+        while self.is_current(generation) && index >= self.active.load(Ordering::Relaxed) {
+            job = self.wake.wait(job).unwrap();
+        }
+        self.is_current(generation)
+    }
+}
+
+/// Persistent mining threads, plus one that throttles them.
+pub struct WorkerPool {
+    options: PoolOptions,
+    shared: Arc<Shared>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    pub fn new(options: PoolOptions, power_level: PowerLevel, settings: MiningSettings) -> Self {
+        let threads = threads_for(power_level, options.threads);
+        let shared = Arc::new(Shared {
+            job: Mutex::new(None),
+            wake: Condvar::new(),
+            generation: AtomicU64::new(0),
+            active: AtomicUsize::new(threads),
+            limits: Mutex::new(Limits { threads, settings }),
+            shutdown: AtomicBool::new(false),
+        });
+        let mut handles: Vec<_> = (0..options.threads)
+            .map(|index| {
+                let shared = shared.clone();
+                std::thread::Builder::new()
+                    .name(format!("miner-{}", index))
+                    .spawn(move || work(index, options, shared))
+                    .unwrap()
+            })
+            .collect();
+        handles.push({
+            let shared = shared.clone();
+            std::thread::Builder::new()
+                .name("miner-throttle".to_string())
+                .spawn(move || monitor(options.threads, shared))
+                .unwrap()
+        });
+        Self {
+            options,
+            shared,
+            handles,
+        }
+    }
+
+    pub fn options(&self) -> PoolOptions {
+        self.options
+    }
+
+    /// Updates the settings that apply without restarting the threads.
+    pub fn configure(&self, power_level: PowerLevel, settings: MiningSettings) {
+        let threads = threads_for(power_level, self.options.threads);
+        let mut limits = self.shared.limits.lock().unwrap();
+        if limits.threads.ne(&threads) {
+            // Start at the new level, the throttle lowers it again if needed
+            self.shared.set_active(threads);
+        }
+        *limits = Limits { threads, settings };
+    }
+
+    /// Replaces the current job. The receiver gets the solution, or closes if the job is replaced first.
+    pub fn mine(
+        &self,
+        challenge: KeccakHash,
+        difficulty: KeccakHash,
+        signer: Pubkey,
+    ) -> Receiver<MiningResult> {
+        let (tx, rx) = bounded(1);
+        let mut job = self.shared.job.lock().unwrap();
+        let generation = self.shared.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        *job = Some(Job {
+            generation,
+            challenge,
+            difficulty,
+            signer,
+            ranges: partition(self.options.threads as u64),
+            result: tx,
+        });
+        self.shared.wake.notify_all();
+        rx
+    }
+
+    /// Stops hashing the current job. The threads keep running, waiting for the next one.
+    pub fn cancel(&self) {
+        let mut job = self.shared.job.lock().unwrap();
+        self.shared.generation.fetch_add(1, Ordering::Relaxed);
+        *job = None;
+        self.shared.wake.notify_all();
+    }
+}
+
+impl Drop for WorkerPool {
+    fn drop(&mut self) {
+        {
+            let _job = self.shared.job.lock().unwrap();
+            self.shared.shutdown.store(true, Ordering::Relaxed);
+            self.shared.wake.notify_all();
+        }
+        for handle in self.handles.drain(..) {
+            handle.join().ok();
+        }
+    }
+}
+
+fn work(index: usize, options: PoolOptions, shared: Arc<Shared>) {
+    if options.pin_threads {
+        os::pin_to_core(index);
+    }
+    if options.low_priority {
+        os::lower_priority();
+    }
+    let mut last = 0;
+    loop {
+        let job = {
+            let mut job = shared.job.lock().unwrap();
+            loop {
+                if shared.shutdown.load(Ordering::Relaxed) {
+                    return;
+                }
+                match job.as_ref() {
+                    Some(next) if next.generation.ne(&last) => break next.clone(),
+                    _ => job = shared.wake.wait(job).unwrap(),
+                }
+            }
+        };
+        last = job.generation;
+        if !shared.wait_turn(index, job.generation) {
+            continue;
+        }
+        let res = search(
+            &job.challenge,
+            &job.signer,
+            &job.difficulty,
+            job.ranges[index].clone(),
+            |hashes| {
+                HASH_COUNT.fetch_add(hashes, Ordering::Relaxed);
+                shared.wait_turn(index, job.generation)
+            },
+        );
+        if let Some(res) = res {
+            if shared.finish(job.generation) {
+                job.result.try_send(res).ok();
+            }
+        }
+    }
+}
+
+/// Adjusts the active threads to the battery and temperature.
+fn monitor(cpus: usize, shared: Arc<Shared>) {
+    let sysfs = PathBuf::from(SYSFS_ROOT);
+    let mut throttle = Throttle::default();
+    while !shared.shutdown.load(Ordering::Relaxed) {
+        let readings = Readings::read(&sysfs);
+        {
+            let limits = shared.limits.lock().unwrap();
+            let active = throttle.limit(limits.threads, cpus, &limits.settings, &readings);
+            if active.ne(&shared.active.load(Ordering::Relaxed)) {
+                log::info!("Mining on {} threads ({:?})", active, readings);
+            }
+            shared.set_active(active);
+        }
+        let job = shared.job.lock().unwrap();
+        drop(
+            shared
+                .wake
+                .wait_timeout_while(job, THROTTLE_INTERVAL, |_| {
+                    !shared.shutdown.load(Ordering::Relaxed)
+                })
+                .unwrap(),
+        );
+    }
+}
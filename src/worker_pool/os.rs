@@ -0,0 +1,46 @@
+//! Per-thread scheduling hints. Each call applies to the calling thread only.
+
+/// Niceness of mining threads on Linux, where 19 is the lowest priority.
+#[cfg(target_os = "linux")]
+const NICENESS: libc::c_int = 10;
+
+/// Keeps the calling thread on one core, chosen round robin by index.
+pub fn pin_to_core(index: usize) {
+    let Some(cores) = core_affinity::get_core_ids().filter(|cores| !cores.is_empty()) else {
+        log::warn!("Pinning mining threads is not supported on this platform");
+        return;
+    };
+    let core = cores[index % cores.len()];
+    if !core_affinity::set_for_current(core) {
+        log::warn!("Failed to pin mining thread {} to core {}", index, core.id);
+    }
+}
+
+/// Lowers the calling thread's priority, so other apps stay responsive while mining.
+#[cfg(target_os = "linux")]
+pub fn lower_priority() {
+    // On Linux, the process priority of the calling thread is the thread's own
+    let res = unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, NICENESS) };
+    if res.ne(&0) {
+        log::warn!(
+            "Failed to lower mining thread priority: {}",
+            std::io::Error::last_os_error()
+        );
+    }
+}
+
+/// Lowers the calling thread's priority, so other apps stay responsive while mining.
+#[cfg(target_os = "macos")]
+pub fn lower_priority() {
+    let res =
+        unsafe { libc::pthread_set_qos_class_self_np(libc::qos_class_t::QOS_CLASS_UTILITY, 0) };
+    if res.ne(&0) {
+        log::warn!("Failed to lower mining thread priority: {}", res);
+    }
+}
+
+/// Lowers the calling thread's priority, so other apps stay responsive while mining.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn lower_priority() {
+    log::warn!("Lowering mining thread priority is not supported on this platform");
+}
@@ -0,0 +1,158 @@
+use std::{fs, path::PathBuf};
+
+use async_std::task::block_on;
+use solana_sdk::{keccak::Hash as KeccakHash, pubkey::Pubkey};
+
+use super::{threads_for, PoolOptions, Readings, Throttle, WorkerPool};
+use crate::{hashing::validate, hooks::PowerLevel, miner::MiningSettings};
+
+fn sysfs(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("mars-sysfs-{}-{}", name, std::process::id()));
+    fs::remove_dir_all(&root).ok();
+    for (path, contents) in files {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+    root
+}
+
+fn hot(temperature: f32) -> Readings {
+    Readings {
+        on_battery: false,
+        temperature: Some(temperature),
+    }
+}
+
+fn pool(threads: usize) -> WorkerPool {
+    let options = PoolOptions {
+        threads,
+        pin_threads: false,
+        low_priority: false,
+    };
+    WorkerPool::new(options, PowerLevel(100), MiningSettings::default())
+}
+
+#[test]
+fn power_level_is_a_percent_of_cpus() {
+    assert_eq!(threads_for(PowerLevel(100), 8), 8);
+    assert_eq!(threads_for(PowerLevel(50), 8), 4);
+    assert_eq!(threads_for(PowerLevel(10), 8), 1);
+    assert_eq!(threads_for(PowerLevel(0), 8), 1);
+    assert_eq!(threads_for(PowerLevel(200), 8), 8);
+}
+
+#[test]
+fn reads_laptop_on_battery() {
+    let root = sysfs(
+        "battery",
+        &[
+            ("class/power_supply/AC/type", "Mains\n"),
+            ("class/power_supply/AC/online", "0\n"),
+            ("class/power_supply/BAT0/type", "Battery\n"),
+            ("class/power_supply/BAT0/status", "Discharging\n"),
+            ("class/thermal/thermal_zone0/temp", "48000\n"),
+            ("class/thermal/thermal_zone1/temp", "91500\n"),
+            ("class/thermal/cooling_device0/temp", "120000\n"),
+        ],
+    );
+    assert_eq!(
+        Readings::read(&root),
+        Readings {
+            on_battery: true,
+            temperature: Some(91.5),
+        }
+    );
+}
+
+#[test]
+fn reads_laptop_on_charger() {
+    let root = sysfs(
+        "charger",
+        &[
+            ("class/power_supply/AC/type", "Mains\n"),
+            ("class/power_supply/AC/online", "1\n"),
+            ("class/power_supply/BAT0/type", "Battery\n"),
+            ("class/power_supply/BAT0/status", "Charging\n"),
+            // Sensors report nonsense when missing
+            ("class/thermal/thermal_zone0/temp", "-273000\n"),
+        ],
+    );
+    assert_eq!(Readings::read(&root), Readings::default());
+}
+
+#[test]
+fn missing_sysfs_is_never_throttled() {
+    let root = sysfs("missing", &[]);
+    assert_eq!(Readings::read(&root), Readings::default());
+}
+
+#[test]
+fn battery_lowers_power_level() {
+    let settings = MiningSettings::default();
+    let on_battery = Readings {
+        on_battery: true,
+        temperature: None,
+    };
+    let mut throttle = Throttle::default();
+    assert_eq!(throttle.limit(8, 10, &settings, &on_battery), 3);
+    assert_eq!(throttle.limit(2, 10, &settings, &on_battery), 2);
+    assert_eq!(throttle.limit(8, 10, &settings, &Readings::default()), 8);
+    let settings = MiningSettings {
+        throttle_on_battery: false,
+        ..settings
+    };
+    assert_eq!(throttle.limit(8, 10, &settings, &on_battery), 8);
+}
+
+#[test]
+fn heat_sheds_threads_until_cool() {
+    let settings = MiningSettings::default();
+    let mut throttle = Throttle::default();
+    assert_eq!(throttle.limit(8, 8, &settings, &hot(86.0)), 4);
+    assert_eq!(throttle.limit(8, 8, &settings, &hot(90.0)), 2);
+    assert_eq!(throttle.limit(8, 8, &settings, &hot(85.0)), 1);
+    assert_eq!(throttle.limit(8, 8, &settings, &hot(85.0)), 1);
+    // Within the hysteresis, threads are neither shed nor added
+    assert_eq!(throttle.limit(8, 8, &settings, &hot(82.0)), 1);
+    let limits: Vec<_> = (0..8)
+        .map(|_| throttle.limit(8, 8, &settings, &hot(60.0)))
+        .collect();
+    assert_eq!(limits, [2, 3, 4, 5, 6, 7, 8, 8]);
+}
+
+#[test]
+fn temperature_limit_can_be_disabled() {
+    let settings = MiningSettings {
+        max_temperature: 0,
+        ..Default::default()
+    };
+    let mut throttle = Throttle::default();
+    assert_eq!(throttle.limit(8, 8, &settings, &hot(100.0)), 8);
+}
+
+#[test]
+fn pool_finds_valid_solutions() {
+    let pool = pool(2);
+    let signer = Pubkey::new_unique();
+    let mut difficulty = [255; 32];
+    difficulty[0] = 0;
+    let difficulty = KeccakHash::new_from_array(difficulty);
+    // The same threads serve one challenge after another
+    for _ in 0..3 {
+        let challenge = KeccakHash::new_unique();
+        let res = block_on(pool.mine(challenge, difficulty, signer).recv()).unwrap();
+        assert!(validate(&challenge, &signer, &difficulty, &res));
+    }
+}
+
+#[test]
+fn replaced_job_is_abandoned() {
+    let pool = pool(2);
+    let impossible = KeccakHash::default();
+    let first = pool.mine(KeccakHash::new_unique(), impossible, Pubkey::new_unique());
+    let second = pool.mine(KeccakHash::new_unique(), impossible, Pubkey::new_unique());
+    assert!(block_on(first.recv()).is_err());
+    pool.cancel();
+    assert!(block_on(second.recv()).is_err());
+}
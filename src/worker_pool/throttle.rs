@@ -0,0 +1,112 @@
+use std::{fs, path::Path};
+
+use super::threads_for;
+use crate::miner::MiningSettings;
+
+/// Where Linux exposes power supplies and thermal zones. Missing on other platforms, which are never throttled.
+pub const SYSFS_ROOT: &str = "/sys";
+
+/// Degrees the cpu must cool below the limit before threads are added back.
+const HYSTERESIS: f32 = 5.0;
+
+/// Readings above this are sensor errors, not temperatures.
+const MAX_PLAUSIBLE_TEMPERATURE: f32 = 150.0;
+
+/// What the machine reports about its power and heat.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Readings {
+    pub on_battery: bool,
+    /// Hottest thermal zone, in degrees Celsius.
+    pub temperature: Option<f32>,
+}
+
+impl Readings {
+    pub fn read(sysfs: &Path) -> Self {
+        Self {
+            on_battery: read_on_battery(sysfs),
+            temperature: read_temperature(sysfs),
+        }
+    }
+}
+
+/// Runs on battery when a charger is present but offline, or a battery is discharging.
+fn read_on_battery(sysfs: &Path) -> bool {
+    let Ok(supplies) = fs::read_dir(sysfs.join("class/power_supply")) else {
+        return false;
+    };
+    let mut charger_online = None;
+    let mut discharging = false;
+    for supply in supplies.flatten() {
+        let path = supply.path();
+        match read_trimmed(&path.join("type")).as_deref() {
+            Some("Mains") | Some("USB") => {
+                let online = read_trimmed(&path.join("online")).as_deref().eq(&Some("1"));
+                charger_online = Some(charger_online.unwrap_or(false) || online);
+            }
+            Some("Battery") => {
+                discharging |= read_trimmed(&path.join("status"))
+                    .as_deref()
+                    .eq(&Some("Discharging"));
+            }
+            _ => {}
+        }
+    }
+    charger_online.eq(&Some(false)) || discharging
+}
+
+fn read_temperature(sysfs: &Path) -> Option<f32> {
+    let zones = fs::read_dir(sysfs.join("class/thermal")).ok()?;
+    zones
+        .flatten()
+        .filter(|zone| {
+            zone.file_name()
+                .to_string_lossy()
+                .starts_with("thermal_zone")
+        })
+        .filter_map(|zone| read_trimmed(&zone.path().join("temp"))?.parse::<i64>().ok())
+        // Zones report millidegrees
+        .map(|millidegrees| millidegrees as f32 / 1000.0)
+        .filter(|degrees| degrees.gt(&0.0) && degrees.lt(&MAX_PLAUSIBLE_TEMPERATURE))
+        .reduce(f32::max)
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+/// Decides how many threads may hash. Keeps state between readings, so a hot cpu sheds
+/// threads step by step and only gets them back once it has cooled down.
+#[derive(Debug, Default)]
+pub struct Throttle {
+    heat_limit: Option<usize>,
+}
+
+impl Throttle {
+    pub fn limit(
+        &mut self,
+        threads: usize,
+        cpus: usize,
+        settings: &MiningSettings,
+        readings: &Readings,
+    ) -> usize {
+        let mut limit = threads;
+        if settings.throttle_on_battery && readings.on_battery {
+            limit = limit.min(threads_for(settings.battery_power_level, cpus));
+        }
+        self.heat_limit = match readings.temperature {
+            Some(temperature) if settings.max_temperature.gt(&0) => {
+                let max = settings.max_temperature as f32;
+                let current = self.heat_limit.unwrap_or(limit).min(limit);
+                if temperature.ge(&max) {
+                    Some((current / 2).max(1))
+                } else if temperature.lt(&(max - HYSTERESIS)) {
+                    (current + 1).lt(&limit).then_some(current + 1)
+                } else {
+                    self.heat_limit
+                }
+            }
+            _ => None,
+        };
+        limit.min(self.heat_limit.unwrap_or(limit))
+    }
+}